@@ -5,7 +5,20 @@ use std::{
 
 use anyhow::{anyhow, Context};
 pub use history::Proof;
-use homotopy_core::common::Mode;
+use homotopy_core::{common::Mode, Diagram};
+use homotopy_graphics::{
+    animation::AnimationOptions,
+    figure::{self, FigureOptions},
+    gltf,
+    manim::{self, StepOptions},
+    mesh::MeshOptions,
+    obj, pdf,
+    raster::{self, Camera, Lighting, RasterOptions},
+    strip::StripOptions,
+    style::{SignatureStyleData, Theme},
+    svg::{self, SvgOptions},
+    tikz,
+};
 pub use homotopy_model::{history, migration, proof, proof::Action, serialize};
 use structopt::StructOpt;
 
@@ -27,6 +40,136 @@ struct Opt {
 
     #[structopt(short, long)]
     no_replay_crash: bool,
+
+    /// Export the animated workspace as OBJ files `<prefix>_<frame>.obj` sharing `<prefix>.mtl`
+    #[structopt(long, parse(from_os_str))]
+    output_obj_sequence: Option<PathBuf>,
+
+    /// Export the animated workspace as a binary glTF file with morph target animation
+    #[structopt(long, parse(from_os_str))]
+    output_gltf: Option<PathBuf>,
+
+    /// Number of frames sampled from the animation, at least one
+    #[structopt(long, default_value = "30", parse(try_from_str = parse_frames))]
+    frames: usize,
+
+    /// Length of the glTF animation in seconds
+    #[structopt(long, default_value = "5")]
+    duration: f32,
+
+    /// Rounds of subdivision applied to the animated geometry
+    #[structopt(long, default_value = "2")]
+    subdivision_depth: u8,
+
+    /// Samples used to inflate wires and points
+    #[structopt(long, default_value = "3")]
+    geometry_samples: u8,
+
+    /// Render the 2- or 3-dimensional workspace to a PNG image
+    #[structopt(long, parse(from_os_str))]
+    output_png: Option<PathBuf>,
+
+    /// Export the 2-dimensional workspace as a vector PDF figure
+    #[structopt(long, parse(from_os_str))]
+    output_pdf: Option<PathBuf>,
+
+    /// Export the workspace as TikZ, projected from the camera if it is 3-dimensional
+    #[structopt(long, parse(from_os_str))]
+    output_tikz: Option<PathBuf>,
+
+    /// Draw 3-dimensional TikZ pictures as a grid of their slices instead
+    #[structopt(long)]
+    tikz_slices: bool,
+
+    /// Number of columns in the grid of slices, which is roughly square by default
+    #[structopt(long)]
+    tikz_columns: Option<usize>,
+
+    /// Export the slices of the workspace side by side as an SVG image
+    #[structopt(long, parse(from_os_str))]
+    output_strip_svg: Option<PathBuf>,
+
+    /// Export the slices of the workspace side by side as TikZ
+    #[structopt(long, parse(from_os_str))]
+    output_strip_tikz: Option<PathBuf>,
+
+    /// Number of times the workspace is sliced for strips
+    #[structopt(long, default_value = "1")]
+    strip_depth: usize,
+
+    /// Leave out the singular slices of strips
+    #[structopt(long)]
+    regular_slices_only: bool,
+
+    /// Export a Manim scene stepping through the slices of the workspace
+    #[structopt(long, parse(from_os_str))]
+    output_manim: Option<PathBuf>,
+
+    /// Step through the workspace after every replayed action in the Manim scene instead
+    #[structopt(long)]
+    manim_steps: bool,
+
+    /// Export the workspace as a standalone SVG image
+    #[structopt(long, parse(from_os_str))]
+    output_svg: Option<PathBuf>,
+
+    /// Draw generator labels in SVG, TikZ and Manim output
+    #[structopt(long)]
+    show_labels: bool,
+
+    /// Animate 3-dimensional SVG images through their slices, with this many seconds per slice
+    #[structopt(long)]
+    slice_duration: Option<f32>,
+
+    /// Resolution of PNG images of 2-dimensional views
+    #[structopt(long, default_value = "300")]
+    dpi: f32,
+
+    /// Space around 2-dimensional figures in points
+    #[structopt(long, default_value = "0")]
+    padding: f32,
+
+    #[structopt(long, default_value = "800")]
+    image_width: u32,
+
+    #[structopt(long, default_value = "600")]
+    image_height: u32,
+
+    /// Horizontal angle of the camera in radians
+    #[structopt(long)]
+    camera_phi: Option<f32>,
+
+    /// Vertical angle of the camera in radians
+    #[structopt(long)]
+    camera_theta: Option<f32>,
+
+    #[structopt(long)]
+    camera_distance: Option<f32>,
+
+    #[structopt(long, default_value = "0.25")]
+    specularity: f32,
+
+    #[structopt(long, default_value = "64")]
+    shininess: f32,
+
+    #[structopt(long, default_value = "2.2")]
+    gamma: f32,
+
+    /// Leave the background of the image transparent, unless the theme fills it in
+    #[structopt(long)]
+    transparent: bool,
+
+    /// Colours of exported images: light, dark or print
+    #[structopt(long, default_value = "light")]
+    theme: Theme,
+}
+
+/// Animations without frames would be exported as empty and invalid files.
+fn parse_frames(frames: &str) -> anyhow::Result<usize> {
+    match frames.parse()? {
+        0 => Err(anyhow!("there must be at least one frame")),
+        frames => Ok(frames),
+    }
 }
 
 fn import_actions(path: &PathBuf) -> anyhow::Result<(Vec<Action>, Option<Action>)> {
@@ -43,7 +186,7 @@ fn import_actions(path: &PathBuf) -> anyhow::Result<(Vec<Action>, Option<Action>
 
 fn import_hom(path: &PathBuf) -> anyhow::Result<Proof> {
     let data = read(path)?;
-    let ((signature, workspace), metadata) = match serialize::deserialize(&data) {
+    let ((signature, tabs, shelf), metadata) = match serialize::deserialize(&data) {
         Some(res) => res,
         None => migration::deserialize(&data)
             .context("Failed to deserialize or migrate from legacy format.")?,
@@ -54,28 +197,236 @@ fn import_hom(path: &PathBuf) -> anyhow::Result<Proof> {
             .check(Mode::Deep)
             .map_err(|e| anyhow!("Signature diagram deep check failed: {:?}", e))?;
     }
-    if let Some(w) = workspace.as_ref() {
+    for w in tabs.iter().filter_map(|tab| tab.workspace.as_ref()) {
         w.diagram
             .check(Mode::Deep)
             .map_err(|e| anyhow!("Workspace diagram deep check failed: {:?}", e))?;
     }
+    for item in shelf.iter() {
+        item.diagram
+            .check(Mode::Deep)
+            .map_err(|e| anyhow!("Shelf diagram deep check failed: {:?}", e))?;
+    }
 
     let mut proof: Proof = Default::default();
     proof.signature = signature;
-    proof.workspace = workspace;
+    proof.shelf = shelf;
     proof.metadata = metadata;
+    proof.set_tabs(tabs);
     Ok(proof)
 }
 
 fn export_hom(path: &PathBuf, proof: &Proof) -> anyhow::Result<()> {
     let data = serialize::serialize(
         proof.signature.clone(),
-        proof.workspace.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
         proof.metadata.clone(),
     );
     write(path, data).context("Could not export .hom file.")
 }
 
+fn visible_diagram(proof: &Proof) -> anyhow::Result<Diagram> {
+    Ok(proof
+        .workspace
+        .as_ref()
+        .context("There is no workspace to export.")?
+        .visible_diagram())
+}
+
+fn animated_diagram(proof: &Proof) -> anyhow::Result<Diagram> {
+    let diagram = visible_diagram(proof)?;
+    if diagram.dimension() < 3 {
+        return Err(anyhow!("Only 3- and 4-dimensional views can be animated."));
+    }
+    Ok(diagram)
+}
+
+fn export_obj_sequence(
+    prefix: &PathBuf,
+    proof: &Proof,
+    options: AnimationOptions,
+) -> anyhow::Result<()> {
+    let diagram = animated_diagram(proof)?;
+    let material_library = prefix.with_extension("mtl");
+    let (frames, materials) = obj::render_sequence(
+        &diagram,
+        &proof.signature,
+        options,
+        &material_library
+            .file_name()
+            .context("Invalid output path.")?
+            .to_string_lossy(),
+    )?;
+
+    let stem = prefix
+        .file_name()
+        .context("Invalid output path.")?
+        .to_string_lossy();
+    for (i, frame) in frames.iter().enumerate() {
+        let path = prefix.with_file_name(format!("{stem}_{i:03}.obj"));
+        write(path, frame).context("Could not export .obj file.")?;
+    }
+    write(material_library, materials).context("Could not export .mtl file.")
+}
+
+fn export_gltf(path: &PathBuf, proof: &Proof, options: AnimationOptions) -> anyhow::Result<()> {
+    let diagram = animated_diagram(proof)?;
+    let data = gltf::render_animation(&diagram, &proof.signature, options)?;
+    write(path, data).context("Could not export .glb file.")
+}
+
+fn export_png(
+    path: &PathBuf,
+    proof: &Proof,
+    options: &RasterOptions,
+    figure_options: FigureOptions,
+    dpi: f32,
+) -> anyhow::Result<()> {
+    let diagram = visible_diagram(proof)?;
+    let data = match diagram.dimension() {
+        2 => figure::render(&diagram, &proof.signature, figure_options, dpi),
+        _ => raster::render(
+            &diagram,
+            &proof.signature,
+            MeshOptions {
+                theme: options.theme,
+                ..Default::default()
+            },
+            options,
+        ),
+    }
+    .map_err(|_err| anyhow!("Only 2- and 3-dimensional views can be rendered."))?;
+    write(path, data).context("Could not export .png file.")
+}
+
+fn export_pdf(path: &PathBuf, proof: &Proof, options: FigureOptions) -> anyhow::Result<()> {
+    let diagram = visible_diagram(proof)?;
+    if diagram.dimension() != 2 {
+        return Err(anyhow!("Only 2-dimensional views can be exported as PDF."));
+    }
+    let data = pdf::render(&diagram, &proof.signature, options)
+        .map_err(|_err| anyhow!("Only 2-dimensional views can be exported as PDF."))?;
+    write(path, data).context("Could not export .pdf file.")
+}
+
+fn export_tikz(
+    path: &PathBuf,
+    proof: &Proof,
+    slices: Option<Option<usize>>,
+    mesh: MeshOptions,
+    camera: &Camera,
+    show_labels: bool,
+) -> anyhow::Result<()> {
+    let diagram = visible_diagram(proof)?;
+    let data = match (diagram.dimension(), slices) {
+        (2, _) => tikz::render(
+            &diagram,
+            &proof.signature,
+            false,
+            true,
+            show_labels,
+            mesh.theme,
+        ),
+        (3, Some(columns)) => tikz::render_slices(
+            &diagram,
+            &proof.signature,
+            false,
+            true,
+            show_labels,
+            columns,
+            mesh.theme,
+        ),
+        (3, None) => tikz::render_projected(&diagram, &proof.signature, mesh, camera, 10.),
+        _ => {
+            return Err(anyhow!(
+                "Only 2- and 3-dimensional views can be exported as TikZ."
+            ))
+        }
+    }
+    .map_err(|_err| anyhow!("Could not render TikZ picture."))?;
+    write(path, data).context("Could not export .tikz file.")
+}
+
+fn export_strip(
+    svg_path: Option<&PathBuf>,
+    tikz_path: Option<&PathBuf>,
+    proof: &Proof,
+    strip: &StripOptions,
+    options: &SvgOptions,
+) -> anyhow::Result<()> {
+    let diagram = visible_diagram(proof)?;
+    if let Some(path) = svg_path {
+        let data = svg::export_strip(&diagram, &proof.signature, strip, options)
+            .map_err(|_err| anyhow!("Could not render the slices of the workspace."))?;
+        write(path, data).context("Could not export .svg file.")?;
+    }
+    if let Some(path) = tikz_path {
+        let data = tikz::render_strip(
+            &diagram,
+            &proof.signature,
+            strip,
+            false,
+            true,
+            options.show_labels,
+            options.figure.theme,
+        )
+        .map_err(|_err| anyhow!("Could not render the slices of the workspace."))?;
+        write(path, data).context("Could not export .tikz file.")?;
+    }
+    Ok(())
+}
+
+fn export_svg(path: &PathBuf, proof: &Proof, options: &SvgOptions) -> anyhow::Result<()> {
+    let diagram = visible_diagram(proof)?;
+    let data = svg::export(&diagram, &proof.signature, options)
+        .map_err(|_err| anyhow!("Only views of dimension 2 or more can be exported as SVG."))?;
+    write(path, data).context("Could not export .svg file.")
+}
+
+fn export_manim(
+    path: &PathBuf,
+    proof: &Proof,
+    steps: Option<&[Diagram]>,
+    show_labels: bool,
+    theme: Theme,
+) -> anyhow::Result<()> {
+    let stylesheet = manim::stylesheet(&proof.signature, theme);
+    let data = match steps {
+        Some(diagrams) => manim::render_steps(
+            diagrams,
+            &proof.signature,
+            &stylesheet,
+            false,
+            show_labels,
+            StepOptions::default(),
+        )
+        .map_err(|_err| anyhow!("Could not lay out the steps of the proof."))?,
+        None => manim::render_slices(
+            &visible_diagram(proof)?,
+            &proof.signature,
+            &stylesheet,
+            false,
+            show_labels,
+            StepOptions::default(),
+        )
+        .map_err(|_err| anyhow!("Only views of dimension 3 or more can be animated."))?,
+    };
+    write(path, data).context("Could not export .py file.")
+}
+
+/// Record the visible diagram of the workspace, unless it is the same as the last one recorded.
+fn record_step(steps: &mut Vec<Diagram>, proof: &Proof) {
+    if let Some(ws) = proof.workspace.as_ref() {
+        if ws.visible_dimension() >= 2 {
+            let diagram = ws.visible_diagram();
+            if steps.last() != Some(&diagram) {
+                steps.push(diagram);
+            }
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     // Give me options.
     let opt = Opt::from_args();
@@ -89,9 +440,13 @@ fn main() -> anyhow::Result<()> {
         None => Default::default(),
     };
 
+    let mut steps = Vec::new();
+    record_step(&mut steps, &proof);
+
     for a in &actions {
         println!("Performing action: {a:?}");
         proof.update(a)?;
+        record_step(&mut steps, &proof);
     }
 
     if !opt.no_replay_crash {
@@ -99,6 +454,7 @@ fn main() -> anyhow::Result<()> {
             println!("Performing final action: {a:?}");
             // When debugging, set a breakpoint here!
             proof.update(&a)?;
+            record_step(&mut steps, &proof);
         }
     }
 
@@ -106,5 +462,114 @@ fn main() -> anyhow::Result<()> {
         export_hom(&path, &proof)?;
     }
 
+    let animation = AnimationOptions {
+        mesh: MeshOptions {
+            subdivision_depth: opt.subdivision_depth,
+            samples: opt.geometry_samples,
+            theme: opt.theme,
+        },
+        frames: opt.frames,
+        duration: opt.duration,
+    };
+
+    if let Some(prefix) = opt.output_obj_sequence {
+        export_obj_sequence(&prefix, &proof, animation)?;
+    }
+
+    if let Some(path) = opt.output_gltf {
+        export_gltf(&path, &proof, animation)?;
+    }
+
+    let figure_options = FigureOptions {
+        padding: opt.padding,
+        background: (!opt.transparent).then(|| opt.theme.paper()),
+        theme: opt.theme,
+        ..Default::default()
+    };
+
+    let svg_options = SvgOptions {
+        figure: figure_options.clone(),
+        show_labels: opt.show_labels,
+        slice_duration: opt.slice_duration,
+        ..Default::default()
+    };
+
+    if let Some(path) = opt.output_svg {
+        export_svg(&path, &proof, &svg_options)?;
+    }
+
+    if opt.output_strip_svg.is_some() || opt.output_strip_tikz.is_some() {
+        let strip = StripOptions {
+            depth: opt.strip_depth,
+            singular_slices: !opt.regular_slices_only,
+            ..Default::default()
+        };
+        export_strip(
+            opt.output_strip_svg.as_ref(),
+            opt.output_strip_tikz.as_ref(),
+            &proof,
+            &strip,
+            &svg_options,
+        )?;
+    }
+
+    if let Some(path) = opt.output_manim {
+        // Generators removed along the way are not styled any more.
+        steps.retain(|diagram| {
+            diagram
+                .generators()
+                .into_iter()
+                .all(|g| proof.signature.generator_style(g).is_some())
+        });
+        export_manim(
+            &path,
+            &proof,
+            opt.manim_steps.then_some(steps.as_slice()),
+            opt.show_labels,
+            opt.theme,
+        )?;
+    }
+
+    if let Some(path) = opt.output_pdf {
+        export_pdf(&path, &proof, figure_options.clone())?;
+    }
+
+    let default = Camera::default();
+    let camera = Camera {
+        phi: opt.camera_phi.unwrap_or(default.phi),
+        theta: opt.camera_theta.unwrap_or(default.theta),
+        distance: opt.camera_distance.unwrap_or(default.distance),
+        ..default
+    };
+
+    if let Some(path) = opt.output_tikz {
+        export_tikz(
+            &path,
+            &proof,
+            opt.tikz_slices.then_some(opt.tikz_columns),
+            animation.mesh,
+            &camera,
+            opt.show_labels,
+        )?;
+    }
+
+    if let Some(path) = opt.output_png {
+        let options = RasterOptions {
+            width: opt.image_width,
+            height: opt.image_height,
+            camera,
+            lighting: Lighting {
+                specularity: opt.specularity,
+                shininess: opt.shininess,
+                gamma: opt.gamma,
+                disable_lighting: false,
+            },
+            transparent: opt.transparent,
+            theme: opt.theme,
+            ..Default::default()
+        };
+        export_png(&path, &proof, &options, figure_options, opt.dpi)?;
+    }
+
     Ok(())
 }
@@ -326,6 +326,23 @@ impl Rewrite {
     }
 }
 
+impl DiagramN {
+    /// The singular heights at which the cospans of two diagrams differ modulo labels, including
+    /// the heights that only one of the diagrams has.
+    #[must_use]
+    pub fn differing_heights(&self, other: &DiagramN) -> Vec<usize> {
+        let (cospans, other_cospans) = (self.cospans(), other.cospans());
+        (0..cospans.len().max(other_cospans.len()))
+            .filter(|&height| {
+                !matches!(
+                    (cospans.get(height), other_cospans.get(height)),
+                    (Some(cospan), Some(other)) if cospan.equals_modulo_labels(other)
+                )
+            })
+            .collect()
+    }
+}
+
 impl Cospan {
     #[must_use]
     pub fn equals_modulo_labels(&self, other: &Cospan) -> bool {
@@ -6,7 +6,7 @@ use std::{
 };
 
 use hashconsing::{HConsed, HConsign, HashConsign};
-use homotopy_common::hash::FastHashSet;
+use homotopy_common::hash::{FastHashMap, FastHashSet};
 use once_cell::unsync::OnceCell;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -182,6 +182,22 @@ impl Diagram {
         }
     }
 
+    /// Renumber the generators mentioned by this diagram, leaving those missing from `map` as
+    /// they are. The map should preserve dimensions.
+    #[must_use]
+    pub fn replace_generators(&self, map: &FastHashMap<Generator, Generator>) -> Self {
+        match self {
+            Self::Diagram0(g) => Self::Diagram0(g.replace_generators(map)),
+            Self::DiagramN(d) => Self::DiagramN(DiagramN::new_unsafe(
+                d.source().replace_generators(map),
+                d.cospans()
+                    .iter()
+                    .map(|cs| cs.map(|r| r.replace_generators(map)))
+                    .collect(),
+            )),
+        }
+    }
+
     pub fn is_invertible(&self, signature: &impl Signature) -> bool {
         self.generators()
             .iter()
@@ -221,6 +237,14 @@ impl Diagram0 {
     pub fn orientation_transform(self, k: Orientation) -> Self {
         Self::new(self.generator, self.orientation * k)
     }
+
+    #[must_use]
+    pub fn replace_generators(self, map: &FastHashMap<Generator, Generator>) -> Self {
+        Self::new(
+            map.get(&self.generator).copied().unwrap_or(self.generator),
+            self.orientation,
+        )
+    }
 }
 
 impl From<Generator> for Diagram0 {
@@ -64,6 +64,21 @@ impl<const N: usize> Layout<N> {
     }
 }
 
+impl Layout<3> {
+    /// The layout of a slice of the diagram, taken from the layout of the whole diagram.
+    ///
+    /// Unlike the layout computed for the slice on its own, positions agree between neighbouring
+    /// slices, so that wires and points stay in place unless the diagram moves them.
+    pub fn slice(&self, index: SliceIndex) -> Layout<2> {
+        Layout(
+            self.iter()
+                .filter(|(key, _)| key[0] == index)
+                .map(|([_, y, x], [px, py, _])| ([*y, *x], [*px, *py]))
+                .collect(),
+        )
+    }
+}
+
 pub type Point = (NodeIndex, SingularHeight);
 
 #[derive(Clone, Debug, Default)]
@@ -282,6 +282,14 @@ impl Rewrite {
             Self::RewriteN(r) => Self::RewriteN(r.remove_framing(generator)),
         }
     }
+
+    #[must_use]
+    pub fn replace_generators(&self, map: &FastHashMap<Generator, Generator>) -> Self {
+        match self {
+            Self::Rewrite0(r) => Self::Rewrite0(r.replace_generators(map)),
+            Self::RewriteN(r) => Self::RewriteN(r.replace_generators(map)),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -375,6 +383,18 @@ impl Rewrite0 {
             }
         }
     }
+
+    #[must_use]
+    pub fn replace_generators(&self, map: &FastHashMap<Generator, Generator>) -> Self {
+        match &self.0 {
+            None => Self(None),
+            Some((source, target, label)) => Self::new(
+                source.replace_generators(map),
+                target.replace_generators(map),
+                label.clone(),
+            ),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -779,6 +799,34 @@ impl RewriteN {
 
         Self::new_unsafe(self.dimension(), cones)
     }
+
+    #[must_use]
+    pub fn replace_generators(&self, map: &FastHashMap<Generator, Generator>) -> Self {
+        let cones = self
+            .cones()
+            .iter()
+            .map(|c| {
+                Cone::new(
+                    c.index,
+                    c.source()
+                        .iter()
+                        .map(|cs| cs.map(|r| r.replace_generators(map)))
+                        .collect(),
+                    c.target().map(|r| r.replace_generators(map)),
+                    c.regular_slices()
+                        .iter()
+                        .map(|r| r.replace_generators(map))
+                        .collect(),
+                    c.singular_slices()
+                        .iter()
+                        .map(|r| r.replace_generators(map))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        Self::new_unsafe(self.dimension(), cones)
+    }
 }
 
 #[derive(Clone, Eq, Serialize, Deserialize)]
@@ -15,6 +15,15 @@ fn assoc_right() {
     assert_debug_snapshot!(examples::associator().1.target());
 }
 
+#[test]
+fn differing_heights() {
+    let (_, scalar) = examples::scalar();
+    let (_, two_scalars) = examples::two_scalars();
+    assert!(two_scalars.differing_heights(&two_scalars).is_empty());
+    assert_eq!(two_scalars.differing_heights(&scalar), vec![1]);
+    assert_eq!(scalar.differing_heights(&two_scalars), vec![1]);
+}
+
 // ensure diagram with (source, target)
 //  |
 //  m     |
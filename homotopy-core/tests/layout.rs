@@ -1,4 +1,4 @@
-use homotopy_core::{examples, layout::Layout};
+use homotopy_core::{examples, layout::Layout, SliceIndex};
 use insta::assert_debug_snapshot;
 
 #[test]
@@ -36,3 +36,15 @@ fn pants_unit() {
     let (_, diagram) = examples::pants_unit();
     assert_debug_snapshot!(Layout::<4>::new(&diagram.into()).unwrap());
 }
+
+#[test]
+fn associator_slices() {
+    let (_, diagram) = examples::associator();
+    let layout = Layout::<3>::new(&diagram.clone().into()).unwrap();
+    for (i, slice) in diagram.slices().enumerate() {
+        let sliced = layout.slice(SliceIndex::Interior(i.into()));
+        let own = Layout::<2>::new(&slice).unwrap();
+        assert_eq!(sliced.len(), own.len());
+        assert!(own.keys().all(|key| sliced.contains_key(key)));
+    }
+}
@@ -0,0 +1,69 @@
+use homotopy_core::{common::DimensionError, Diagram};
+
+use crate::{
+    geom::{CubicalGeometry, SimplicialGeometry},
+    mesh::{Mesh, MeshOptions},
+    style::SignatureStyleData,
+};
+
+/// Options controlling how the time-sliced view of a diagram is sampled for export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationOptions {
+    pub mesh: MeshOptions,
+    /// Number of evenly spaced time steps at which the diagram is sliced.
+    pub frames: usize,
+    /// Length of the whole animation in seconds.
+    pub duration: f32,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            // Subdividing 4-dimensional geometry is much more expensive than in 3 dimensions.
+            mesh: MeshOptions {
+                subdivision_depth: 2,
+                ..Default::default()
+            },
+            frames: 30,
+            duration: 5.,
+        }
+    }
+}
+
+/// Slice the animated view of a diagram at evenly spaced times, giving one mesh per frame.
+///
+/// 3-diagrams are animated through their last dimension in the plane, as in the WebGL view with
+/// 3D animation enabled, while 4-diagrams are animated through their last dimension in space.
+pub fn frames(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: AnimationOptions,
+) -> Result<Vec<Mesh>, DimensionError> {
+    let mut cubical = match diagram.dimension() {
+        3 => CubicalGeometry::new::<3>(diagram, true)?,
+        _ => CubicalGeometry::new::<4>(diagram, true)?,
+    };
+    cubical.subdivide(true, options.mesh.subdivision_depth);
+    let simplicial = SimplicialGeometry::from(cubical);
+
+    Ok(times(options.frames)
+        .map(|t| {
+            let mut slice = simplicial.slice(t);
+            slice.inflate_3d(options.mesh.samples, signature_styles);
+            Mesh::from_geometry(
+                &slice,
+                diagram.dimension(),
+                signature_styles,
+                options.mesh.theme,
+            )
+        })
+        .collect())
+}
+
+/// The times at which frames are sliced, in the normalised interval `[-1, 1]`.
+///
+/// Each frame is sampled at the middle of its share of the interval, which keeps the slices away
+/// from the source and target where the geometry is degenerate.
+fn times(frames: usize) -> impl Iterator<Item = f32> {
+    (0..frames).map(move |i| -1. + (2 * i + 1) as f32 / frames as f32)
+}
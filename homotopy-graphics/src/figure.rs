@@ -0,0 +1,481 @@
+use euclid::default::{Box2D, Transform2D};
+use homotopy_core::{
+    common::DimensionError,
+    complex::make_complex,
+    layout::Layout,
+    projection::{Depths, Projection},
+    Boundary, Diagram, Diagram0,
+};
+use lyon_path::{iterator::PathIterator, Event, Path};
+
+use crate::{
+    label::{place_labels, Label, LABEL_DISTANCE},
+    path_util::simplify_graphic,
+    png,
+    raster::downsample,
+    style::{
+        Color, GeneratorRepresentation, GeneratorStyle, SignatureStyleData, SurfaceStyle, Theme,
+        VertexShape, WireStyle,
+    },
+    svg::{
+        generator_color,
+        render::GraphicElement,
+        shape::{distance_to_line_segment, Point},
+    },
+};
+
+/// Width of the outline drawn around surfaces, which covers the hairline gaps between adjacent
+/// surfaces just like the default stroke in the SVG view.
+pub const SURFACE_OUTLINE: f32 = 1.;
+
+/// Number of samples taken along each axis of a pixel when rasterising, for antialiasing.
+const SUPERSAMPLING: u32 = 2;
+
+/// Maximal distance in pixels between a curve and its flattening.
+const FLATTENING_TOLERANCE: f32 = 0.1;
+
+/// Options controlling the size and appearance of a figure of a 2-dimensional diagram.
+///
+/// Lengths are given in points (1/72 of an inch) and default to the sizes of the SVG view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FigureOptions {
+    /// Length of one unit of the diagram layout.
+    pub scale: f32,
+    pub wire_thickness: f32,
+    pub point_radius: f32,
+    /// Empty space around the diagram on every side.
+    pub padding: f32,
+    /// Colour filling the whole figure, overriding the background of the theme. The figure is
+    /// left transparent if neither gives one.
+    pub background: Option<Color>,
+    pub theme: Theme,
+}
+
+impl Default for FigureOptions {
+    fn default() -> Self {
+        Self {
+            scale: 40.,
+            wire_thickness: 8.,
+            point_radius: 6.,
+            padding: 0.,
+            background: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl FigureOptions {
+    /// The colour filling the whole figure, if any.
+    pub fn background(&self) -> Option<Color> {
+        self.background.clone().or_else(|| self.theme.background())
+    }
+}
+
+/// An element of a figure, with its colour and style resolved from the signature styles.
+#[derive(Debug, Clone)]
+pub enum FigureElement {
+    /// A surface given by a closed path to be filled.
+    Surface(Diagram0, Color, SurfaceStyle, Path),
+    /// A wire given by a path to be stroked, together with the paths of the wires in front of
+    /// it. The wire is cut within one wire thickness of those paths, leaving a gap of half a wire
+    /// thickness on either side of the wires in front.
+    Wire(Diagram0, Color, WireStyle, Path, Vec<Path>),
+    Point(Diagram0, Color, VertexShape, Point),
+}
+
+/// The 2-dimensional graphic of a diagram laid out on a page, independent of the output format.
+///
+/// Coordinates are given in points, with the origin in the top left corner and the y-axis
+/// pointing down, as in the SVG view. Elements are to be drawn in order.
+#[derive(Debug, Clone)]
+pub struct Figure {
+    pub width: f32,
+    pub height: f32,
+    /// The dimension of the diagram the figure was drawn from.
+    pub dimension: usize,
+    pub options: FigureOptions,
+    pub elements: Vec<FigureElement>,
+    /// Labels of the generators in the figure, placed away from its points and wires.
+    pub labels: Vec<Label>,
+}
+
+impl Figure {
+    pub fn new(
+        diagram: &Diagram,
+        signature_styles: &impl SignatureStyleData,
+        options: FigureOptions,
+    ) -> Result<Self, DimensionError> {
+        let dimension = diagram.dimension();
+        let layout = Layout::<2>::new(diagram)?;
+        let complex = make_complex(diagram);
+        let depths = Depths::<2>::new(diagram)?;
+        let projection = Projection::<2>::new(diagram, &layout, &depths)?;
+        let graphic = GraphicElement::build(&complex, &layout, &projection, &depths);
+
+        let size = Point::from(layout[&[Boundary::Target.into(); 2]])
+            .max((1.0, 1.0).into())
+            .to_vector()
+            .to_size()
+            * options.scale;
+        let transform = Transform2D::scale(options.scale, -options.scale)
+            .then_translate((options.padding, size.height + options.padding).into());
+
+        let color = |diagram: Diagram0, representation| {
+            generator_color(
+                signature_styles,
+                diagram,
+                dimension,
+                representation,
+                options.theme,
+            )
+        };
+        let style = |diagram: Diagram0| signature_styles.generator_style(diagram.generator);
+
+        let elements = graphic
+            .iter()
+            .map(|element| match element.transformed(&transform) {
+                GraphicElement::Surface(g, path) => FigureElement::Surface(
+                    g,
+                    color(g, GeneratorRepresentation::Surface),
+                    style(g)
+                        .map(GeneratorStyle::surface_style)
+                        .unwrap_or_default(),
+                    path,
+                ),
+                GraphicElement::Wire(g, _, path, mask) => FigureElement::Wire(
+                    g,
+                    color(g, GeneratorRepresentation::Wire),
+                    style(g).map(GeneratorStyle::wire_style).unwrap_or_default(),
+                    path,
+                    mask,
+                ),
+                GraphicElement::Point(g, point) => {
+                    let shape = style(g).map(GeneratorStyle::shape).unwrap_or_default();
+                    let color = color(g, GeneratorRepresentation::Point);
+                    FigureElement::Point(g, color, shape, point)
+                }
+            })
+            .collect();
+
+        let labels = place_labels(
+            &simplify_graphic(&graphic),
+            signature_styles,
+            LABEL_DISTANCE,
+        )
+        .into_iter()
+        .map(|label| Label {
+            anchor: transform.transform_point(label.anchor),
+            position: transform.transform_point(label.position),
+            ..label
+        })
+        .collect();
+
+        Ok(Self {
+            width: size.width + 2. * options.padding,
+            height: size.height + 2. * options.padding,
+            dimension,
+            options,
+            elements,
+            labels,
+        })
+    }
+
+    /// The size of the figure in pixels at the given resolution in dots per inch.
+    pub fn pixel_size(&self, dpi: f32) -> (u32, u32) {
+        let scale = dpi / 72.;
+        (
+            (self.width * scale).ceil() as u32,
+            (self.height * scale).ceil() as u32,
+        )
+    }
+
+    /// Rasterise the figure at the given resolution in dots per inch into 8-bit RGBA pixels, row
+    /// by row, with the dimensions given by [`Figure::pixel_size`].
+    pub fn rasterize(&self, dpi: f32) -> Vec<u8> {
+        let (width, height) = self.pixel_size(dpi);
+        let scale = dpi / 72. * SUPERSAMPLING as f32;
+
+        let background = self.options.background().map_or([0.; 4], rgba);
+        let paper = rgba(self.options.theme.paper());
+        let mut canvas = Canvas {
+            width: width * SUPERSAMPLING,
+            height: height * SUPERSAMPLING,
+            pixels: vec![background; (width * height * SUPERSAMPLING * SUPERSAMPLING) as usize],
+        };
+
+        let transform = Transform2D::scale(scale, scale);
+        let wire_thickness = self.options.wire_thickness * scale;
+        let point_radius = self.options.point_radius * scale;
+
+        for element in &self.elements {
+            match element {
+                FigureElement::Surface(_, color, style, path) => {
+                    let segments = segments(&path.clone().transformed(&transform), true);
+                    let mut color = rgba(color.clone());
+                    color[3] = style.alpha();
+                    canvas.fill(&segments, color);
+                    // The outline would be blended twice where it overlaps the fill.
+                    if style.is_opaque() {
+                        canvas.stroke(&segments, SURFACE_OUTLINE * scale, &[], color);
+                    }
+                }
+                FigureElement::Wire(_, color, style, path, mask) => {
+                    let mask: Vec<_> = mask
+                        .iter()
+                        .flat_map(|path| segments(&path.clone().transformed(&transform), false))
+                        .collect();
+                    let width = wire_thickness * style.thickness_scale();
+                    let segments = dashed(
+                        segments(&path.clone().transformed(&transform), false),
+                        &style.dash_array(width),
+                    );
+                    canvas.stroke(&segments, width, &mask, rgba(color.clone()));
+                }
+                FigureElement::Point(_, color, shape, point) => {
+                    canvas.point(
+                        transform.transform_point(*point),
+                        point_radius,
+                        shape,
+                        rgba(color.clone()),
+                        paper,
+                    );
+                }
+            }
+        }
+
+        downsample(&canvas.pixels, width, height, SUPERSAMPLING)
+    }
+}
+
+/// Render a 2-dimensional diagram to a PNG file at the given resolution in dots per inch.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: FigureOptions,
+    dpi: f32,
+) -> Result<Vec<u8>, DimensionError> {
+    let figure = Figure::new(diagram, signature_styles, options)?;
+    let (width, height) = figure.pixel_size(dpi);
+    Ok(png::encode(width, height, &figure.rasterize(dpi)))
+}
+
+type Segment = (Point, Point);
+
+fn rgba(color: Color) -> [f32; 4] {
+    let (r, g, b) = color.into_components::<f32>();
+    [r, g, b, 1.]
+}
+
+/// Flatten a path into line segments. Subpaths of paths to be filled are always closed.
+fn segments(path: &Path, filled: bool) -> Vec<Segment> {
+    path.iter()
+        .flattened(FLATTENING_TOLERANCE)
+        .filter_map(|event| match event {
+            Event::Line { from, to } => Some((from, to)),
+            Event::End { last, first, close } if close || filled => Some((last, first)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Cut a polyline into dashes, given the alternating lengths of dashes and gaps.
+fn dashed(segments: Vec<Segment>, lengths: &[f32]) -> Vec<Segment> {
+    if lengths.is_empty() {
+        return segments;
+    }
+
+    let mut dashes = Vec::new();
+    let mut index = 0;
+    let mut remaining = lengths[0];
+    for (from, to) in segments {
+        let length = (to - from).length();
+        let at = |t: f32| from.lerp(to, if length > 0. { t / length } else { 0. });
+        let mut t = 0.;
+        while t < length {
+            let step = remaining.min(length - t);
+            if index % 2 == 0 {
+                dashes.push((at(t), at(t + step)));
+            }
+            t += step;
+            remaining -= step;
+            if remaining <= 0. {
+                index = (index + 1) % lengths.len();
+                remaining = lengths[index];
+            }
+        }
+    }
+    dashes
+}
+
+/// Source-over compositing of straight (not premultiplied) colours.
+fn blend(below: [f32; 4], above: [f32; 4]) -> [f32; 4] {
+    let alpha = above[3] + below[3] * (1. - above[3]);
+    if alpha <= 0. {
+        return [0.; 4];
+    }
+    let mut color = [0., 0., 0., alpha];
+    for ((channel, a), b) in color.iter_mut().zip(above).zip(below).take(3) {
+        *channel = (a * above[3] + b * below[3] * (1. - above[3])) / alpha;
+    }
+    color
+}
+
+/// A grid of samples, painted over with alpha blending.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    /// Paint every sample whose centre lies within the given box and satisfies the predicate.
+    fn paint(&mut self, bounds: Box2D<f32>, color: [f32; 4], inside: impl Fn(Point) -> bool) {
+        let rows =
+            bounds.min.y.floor().max(0.) as u32..(bounds.max.y.ceil() as u32).min(self.height);
+        let columns =
+            bounds.min.x.floor().max(0.) as u32..(bounds.max.x.ceil() as u32).min(self.width);
+
+        for row in rows {
+            for column in columns.clone() {
+                if inside(Point::new(column as f32 + 0.5, row as f32 + 0.5)) {
+                    let pixel = &mut self.pixels[(row * self.width + column) as usize];
+                    *pixel = blend(*pixel, color);
+                }
+            }
+        }
+    }
+
+    /// Fill the region enclosed by closed polygons with the non-zero winding rule, like SVG and
+    /// PDF do by default.
+    fn fill(&mut self, segments: &[Segment], color: [f32; 4]) {
+        let bounds = Box2D::from_points(segments.iter().flat_map(|&(from, to)| [from, to]));
+        let rows =
+            bounds.min.y.floor().max(0.) as u32..(bounds.max.y.ceil() as u32).min(self.height);
+
+        let mut crossings = Vec::new();
+        for row in rows {
+            let y = row as f32 + 0.5;
+
+            crossings.clear();
+            for &(from, to) in segments {
+                // Segments are half-open, so that shared endpoints are only crossed once.
+                if (from.y <= y) != (to.y <= y) {
+                    let x = from.x + (y - from.y) / (to.y - from.y) * (to.x - from.x);
+                    crossings.push((x, if from.y < to.y { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding != 0 {
+                    let start = (pair[0].0 - 0.5).ceil().max(0.) as u32;
+                    let end = ((pair[1].0 - 0.5).ceil().max(0.) as u32).min(self.width);
+                    for column in start..end {
+                        let pixel = &mut self.pixels[(row * self.width + column) as usize];
+                        *pixel = blend(*pixel, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Stroke line segments with round caps and joins, except where they come within one width
+    /// of a segment of the mask.
+    fn stroke(&mut self, segments: &[Segment], width: f32, mask: &[Segment], color: [f32; 4]) {
+        let radius = 0.5 * width;
+        for &(from, to) in segments {
+            let bounds = Box2D::from_points([from, to]).inflate(radius, radius);
+            self.paint(bounds, color, |point| {
+                distance_to_line_segment(point, from, to) <= radius
+                    && mask
+                        .iter()
+                        .all(|&(a, b)| distance_to_line_segment(point, a, b) > width)
+            });
+        }
+    }
+
+    /// Paint a vertex shape, filling hollow shapes with the paper colour.
+    fn point(
+        &mut self,
+        center: Point,
+        radius: f32,
+        shape: &VertexShape,
+        color: [f32; 4],
+        paper: [f32; 4],
+    ) {
+        let outline = VertexShape::OUTLINE * radius;
+        if let Some(corners) = shape.polygon() {
+            let corners: Vec<_> = corners
+                .into_iter()
+                .map(|[x, y]| center + euclid::vec2(x, -y) * radius)
+                .collect();
+            let edges: Vec<_> = corners
+                .iter()
+                .zip(corners.iter().cycle().skip(1))
+                .map(|(&from, &to)| (from, to))
+                .collect();
+            if shape.is_hollow() {
+                self.fill(&edges, paper);
+                self.stroke(&edges, outline, &[], color);
+            } else {
+                self.fill(&edges, color);
+            }
+        } else if shape.is_hollow() {
+            self.disc(center, radius + 0.5 * outline, color);
+            self.disc(center, radius - 0.5 * outline, paper);
+        } else {
+            self.disc(center, radius, color);
+        }
+    }
+
+    fn disc(&mut self, center: Point, radius: f32, color: [f32; 4]) {
+        let bounds = Box2D::new(center, center).inflate(radius, radius);
+        self.paint(bounds, color, |point| (point - center).length() <= radius);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dashed, Canvas, Point, Segment};
+
+    fn segment(from: (f32, f32), to: (f32, f32)) -> Segment {
+        (Point::new(from.0, from.1), Point::new(to.0, to.1))
+    }
+
+    #[test]
+    fn dashed_across_segments() {
+        let segments = vec![segment((0., 0.), (4., 0.)), segment((4., 0.), (12., 0.))];
+        assert_eq!(
+            dashed(segments, &[3., 2.]),
+            vec![
+                segment((0., 0.), (3., 0.)),
+                segment((5., 0.), (8., 0.)),
+                segment((10., 0.), (12., 0.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn stroke_mask_gap() {
+        let mut canvas = Canvas {
+            width: 40,
+            height: 1,
+            pixels: vec![[0.; 4]; 40],
+        };
+        canvas.stroke(
+            &[segment((0., 0.5), (40., 0.5))],
+            4.,
+            &[segment((20., -10.), (20., 10.))],
+            [1.; 4],
+        );
+
+        // A wire of the same width in front covers half of the cut, leaving a gap of half the
+        // width on either side of it.
+        for (column, pixel) in canvas.pixels.iter().enumerate() {
+            let x = column as f32 + 0.5;
+            assert_eq!(pixel[3] > 0., (x - 20.).abs() > 4.);
+        }
+    }
+}
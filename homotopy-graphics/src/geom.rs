@@ -4,7 +4,12 @@ use std::{
     mem,
 };
 
-use homotopy_common::{declare_idx, hash::FastHashMap, idx::IdxVec};
+use homotopy_common::{
+    declare_idx,
+    hash::{FastHashMap, FastHashSet},
+    idx::IdxVec,
+    parity,
+};
 use homotopy_core::{
     common::DimensionError, layout::Layout, mesh::Mesh, Diagram, Diagram0, SliceIndex,
 };
@@ -323,12 +328,16 @@ impl From<CubicalGeometry> for SimplicialGeometry {
 // If we every get geometry shaders (via WebGPU) all of this can go
 // and be replaced by real-time geometry synthesis.
 
+const TUBE_RADIUS: f32 = 0.05;
+
 impl SimplicialGeometry {
     pub fn inflate_point_3d(&mut self, point: Vert, samples: u8, generator_shape: &VertexShape) {
-        use VertexShape::{Circle, Square};
-        match generator_shape {
-            Circle => self.inflate_point_3d_sphere(point, samples),
+        use VertexShape::{Diamond, Square, Triangle};
+        match generator_shape.solid() {
             Square => self.inflate_point_3d_cube(point),
+            Diamond => self.inflate_point_3d_octahedron(point),
+            Triangle => self.inflate_point_3d_tetrahedron(point),
+            _ => self.inflate_point_3d_sphere(point, samples),
         }
     }
 
@@ -437,6 +446,58 @@ impl SimplicialGeometry {
         mk_area(self, [0, 5, 1], &mut used);
     }
 
+    fn inflate_point_3d_octahedron(&mut self, point: Vert) {
+        const R: f32 = 0.12;
+
+        let corners = [
+            Vec3::new(R, 0., 0.),
+            Vec3::new(-R, 0., 0.),
+            Vec3::new(0., R, 0.),
+            Vec3::new(0., -R, 0.),
+            Vec3::new(0., 0., R),
+            Vec3::new(0., 0., -R),
+        ];
+        let faces = [
+            [0, 2, 4],
+            [0, 2, 5],
+            [0, 3, 4],
+            [0, 3, 5],
+            [1, 2, 4],
+            [1, 2, 5],
+            [1, 3, 4],
+            [1, 3, 5],
+        ];
+        self.inflate_point_3d_polyhedron(point, &corners, &faces);
+    }
+
+    fn inflate_point_3d_tetrahedron(&mut self, point: Vert) {
+        // Corners at a distance of 0.13 from the centre
+        const R: f32 = 0.075;
+
+        let corners = [
+            Vec3::new(R, R, R),
+            Vec3::new(R, -R, -R),
+            Vec3::new(-R, R, -R),
+            Vec3::new(-R, -R, R),
+        ];
+        let faces = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]];
+        self.inflate_point_3d_polyhedron(point, &corners, &faces);
+    }
+
+    /// Inflate a point into a convex polyhedron with flat faces, given by its corners around the
+    /// origin and its triangles. Triangles are wound the same way as those of the cube, whatever
+    /// their given order.
+    fn inflate_point_3d_polyhedron(&mut self, point: Vert, corners: &[Vec3], faces: &[[usize; 3]]) {
+        for &[a, b, c] in faces {
+            let [a, b, c] = [corners[a], corners[b], corners[c]];
+            let inwards = (b - a).cross(c - a).dot(a + b + c) < 0.;
+            let [b, c] = if inwards { [b, c] } else { [c, b] };
+            // Each face gets its own vertices for flat normals.
+            let verts = [a, b, c].map(|corner| self.mk_displaced_copy(point, corner.into()));
+            self.mk_area(verts, Parity::Even);
+        }
+    }
+
     fn inflate_tube_segment(
         &mut self,
         vert: Vert,
@@ -444,18 +505,17 @@ impl SimplicialGeometry {
         binormal: Vec3,
         connect: Option<Parity>,
         sectors: u8,
+        radius: f32,
     ) {
         use homotopy_common::idx::Idx;
 
-        const TUBE_RADIUS: f32 = 0.05;
-
         let len = self.verts.len();
 
         for j in 0..sectors {
             let theta = f32::from(j) * TAU / f32::from(sectors);
             self.mk_displaced_copy(
                 vert,
-                (TUBE_RADIUS * (f32::cos(theta) * normal + f32::sin(theta) * binormal)).into(),
+                (radius * (f32::cos(theta) * normal + f32::sin(theta) * binormal)).into(),
             );
         }
 
@@ -479,7 +539,7 @@ impl SimplicialGeometry {
         }
     }
 
-    fn inflate_curve_3d(&mut self, curve: Curve, samples: u8) {
+    fn inflate_curve_3d(&mut self, curve: Curve, samples: u8, radius: f32) {
         let mut verts = vec![];
         let mut parities = vec![];
         let sectors = samples;
@@ -500,7 +560,7 @@ impl SimplicialGeometry {
         })
         .normalized();
 
-        self.inflate_tube_segment(verts[0], n, d_0.cross(n), None, sectors);
+        self.inflate_tube_segment(verts[0], n, d_0.cross(n), None, sectors, radius);
 
         for i in 2..verts.len() {
             let v_0 = verts[i - 1];
@@ -514,10 +574,10 @@ impl SimplicialGeometry {
             n = t.cross(n).cross(t).normalized();
             let bn = t.cross(n).normalized();
 
-            self.inflate_tube_segment(v_0, n, bn, Some(parities[i - 2]), sectors);
+            self.inflate_tube_segment(v_0, n, bn, Some(parities[i - 2]), sectors, radius);
 
             if i == verts.len() - 1 {
-                self.inflate_tube_segment(v_1, n, bn, Some(parities[i - 1]), sectors);
+                self.inflate_tube_segment(v_1, n, bn, Some(parities[i - 1]), sectors, radius);
             }
         }
     }
@@ -533,7 +593,14 @@ impl SimplicialGeometry {
         }
 
         for curve in self.curves.keys() {
-            self.inflate_curve_3d(curve, samples);
+            // Tubes keep their round section, only their thickness follows the wire style.
+            let radius = TUBE_RADIUS
+                * signature_styles
+                    .generator_style(self.curves[curve].generator.generator)
+                    .map(GeneratorStyle::wire_style)
+                    .unwrap_or_default()
+                    .thickness_scale();
+            self.inflate_curve_3d(curve, samples, radius);
         }
 
         self.points.clear();
@@ -615,3 +682,271 @@ impl SimplicialGeometry {
             .unwrap_or(Ordering::Equal)
     }
 }
+
+// Time slicing
+//
+// This reproduces on the CPU what the 4D vertex shader does for animated geometry, so that
+// animations can be exported without a browser.
+
+impl SimplicialGeometry {
+    /// Slice animated geometry at time `t`, giving the uninflated 3-dimensional geometry seen at
+    /// that instant: volumes become areas, areas become curves and lines become points.
+    pub fn slice(&self, t: f32) -> Self {
+        let mut slice = Self::default();
+        let mut crossings: FastHashMap<(Vert, Vert), Vert> = FastHashMap::default();
+
+        // Does the edge from `i` to `j` pass through time `t`?
+        let spans = |i: Vert, j: Vert| {
+            let (a, b) = (self.verts[i].position.w, self.verts[j].position.w);
+            a.min(b) <= t && t <= a.max(b) && (a - b).abs() > f32::EPSILON
+        };
+
+        // The vertex at which the edge from `i` to `j` passes through time `t`.
+        let mut cross = |slice: &mut Self, i: Vert, j: Vert| -> Vert {
+            let (i, j) = if self.time_order(i, j).is_gt() {
+                (j, i)
+            } else {
+                (i, j)
+            };
+            *crossings.entry((i, j)).or_insert_with(|| {
+                let start = self.verts[i].position;
+                let end = self.verts[j].position;
+                let lerp = (t - start.w) / (end.w - start.w);
+                slice.mk_vert(VertData {
+                    position: start + lerp * (end - start),
+                    ..self.verts[i].clone()
+                })
+            })
+        };
+
+        for (mut tetra, parity) in self.volumes.values().copied() {
+            let parity = parity * parity::sort_4(&mut tetra, |i, j| self.time_order(i, j)).into();
+            let [i, j, k, l] = tetra;
+
+            let tris = if parity.is_even() {
+                [
+                    [(i, j), (i, l), (i, k)],
+                    [(j, l), (i, k), (j, k)],
+                    [(j, l), (i, l), (i, k)],
+                    [(j, l), (i, l), (k, l)],
+                ]
+            } else {
+                [
+                    [(i, l), (i, j), (i, k)],
+                    [(i, k), (j, l), (j, k)],
+                    [(i, l), (j, l), (i, k)],
+                    [(i, l), (j, l), (k, l)],
+                ]
+            };
+
+            for tri in tris {
+                if tri.iter().all(|&(a, b)| spans(a, b)) {
+                    let tri = tri.map(|(a, b)| cross(&mut slice, a, b));
+                    slice.mk_area(tri, Parity::Even);
+                }
+            }
+        }
+
+        // Wires are assembled from the segments in which areas meet the slice.
+        let mut segments: FastHashMap<Diagram0, FastHashMap<Vert, Vec<Vert>>> =
+            FastHashMap::default();
+
+        for (mut tri, _) in self.areas.values().copied() {
+            parity::sort_3(&mut tri, |i, j| self.time_order(i, j));
+            let [i, j, k] = tri;
+
+            let segment = if spans(i, j) && spans(i, k) {
+                [(i, j), (i, k)]
+            } else if spans(j, k) && spans(i, k) {
+                [(i, k), (j, k)]
+            } else { continue };
+
+            let [a, b] = segment.map(|(a, b)| cross(&mut slice, a, b));
+            if a != b {
+                let adjacency = segments.entry(self.verts[i].generator).or_default();
+                adjacency.entry(a).or_default().push(b);
+                adjacency.entry(b).or_default().push(a);
+            }
+        }
+
+        for (generator, adjacency) in segments {
+            for verts in chain_segments(&adjacency) {
+                let verts = if verts.len() == 2 {
+                    // Tubes need at least two segments to be inflated.
+                    let midpoint =
+                        0.5 * (slice.verts[verts[0]].position + slice.verts[verts[1]].position);
+                    let midpoint = slice.mk_vert(VertData {
+                        position: midpoint,
+                        ..slice.verts[verts[0]].clone()
+                    });
+                    vec![verts[0], midpoint, verts[1]]
+                } else {
+                    verts
+                };
+
+                slice.curves.push(CurveData {
+                    parities: vec![Parity::Even; verts.len() - 1],
+                    verts,
+                    generator,
+                });
+            }
+        }
+
+        for ([i, j], _) in self.lines.values().copied() {
+            if spans(i, j) {
+                let point = cross(&mut slice, i, j);
+                slice.mk_point(point);
+            }
+        }
+
+        slice
+    }
+}
+
+/// Join segments, given as an adjacency map, into maximal chains of vertices. Closed loops are
+/// returned with their first vertex repeated at the end.
+fn chain_segments(adjacency: &FastHashMap<Vert, Vec<Vert>>) -> Vec<Vec<Vert>> {
+    let mut visited: FastHashSet<Vert> = FastHashSet::default();
+    let mut chains = Vec::new();
+
+    // Open chains start at vertices with a single neighbour, after which only loops remain.
+    let mut starts: Vec<Vert> = adjacency
+        .iter()
+        .filter(|(_, neighbours)| neighbours.len() == 1)
+        .map(|(&v, _)| v)
+        .collect();
+    starts.sort();
+    let mut rest: Vec<Vert> = adjacency.keys().copied().collect();
+    rest.sort();
+    starts.extend(rest);
+
+    for start in starts {
+        if !visited.insert(start) {
+            continue;
+        }
+
+        let mut chain = vec![start];
+        let mut current = start;
+        while let Some(&next) = adjacency[&current].iter().find(|v| !visited.contains(*v)) {
+            visited.insert(next);
+            chain.push(next);
+            current = next;
+        }
+
+        if chain.len() > 2 && adjacency[&current].contains(&start) {
+            chain.push(start);
+        }
+
+        if chain.len() > 1 {
+            chains.push(chain);
+        }
+    }
+
+    chains
+}
+
+#[cfg(test)]
+mod test {
+    use homotopy_common::hash::FastHashMap;
+    use homotopy_core::{Diagram0, Generator};
+    use ultraviolet::Vec4;
+
+    use super::{chain_segments, SimplicialGeometry, Vert, VertData};
+    use crate::parity::Parity;
+
+    fn vert(geom: &mut SimplicialGeometry, x: f32, w: f32) -> Vert {
+        geom.mk_vert(VertData {
+            position: Vec4::new(x, 0.0, 0.0, w),
+            boundary: [false; 4],
+            generator: Diagram0::from(Generator::new(0, 0)),
+            k: 0,
+        })
+    }
+
+    /// Chain the given edges between fresh vertices, flattening the chains.
+    fn chain(geom: &mut SimplicialGeometry, edges: &[(usize, usize)]) -> Vec<Vert> {
+        let verts: Vec<Vert> = (0..=edges.iter().map(|&(a, b)| a.max(b)).max().unwrap())
+            .map(|i| vert(geom, i as f32, 0.0))
+            .collect();
+        let mut adjacency: FastHashMap<Vert, Vec<Vert>> = FastHashMap::default();
+        for &(a, b) in edges {
+            adjacency.entry(verts[a]).or_default().push(verts[b]);
+            adjacency.entry(verts[b]).or_default().push(verts[a]);
+        }
+        chain_segments(&adjacency).into_iter().flatten().collect()
+    }
+
+    #[test]
+    fn chain_open_segments() {
+        let mut geom = SimplicialGeometry::default();
+        let verts = chain(&mut geom, &[(2, 3), (0, 1), (1, 2)]);
+        assert_eq!(verts, (0..4).map(Vert).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn chain_closed_segments() {
+        let mut geom = SimplicialGeometry::default();
+        let verts = chain(&mut geom, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(verts.len(), 4);
+        assert_eq!(verts.first(), verts.last());
+    }
+
+    #[test]
+    fn chain_separate_segments() {
+        let mut geom = SimplicialGeometry::default();
+        let mut adjacency: FastHashMap<Vert, Vec<Vert>> = FastHashMap::default();
+        let verts: Vec<Vert> = (0..5).map(|i| vert(&mut geom, i as f32, 0.0)).collect();
+        for (a, b) in [(0, 1), (2, 3), (3, 4), (4, 2)] {
+            adjacency.entry(verts[a]).or_default().push(verts[b]);
+            adjacency.entry(verts[b]).or_default().push(verts[a]);
+        }
+
+        let chains = chain_segments(&adjacency);
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0], vec![verts[0], verts[1]]);
+        assert_eq!(chains[1].len(), 4);
+    }
+
+    #[test]
+    fn slice_tetrahedron() {
+        // One vertex before the slice and three after it cut a triangle from the tetrahedron,
+        // two on either side cut a quadrilateral.
+        for (before, areas) in [(1, 1), (2, 2)] {
+            let mut geom = SimplicialGeometry::default();
+            let tetra = [0, 1, 2, 3].map(|i| {
+                let w = if i < before { -1.0 } else { 1.0 };
+                vert(&mut geom, i as f32, w)
+            });
+            geom.mk_volume(tetra, Parity::Even);
+
+            let slice = geom.slice(0.0);
+            assert_eq!(slice.areas.len(), areas);
+            assert_eq!(slice.verts.len(), before * (4 - before));
+            assert!(slice.verts.values().all(|v| v.position.w.abs() < 1e-6));
+            assert!(geom.slice(2.0).areas.is_empty());
+        }
+    }
+
+    #[test]
+    fn slice_triangle_and_line() {
+        let mut geom = SimplicialGeometry::default();
+        let tri = [(0.0, -1.0), (1.0, 1.0), (2.0, 1.0)].map(|(x, w)| vert(&mut geom, x, w));
+        geom.mk_area(tri, Parity::Even);
+        geom.mk_line([tri[0], tri[1]], Parity::Even);
+
+        let slice = geom.slice(0.0);
+
+        // The triangle meets the slice in a single segment, which is given a midpoint so that
+        // it can be inflated into a tube.
+        assert_eq!(slice.curves.len(), 1);
+        let curve = &slice.curves.values().next().unwrap().verts;
+        assert_eq!(curve.len(), 3);
+        let [a, m, b] = [0, 1, 2].map(|i| slice.verts[curve[i]].position);
+        assert!((0.5 * (a + b) - m).mag() < 1e-6);
+
+        // The line becomes a point, halfway along it.
+        assert_eq!(slice.points.len(), 1);
+        let point = slice.verts[*slice.points.values().next().unwrap()].position;
+        assert!((point - Vec4::new(0.5, 0.0, 0.0, 0.0)).mag() < 1e-6);
+    }
+}
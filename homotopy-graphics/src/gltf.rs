@@ -0,0 +1,351 @@
+use homotopy_common::hash::FastHashMap;
+use homotopy_core::{common::DimensionError, Diagram};
+use serde_json::{json, Value};
+use ultraviolet::Vec3;
+
+use crate::{
+    animation::{self, AnimationOptions},
+    mesh::{Mesh, MeshGroup, MeshOptions},
+    style::SignatureStyleData,
+};
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+const ARRAY_BUFFER: u32 = 0x8892;
+const ELEMENT_ARRAY_BUFFER: u32 = 0x8893;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const TRIANGLES: u32 = 4;
+
+/// Render a 3-dimensional diagram as a binary glTF 2.0 (`.glb`) file.
+///
+/// Every generator becomes a primitive with its own material, so that colours survive the
+/// import into other tools.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
+) -> Result<Vec<u8>, DimensionError> {
+    let mesh = Mesh::new(diagram, signature_styles, options)?;
+    let mut builder = Builder::default();
+
+    let mut materials = Vec::new();
+    let mut primitives = Vec::new();
+
+    for group in &mesh.groups {
+        materials.push(material(group));
+
+        let position = builder.push_vec3s(&group.positions, true);
+        let normal = builder.push_vec3s(&group.normals, false);
+        let indices = builder.push_indices(&group.triangles);
+        primitives.push(json!({
+            "attributes": { "POSITION": position, "NORMAL": normal },
+            "indices": indices,
+            "material": materials.len() - 1,
+            "mode": TRIANGLES,
+        }));
+    }
+
+    Ok(builder.finish(json!({
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "name": "assoc", "mesh": 0 }],
+        "meshes": [{ "name": "assoc", "primitives": primitives }],
+        "materials": materials,
+    })))
+}
+
+/// Render the animated view of a diagram as a binary glTF 2.0 (`.glb`) file.
+///
+/// glTF morph targets cannot change the topology of a mesh, so every frame contributes its own
+/// vertices to a single mesh. Their base positions are collapsed onto the origin, and each frame
+/// has a morph target moving its vertices into place. The animation steps through the frames by
+/// switching on one morph target at a time.
+pub fn render_animation(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: AnimationOptions,
+) -> Result<Vec<u8>, DimensionError> {
+    let frames = animation::frames(diagram, signature_styles, options)?;
+    let mut builder = Builder::default();
+
+    // Gather the groups sharing a material across all frames.
+    let mut material_indices: FastHashMap<&str, usize> = FastHashMap::default();
+    let mut materials: Vec<Vec<(usize, &MeshGroup)>> = Vec::new();
+    for (frame, mesh) in frames.iter().enumerate() {
+        for group in &mesh.groups {
+            let index = *material_indices.entry(&group.name).or_insert_with(|| {
+                materials.push(Vec::new());
+                materials.len() - 1
+            });
+            materials[index].push((frame, group));
+        }
+    }
+
+    let mut primitives = Vec::new();
+
+    for (material, members) in materials.iter().enumerate() {
+        let count: usize = members.iter().map(|(_, group)| group.positions.len()).sum();
+
+        let mut triangles = Vec::new();
+        let mut ranges = vec![None; frames.len()];
+        let mut offset = 0;
+        for &(frame, group) in members {
+            triangles.extend(group.triangles.iter().map(|t| t.map(|v| v + offset as u32)));
+            ranges[frame] = Some((offset, group));
+            offset += group.positions.len();
+        }
+
+        let collapsed = vec![Vec3::zero(); count];
+        let position = builder.push_vec3s(&collapsed, true);
+        let normal = builder.push_vec3s(&collapsed, false);
+        let indices = builder.push_indices(&triangles);
+
+        let targets: Vec<Value> = ranges
+            .into_iter()
+            .map(|range| {
+                let (offset, positions, normals) = match range {
+                    Some((offset, group)) => {
+                        (offset, group.positions.as_slice(), group.normals.as_slice())
+                    }
+                    None => (0, [].as_slice(), [].as_slice()),
+                };
+                json!({
+                    "POSITION": builder.push_sparse_vec3s(count, offset, positions, true),
+                    "NORMAL": builder.push_sparse_vec3s(count, offset, normals, false),
+                })
+            })
+            .collect();
+
+        primitives.push(json!({
+            "attributes": { "POSITION": position, "NORMAL": normal },
+            "indices": indices,
+            "material": material,
+            "mode": TRIANGLES,
+            "targets": targets,
+        }));
+    }
+
+    let materials: Vec<Value> = materials
+        .iter()
+        .map(|members| material(members[0].1))
+        .collect();
+
+    // One keyframe per frame, each switching on the morph target of that frame alone.
+    let step = options.duration / frames.len().max(1) as f32;
+    let times: Vec<f32> = (0..frames.len()).map(|i| i as f32 * step).collect();
+    let weights: Vec<f32> = (0..frames.len())
+        .flat_map(|i| (0..frames.len()).map(move |j| if i == j { 1. } else { 0. }))
+        .collect();
+    let initial_weights = &weights[..frames.len()];
+    let input = builder.push_scalars(&times, true);
+    let output = builder.push_scalars(&weights, false);
+
+    Ok(builder.finish(json!({
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "name": "assoc", "mesh": 0 }],
+        "meshes": [{
+            "name": "assoc",
+            "primitives": primitives,
+            "weights": initial_weights,
+        }],
+        "materials": materials,
+        "animations": [{
+            "name": "time",
+            "samplers": [{ "input": input, "output": output, "interpolation": "STEP" }],
+            "channels": [{ "sampler": 0, "target": { "node": 0, "path": "weights" } }],
+        }],
+    })))
+}
+
+fn material(group: &MeshGroup) -> Value {
+    let (r, g, b) = group.color.clone().into_linear_f32_components();
+    json!({
+        "name": group.name,
+        "pbrMetallicRoughness": {
+            "baseColorFactor": [r, g, b, 1.0],
+            "metallicFactor": 0.0,
+            "roughnessFactor": 0.5,
+        },
+        "doubleSided": true,
+    })
+}
+
+/// Accumulates the binary buffer of a glTF file along with the views and accessors into it.
+#[derive(Default)]
+struct Builder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+}
+
+impl Builder {
+    fn push_view(&mut self, bytes: &[u8], target: Option<u32>) -> usize {
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.buffer_views.push(view);
+        self.buffer.extend_from_slice(bytes);
+        self.buffer_views.len() - 1
+    }
+
+    /// Push an accessor for a list of vectors, returning its index. Positions need their
+    /// bounds recorded in the accessor.
+    fn push_vec3s(&mut self, vecs: &[Vec3], bounded: bool) -> usize {
+        let view = self.push_view(&vec3_bytes(vecs), Some(ARRAY_BUFFER));
+
+        let mut accessor = json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": vecs.len(),
+            "type": "VEC3",
+        });
+        if bounded {
+            let (min, max) = bounds(vecs.iter().copied());
+            accessor["min"] = json!([min.x, min.y, min.z]);
+            accessor["max"] = json!([max.x, max.y, max.z]);
+        }
+
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    /// Push an accessor of the given length which is zero except for a contiguous run of
+    /// vectors starting at `offset`, returning its index.
+    fn push_sparse_vec3s(
+        &mut self,
+        count: usize,
+        offset: usize,
+        vecs: &[Vec3],
+        bounded: bool,
+    ) -> usize {
+        let mut accessor = json!({
+            "componentType": FLOAT,
+            "count": count,
+            "type": "VEC3",
+        });
+
+        // Sparse storage must contain at least one element, otherwise all zeros are implied.
+        if !vecs.is_empty() {
+            let indices: Vec<u8> = (offset..offset + vecs.len())
+                .flat_map(|i| (i as u32).to_le_bytes())
+                .collect();
+            let indices = self.push_view(&indices, None);
+            let values = self.push_view(&vec3_bytes(vecs), None);
+            accessor["sparse"] = json!({
+                "count": vecs.len(),
+                "indices": { "bufferView": indices, "componentType": UNSIGNED_INT },
+                "values": { "bufferView": values },
+            });
+        }
+
+        if bounded {
+            let zeros = (vecs.len() < count).then(Vec3::zero);
+            let (min, max) = bounds(vecs.iter().copied().chain(zeros));
+            accessor["min"] = json!([min.x, min.y, min.z]);
+            accessor["max"] = json!([max.x, max.y, max.z]);
+        }
+
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn push_scalars(&mut self, values: &[f32], bounded: bool) -> usize {
+        let bytes: Vec<u8> = values.iter().copied().flat_map(f32::to_le_bytes).collect();
+        let view = self.push_view(&bytes, None);
+
+        let mut accessor = json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": values.len(),
+            "type": "SCALAR",
+        });
+        if bounded {
+            let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            accessor["min"] = json!([min]);
+            accessor["max"] = json!([max]);
+        }
+
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn push_indices(&mut self, triangles: &[[u32; 3]]) -> usize {
+        let bytes: Vec<u8> = triangles
+            .iter()
+            .flatten()
+            .copied()
+            .flat_map(u32::to_le_bytes)
+            .collect();
+        let view = self.push_view(&bytes, Some(ELEMENT_ARRAY_BUFFER));
+
+        self.accessors.push(json!({
+            "bufferView": view,
+            "componentType": UNSIGNED_INT,
+            "count": triangles.len() * 3,
+            "type": "SCALAR",
+        }));
+        self.accessors.len() - 1
+    }
+
+    /// Complete the given glTF document with the buffer, views and accessors, and pack it into
+    /// a GLB container.
+    fn finish(self, mut document: Value) -> Vec<u8> {
+        document["asset"] = json!({ "version": "2.0", "generator": "homotopy.io" });
+        document["buffers"] = json!([{ "byteLength": self.buffer.len() }]);
+        document["bufferViews"] = Value::Array(self.buffer_views);
+        document["accessors"] = Value::Array(self.accessors);
+
+        let mut json = serde_json::to_vec(&document).unwrap();
+        pad(&mut json, b' ');
+        let mut bin = self.buffer;
+        pad(&mut bin, 0);
+
+        let length = 12 + 8 + json.len() + 8 + bin.len();
+        let mut output = Vec::with_capacity(length);
+        for word in [GLB_MAGIC, GLB_VERSION, length as u32] {
+            output.extend_from_slice(&word.to_le_bytes());
+        }
+        for (chunk_type, chunk) in [(CHUNK_JSON, json), (CHUNK_BIN, bin)] {
+            output.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+            output.extend_from_slice(&chunk_type.to_le_bytes());
+            output.extend_from_slice(&chunk);
+        }
+
+        output
+    }
+}
+
+fn vec3_bytes(vecs: &[Vec3]) -> Vec<u8> {
+    vecs.iter()
+        .flat_map(|v| [v.x, v.y, v.z])
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
+
+fn bounds(vecs: impl Iterator<Item = Vec3>) -> (Vec3, Vec3) {
+    vecs.fold(
+        (
+            Vec3::broadcast(f32::INFINITY),
+            Vec3::broadcast(f32::NEG_INFINITY),
+        ),
+        |(min, max), v| (min.min_by_component(v), max.max_by_component(v)),
+    )
+}
+
+/// Chunks of a GLB file must be aligned to 4 bytes.
+fn pad(bytes: &mut Vec<u8>, padding: u8) {
+    while bytes.len() % 4 != 0 {
+        bytes.push(padding);
+    }
+}
@@ -0,0 +1,227 @@
+use homotopy_core::Generator;
+use lyon_path::{iterator::PathIterator, Event, Path};
+
+use crate::{
+    style::{GeneratorStyle, SignatureStyleData, VertexShape},
+    svg::{render::GraphicElement, shape::Point},
+};
+
+/// Distance between labels and the points or wires they refer to, in layout coordinates.
+pub const LABEL_DISTANCE: f32 = 0.5;
+
+/// Tolerance used when sampling wires to find free space around labels.
+const FLATTENING_TOLERANCE: f32 = 0.05;
+
+/// Directions in which a label may be offset from its anchor, in order of preference.
+const DIRECTIONS: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (1.0, 1.0),
+    (-1.0, 1.0),
+    (1.0, -1.0),
+    (-1.0, -1.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+];
+
+/// Unicode counterparts of the LaTeX commands for symbols commonly found in labels.
+const SYMBOLS: [(&str, &str); 45] = [
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    ("circ", "∘"),
+    ("otimes", "⊗"),
+    ("oplus", "⊕"),
+    ("times", "×"),
+    ("cdot", "·"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("infty", "∞"),
+    ("dagger", "†"),
+];
+
+/// A generator label placed next to a point or wire of a 2-dimensional graphic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub generator: Generator,
+    /// The LaTeX source of the label.
+    pub text: String,
+    /// The point of the graphic the label refers to.
+    pub anchor: Point,
+    /// The centre of the label.
+    pub position: Point,
+}
+
+/// Place labels for every labelled point and wire in a graphic, in layout coordinates.
+///
+/// Each label is put at the given distance from its anchor, in whichever direction keeps it
+/// furthest away from the wires, points and previously placed labels. Labels of points drawn as
+/// labelled boxes go inside the box instead.
+pub fn place_labels<const N: usize>(
+    graphic: &[GraphicElement<N>],
+    styles: &impl SignatureStyleData,
+    distance: f32,
+) -> Vec<Label> {
+    let mut obstacles = Vec::new();
+    let mut anchors = Vec::new();
+
+    for element in graphic {
+        match element {
+            GraphicElement::Surface(_, _) => {}
+            GraphicElement::Wire(d, _, path, _) => {
+                let samples = sample_path(path);
+                if !samples.is_empty() {
+                    anchors.push((d.generator, samples[samples.len() / 2], false));
+                }
+                obstacles.extend(samples);
+            }
+            GraphicElement::Point(d, point) => {
+                let boxed = styles
+                    .generator_style(d.generator)
+                    .map_or(false, |style| style.shape() == VertexShape::LabelledBox);
+                anchors.push((d.generator, *point, boxed));
+                obstacles.push(*point);
+            }
+        }
+    }
+
+    let mut labels: Vec<Label> = Vec::new();
+
+    for (generator, anchor, boxed) in anchors {
+        let Some(text) = styles
+            .generator_style(generator)
+            .and_then(GeneratorStyle::label)
+        else { continue };
+
+        if boxed {
+            labels.push(Label {
+                generator,
+                text,
+                anchor,
+                position: anchor,
+            });
+            continue;
+        }
+
+        let clearance = |position: Point| {
+            obstacles
+                .iter()
+                .chain(labels.iter().map(|label| &label.position))
+                .map(|p| (*p - position).length())
+                .fold(f32::INFINITY, f32::min)
+        };
+
+        let position = DIRECTIONS
+            .iter()
+            .map(|&(x, y)| anchor + euclid::vec2(x, y).normalize() * distance)
+            .fold(None, |best: Option<(Point, f32)>, position| {
+                let c = clearance(position);
+                match best {
+                    Some((_, best_clearance)) if best_clearance >= c => best,
+                    _ => Some((position, c)),
+                }
+            })
+            .map(|(position, _)| position)
+            .unwrap();
+
+        labels.push(Label {
+            generator,
+            text,
+            anchor,
+            position,
+        });
+    }
+
+    labels
+}
+
+/// Approximate the LaTeX source of a label by plain text, for formats which cannot typeset it.
+///
+/// Commands for common symbols are replaced by their Unicode counterparts and other commands are
+/// dropped, keeping their arguments. Braces and the markers of subscripts and superscripts are
+/// removed.
+pub fn plain_text(tex: &str) -> String {
+    let mut text = String::new();
+    let mut chars = tex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_alphabetic) {
+                    name.push(c);
+                }
+                if name.is_empty() {
+                    // Escaped characters like `\{`, except for spacing commands like `\,`.
+                    if let Some(c) = chars.next().filter(|c| !matches!(c, ',' | ';' | ':' | '!')) {
+                        text.push(c);
+                    }
+                } else if let Some((_, symbol)) =
+                    SYMBOLS.iter().find(|(command, _)| *command == name)
+                {
+                    text.push_str(symbol);
+                }
+            }
+            '{' | '}' | '$' | '^' | '_' => {}
+            '~' => text.push(' '),
+            c => text.push(c),
+        }
+    }
+    text.trim().to_owned()
+}
+
+fn sample_path(path: &Path) -> Vec<Point> {
+    path.iter()
+        .flattened(FLATTENING_TOLERANCE)
+        .filter_map(|event| match event {
+            Event::Begin { at } => Some(at),
+            Event::Line { to, .. } => Some(to),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::plain_text;
+
+    #[test]
+    fn plain_text_symbols() {
+        assert_eq!(plain_text(r"\alpha \otimes \beta"), "α ⊗ β");
+        assert_eq!(plain_text(r"\mathrm{id}_{A}"), "idA");
+        assert_eq!(plain_text(r"\text{\{a\}}\,b^2"), "{a}b2");
+        assert_eq!(plain_text(r"f \circ g"), "f ∘ g");
+    }
+}
@@ -1,8 +1,19 @@
+pub mod animation;
+pub mod figure;
 pub mod geom;
+pub mod gltf;
+pub mod label;
 pub mod manim;
+pub mod mesh;
+pub mod obj;
 pub mod parity;
 pub mod path_util;
+pub mod pdf;
+pub mod ply;
+pub mod png;
+pub mod raster;
 pub mod stl;
+pub mod strip;
 pub mod style;
 pub mod subdivision;
 pub mod svg;
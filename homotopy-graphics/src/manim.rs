@@ -8,22 +8,34 @@ use homotopy_core::{
     diagram::Diagram0,
     layout::Layout,
     projection::{Depths, Projection},
-    Diagram, Generator, Orientation,
+    Diagram, DiagramN, Generator, Orientation, SliceIndex,
 };
 use itertools::Itertools;
-use lyon_path::{Event, Path};
+use lyon_path::{iterator::PathIterator, Event, Path};
 
 use crate::{
+    label::{place_labels, Label, LABEL_DISTANCE},
     path_util::simplify_graphic,
-    style::{GeneratorRepresentation, GeneratorStyle, SignatureStyleData, VertexShape},
+    style::{
+        GeneratorRepresentation, GeneratorStyle, SignatureStyleData, SurfaceStyle, Theme, WireStyle,
+    },
     svg::render::GraphicElement,
 };
 
 const INDENT: &str = "    ";
 
-pub fn stylesheet(styles: &impl SignatureStyleData) -> String {
+pub fn stylesheet(styles: &impl SignatureStyleData, theme: Theme) -> String {
     let mut stylesheet = String::new();
 
+    // The colours of the background and of the labels drawn on it.
+    writeln!(
+        stylesheet,
+        "            \"background\": \"{}\",\n            \"foreground\": \"{}\",",
+        theme.paper().hex(),
+        theme.foreground().hex()
+    )
+    .unwrap();
+
     for generator in styles.generators() {
         let color = styles.generator_style(generator).unwrap().color();
         for c in 0..3 {
@@ -36,7 +48,7 @@ pub fn stylesheet(styles: &impl SignatureStyleData) -> String {
                     stylesheet,
                     "            \"{generator}\": \"{color}\",",
                     generator = name(generator, c, orientation),
-                    color = color.lighten(c, orientation).hex()
+                    color = theme.shade(&color, c, orientation).hex()
                 )
                 .unwrap();
             }
@@ -67,113 +79,52 @@ fn name(generator: Generator, c: usize, orientation: Orientation) -> String {
         "generator_{}_{}_{c}_{}",
         generator.id,
         generator.dimension,
-        match orientation {
-            Orientation::Positive => "pos",
-            Orientation::Negative => "neg",
-            Orientation::Zero => "zer",
-        }
+        orientation_name(orientation)
     )
 }
 
+#[inline]
+fn orientation_name(orientation: Orientation) -> &'static str {
+    match orientation {
+        Orientation::Positive => "pos",
+        Orientation::Negative => "neg",
+        Orientation::Zero => "zer",
+    }
+}
+
 pub fn render(
     diagram: &Diagram,
     signature_styles: &impl SignatureStyleData,
     stylesheet: &str,
     use_opengl: bool,
+    show_labels: bool,
 ) -> Result<String, DimensionError> {
     let layout = Layout::<2>::new(diagram)?;
-    let complex = make_complex(diagram);
-    let depths = Depths::<2>::new(diagram)?;
-    let projection = Projection::<2>::new(diagram, &layout, &depths)?;
-    let graphic = simplify_graphic(&GraphicElement::build(
-        &complex,
-        &layout,
-        &projection,
-        &depths,
-    ));
+    let graphic = build_graphic(diagram, &layout)?;
+    let labels = if show_labels {
+        place_labels(&graphic, signature_styles, LABEL_DISTANCE)
+    } else {
+        Vec::new()
+    };
 
     let mut surfaces = Vec::default();
     let mut wires: FastHashMap<usize, Vec<(Diagram0, Path)>> = FastHashMap::default();
     let mut points = Vec::default();
 
     // Needed for working out translations/scalings
-    let mut max_point = Point2D::<f32>::zero();
+    let max_point = max_point_graphic(&graphic);
     for element in graphic {
         match element {
-            GraphicElement::Surface(g, path) => {
-                max_point = max_point.max(max_point_path(&path));
-                surfaces.push((g, path));
-            }
+            GraphicElement::Surface(g, path) => surfaces.push((g, path)),
             GraphicElement::Wire(g, depth, path, _mask) => {
-                max_point = max_point.max(max_point_path(&path));
                 wires.entry(depth).or_default().push((g, path));
             }
-            GraphicElement::Point(g, point) => {
-                max_point = max_point.max(point);
-                points.push((g, point));
-            }
+            GraphicElement::Point(g, point) => points.push((g, point)),
         }
     }
 
     let mut manim = String::new();
-    if use_opengl {
-        manim.push_str(
-            "# Render with 'manim --format mp4 --renderer=opengl homotopy_io_export.py'\n",
-        );
-    } else {
-        manim.push_str(
-            "# Render with 'manim --format mp4 --renderer=cairo homotopy_io_export.py'\n",
-        );
-    }
-    manim.push_str("import numpy as np\n");
-    manim.push_str("from manim import *\n");
-    if use_opengl {
-        manim.push_str(
-            "from manim.mobject.opengl.opengl_vectorized_mobject import OpenGLVMobject\n",
-        );
-    }
-
-    writeln!(
-        manim,
-        concat!(
-            "\nclass HomotopyIoManim(Scene):\n",
-            "{ind}def get_colors(self):\n",
-            "{ind}{ind}colors = {{\n",
-            "{stylesheet}",
-            "{ind}{ind}}}\n",
-            "{ind}{ind}return colors\n",
-        ),
-        ind = INDENT,
-        stylesheet = stylesheet
-    )
-    .unwrap();
-
-    let vmobj = if use_opengl {
-        "OpenGLVMobject"
-    } else {
-        "VMobject"
-    };
-    writeln!(
-        manim,
-        concat!(
-            "{ind}def build_path(self, geom, **kwargs):\n",
-            "{ind}{ind}obj = {vmobj}()\n",
-            "{ind}{ind}obj.set_stroke(**kwargs)\n",
-            "{ind}{ind}for c in geom:\n",
-            "{ind}{ind}{ind}if c[0] == 0:\n",
-            "{ind}{ind}{ind}{ind}obj.start_new_path(c[1])\n",
-            "{ind}{ind}{ind}elif c[0] == 1:\n",
-            "{ind}{ind}{ind}{ind}obj.add_line_to(c[1])\n",
-            "{ind}{ind}{ind}elif c[0] == 2:\n",
-            "{ind}{ind}{ind}{ind}obj.add_quadratic_bezier_curve_to(c[1],c[2])\n",
-            "{ind}{ind}{ind}else:\n",
-            "{ind}{ind}{ind}{ind}obj.add_cubic_bezier_curve_to(c[1],c[2],c[3])\n",
-            "{ind}{ind}return obj\n",
-        ),
-        ind = INDENT,
-        vmobj = vmobj,
-    )
-    .unwrap();
+    write_preamble(&mut manim, stylesheet, use_opengl, max_point);
 
     // Surfaces
     writeln!(
@@ -190,12 +141,15 @@ pub fn render(
     for (d, path) in surfaces {
         writeln!(
             manim,
-            "{ind}{ind}surfaces.add(self.build_path({path},width=1).set_fill(C[\"{color}\"],1)) # path_{id}_{dim}",
-            ind=INDENT,
-            color=name_from_diagram_dim(d, diagram.dimension(), GeneratorRepresentation::Surface),
-            id=d.generator.id,
-            dim=d.generator.dimension,
-            path=&render_path(&path)
+            "{ind}{ind}surfaces.add({surface}) # path_{id}_{dim}",
+            ind = INDENT,
+            surface = render_surface(
+                &path,
+                &name_from_diagram_dim(d, diagram.dimension(), GeneratorRepresentation::Surface),
+                surface_style(signature_styles, d),
+            ),
+            id = d.generator.id,
+            dim = d.generator.dimension,
         )
         .unwrap();
     }
@@ -223,22 +177,30 @@ pub fn render(
         if i > 0 {
             writeln!(manim, "{INDENT}{INDENT}# Begin scope").unwrap();
             for (d, path) in &layer {
-                writeln!(manim, "{INDENT}{INDENT}wires.add(Intersection(surfaces,self.build_path({path},width=20),color=C[\"generator_{id}_{dim}\"]))",
+                writeln!(manim, "{INDENT}{INDENT}wires.add(Intersection(surfaces,self.build_path({path},width={width}),color=C[\"generator_{id}_{dim}\"]))",
                          id=d.generator.id,
                          dim=d.generator.dimension,
-                         path=&render_path(path)
+                         path=&render_path(path),
+                         width=WIRE_WIDTH * wire_style(signature_styles, *d).thickness_scale(),
                 ).unwrap();
             }
             writeln!(manim, "{INDENT}{INDENT}# End scope").unwrap();
         }
 
         for (d, path) in &layer {
-            writeln!(manim, "{INDENT}{INDENT}wires.add(self.build_path({path},width=20,color=C[\"{color}\"])) # path_{id}_{dim}",
-                color=name_from_diagram_dim(*d, diagram.dimension(), GeneratorRepresentation::Wire),
-                id=d.generator.id,
-                dim=d.generator.dimension,
-                path=&render_path(path)
-            ).unwrap();
+            writeln!(
+                manim,
+                "{ind}{ind}wires.add({wire}) # path_{id}_{dim}",
+                ind = INDENT,
+                wire = render_wire(
+                    path,
+                    &name_from_diagram_dim(*d, diagram.dimension(), GeneratorRepresentation::Wire),
+                    wire_style(signature_styles, *d),
+                ),
+                id = d.generator.id,
+                dim = d.generator.dimension,
+            )
+            .unwrap();
         }
     }
 
@@ -261,34 +223,61 @@ pub fn render(
         let vertex = render_vertex(
             signature_styles.generator_style(d.generator).unwrap(),
             &name_from_diagram_dim(d, diagram.dimension(), GeneratorRepresentation::Point),
+            point,
         );
         writeln!(
             manim,
-            "{ind}{ind}points.add({vertex}.move_to(np.array([{ptx},{pty},1])) # circle_{id}_{dim}",
+            "{ind}{ind}points.add({vertex}) # circle_{id}_{dim}",
             ind = INDENT,
             id = d.generator.id,
             dim = d.generator.dimension,
             vertex = vertex,
-            ptx = point.x,
-            pty = point.y
         )
         .unwrap();
     }
 
+    // Labels
     writeln!(
         manim,
-        concat!("{ind}{ind}return points\n\n",
+        concat!(
+            "{ind}{ind}return points\n\n",
+            "{ind}# Labels\n",
+            "{ind}def get_labels(self):\n",
+            "{ind}{ind}C = self.get_colors()\n",
+            "{ind}{ind}labels = VGroup()"
+        ),
+        ind = INDENT
+    )
+    .unwrap();
+
+    for label in labels {
+        writeln!(
+            manim,
+            "{ind}{ind}labels.add(MathTex({text}, color=C[\"foreground\"]).scale(0.5).move_to(np.array([{x},{y},1]))) # label_{id}_{dim}",
+            ind = INDENT,
+            text = python_string(&label.text),
+            x = label.position.x,
+            y = label.position.y,
+            id = label.generator.id,
+            dim = label.generator.dimension,
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        manim,
+        concat!("{ind}{ind}return labels\n\n",
             "{ind}# We now put everything together\n",
             "{ind}def construct(self):\n",
-            "{ind}{ind}#C = self.get_colors()\n",
+            "{ind}{ind}C = self.get_colors()\n",
             "{ind}{ind}surfaces = self.get_surfaces()\n",
             "{ind}{ind}wires = self.get_wires(surfaces)\n",
             "{ind}{ind}points = self.get_points()\n",
-            "{ind}{ind}# Background (for rendering consistency, set color=BLACK if unwanted)\n",
-            "{ind}{ind}bg = Rectangle(width={x}*2,height={y}*2,color=WHITE).move_to(surfaces)\n",
+            "{ind}{ind}labels = self.get_labels()\n",
+            "{ind}{ind}# Background (for rendering consistency, remove from root if unwanted)\n",
+            "{ind}{ind}bg = Rectangle(width={x}*2,height={y}*2,color=C[\"background\"],fill_opacity=1).move_to(surfaces)\n",
             "{ind}{ind}# Root\n",
-            "{ind}{ind}scale_factor = max(config.frame_size[0]/{x},config.frame_size[1]/{x})*0.002 # Magic number\n",
-            "{ind}{ind}root = VGroup(bg,surfaces,wires,points).shift({x}*LEFT+{y}*DOWN).scale(scale_factor)\n",
+            "{ind}{ind}root = self.place(VGroup(bg,surfaces,wires,points,labels))\n",
             "{ind}{ind}# Static output (low rendering times)\n",
             "{ind}{ind}#self.add(root)\n",
             "{ind}{ind}# Animated output\n",
@@ -306,6 +295,428 @@ pub fn render(
     Ok(manim)
 }
 
+/// Timing of scenes morphing from one diagram into the next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepOptions {
+    /// Seconds spent morphing a diagram into the next one.
+    pub step_duration: f32,
+    /// Seconds spent on every diagram before moving on.
+    pub pause: f32,
+}
+
+impl Default for StepOptions {
+    fn default() -> Self {
+        Self {
+            step_duration: 1.,
+            pause: 0.5,
+        }
+    }
+}
+
+/// Render a scene stepping through the slices of a diagram of dimension at least 3, each of
+/// them drawn as its 2-dimensional projection.
+///
+/// The slices are laid out as parts of the whole diagram, so wires and points keep their
+/// positions between slices and move smoothly through contractions and expansions.
+pub fn render_slices(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    stylesheet: &str,
+    use_opengl: bool,
+    show_labels: bool,
+    steps: StepOptions,
+) -> Result<String, DimensionError> {
+    let layout = Layout::<3>::new(diagram)?;
+    let frames = <&DiagramN>::try_from(diagram)?
+        .slices()
+        .enumerate()
+        .map(|(i, slice)| {
+            let layout = layout.slice(SliceIndex::Interior(i.into()));
+            Frame::new(&slice, &layout, signature_styles, show_labels)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(render_frames(
+        &frames,
+        signature_styles,
+        stylesheet,
+        use_opengl,
+        steps,
+    ))
+}
+
+/// Render a scene morphing through a sequence of diagrams of dimension at least 2, such as the
+/// workspace after every step of a proof.
+///
+/// Points, wires and surfaces of the same generator are morphed into each other, and everything
+/// else fades in or out.
+pub fn render_steps(
+    diagrams: &[Diagram],
+    signature_styles: &impl SignatureStyleData,
+    stylesheet: &str,
+    use_opengl: bool,
+    show_labels: bool,
+    steps: StepOptions,
+) -> Result<String, DimensionError> {
+    let frames = diagrams
+        .iter()
+        .map(|diagram| {
+            let layout = Layout::<2>::new(diagram)?;
+            Frame::new(diagram, &layout, signature_styles, show_labels)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(render_frames(
+        &frames,
+        signature_styles,
+        stylesheet,
+        use_opengl,
+        steps,
+    ))
+}
+
+/// The graphic of one diagram in an animated scene.
+struct Frame {
+    dimension: usize,
+    graphic: Vec<GraphicElement<2>>,
+    labels: Vec<Label>,
+}
+
+impl Frame {
+    fn new(
+        diagram: &Diagram,
+        layout: &Layout<2>,
+        signature_styles: &impl SignatureStyleData,
+        show_labels: bool,
+    ) -> Result<Self, DimensionError> {
+        let graphic = build_graphic(diagram, layout)?;
+        let labels = if show_labels {
+            place_labels(&graphic, signature_styles, LABEL_DISTANCE)
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            dimension: diagram.dimension(),
+            graphic,
+            labels,
+        })
+    }
+
+    /// The objects of the frame as Python expressions, under keys which are shared with the
+    /// corresponding objects of other frames.
+    ///
+    /// Objects of the same kind and generator are told apart by their position from left to right
+    /// and bottom to top.
+    fn objects(
+        &self,
+        signature_styles: &impl SignatureStyleData,
+        max_depth: usize,
+    ) -> Vec<(String, String)> {
+        let mut groups: FastHashMap<(&str, Generator, &str), Vec<(Point2D<f32>, String)>> =
+            FastHashMap::default();
+
+        for element in &self.graphic {
+            match element {
+                GraphicElement::Surface(d, path) => {
+                    let surface = render_surface(
+                        path,
+                        &name_from_diagram_dim(
+                            *d,
+                            self.dimension,
+                            GeneratorRepresentation::Surface,
+                        ),
+                        surface_style(signature_styles, *d),
+                    );
+                    let object = format!("{surface}.set_z_index(0)");
+                    groups
+                        .entry(("surface", d.generator, orientation_name(d.orientation)))
+                        .or_default()
+                        .push((start_point(path), object));
+                }
+                GraphicElement::Wire(d, depth, path, _mask) => {
+                    let wire = render_wire(
+                        path,
+                        &name_from_diagram_dim(*d, self.dimension, GeneratorRepresentation::Wire),
+                        wire_style(signature_styles, *d),
+                    );
+                    let object = format!("{wire}.set_z_index({z})", z = 1 + max_depth - depth);
+                    groups
+                        .entry(("wire", d.generator, orientation_name(d.orientation)))
+                        .or_default()
+                        .push((start_point(path), object));
+                }
+                GraphicElement::Point(d, point) => {
+                    let vertex = render_vertex(
+                        signature_styles.generator_style(d.generator).unwrap(),
+                        &name_from_diagram_dim(*d, self.dimension, GeneratorRepresentation::Point),
+                        *point,
+                    );
+                    let object = format!("{vertex}.set_z_index({z})", z = max_depth + 2);
+                    groups
+                        .entry(("point", d.generator, orientation_name(d.orientation)))
+                        .or_default()
+                        .push((*point, object));
+                }
+            }
+        }
+
+        for label in &self.labels {
+            let object = format!(
+                "MathTex({text}, color=C[\"foreground\"]).scale(0.5).move_to(np.array([{x},{y},1])).set_z_index({z})",
+                text = python_string(&label.text),
+                x = label.position.x,
+                y = label.position.y,
+                z = max_depth + 3,
+            );
+            groups
+                .entry(("label", label.generator, "all"))
+                .or_default()
+                .push((label.position, object));
+        }
+
+        groups
+            .into_iter()
+            .flat_map(|((kind, generator, orientation), mut objects)| {
+                objects.sort_by(|(p, _), (q, _)| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+                objects
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(i, (_, object))| {
+                        let key = format!(
+                            "{kind}_{}_{}_{orientation}_{i}",
+                            generator.id, generator.dimension
+                        );
+                        (key, object)
+                    })
+            })
+            .sorted()
+            .collect()
+    }
+}
+
+fn render_frames(
+    frames: &[Frame],
+    signature_styles: &impl SignatureStyleData,
+    stylesheet: &str,
+    use_opengl: bool,
+    steps: StepOptions,
+) -> String {
+    // Needed for working out translations/scalings
+    let max_point = max_point_graphic(frames.iter().flat_map(|frame| &frame.graphic));
+    let max_depth = frames
+        .iter()
+        .flat_map(|frame| &frame.graphic)
+        .filter_map(|element| match element {
+            GraphicElement::Wire(_, depth, _, _) => Some(*depth),
+            _ => None,
+        })
+        .max()
+        .unwrap_or_default();
+
+    let mut manim = String::new();
+    write_preamble(&mut manim, stylesheet, use_opengl, max_point);
+
+    // Frames, as dictionaries of objects keyed by what they depict
+    for (i, frame) in frames.iter().enumerate() {
+        writeln!(
+            manim,
+            concat!(
+                "{ind}# Frame {i}\n",
+                "{ind}def get_frame_{i}(self):\n",
+                "{ind}{ind}C = self.get_colors()\n",
+                "{ind}{ind}return {{",
+            ),
+            ind = INDENT,
+            i = i,
+        )
+        .unwrap();
+        for (key, object) in frame.objects(signature_styles, max_depth) {
+            writeln!(manim, "{INDENT}{INDENT}{INDENT}\"{key}\": {object},").unwrap();
+        }
+        writeln!(manim, "{INDENT}{INDENT}}}\n").unwrap();
+    }
+
+    writeln!(
+        manim,
+        concat!(
+            "{ind}# We now put everything together\n",
+            "{ind}def construct(self):\n",
+            "{ind}{ind}frames = [{frames}]\n",
+            "{ind}{ind}if not frames:\n",
+            "{ind}{ind}{ind}return\n",
+            "{ind}{ind}C = self.get_colors()\n",
+            "{ind}{ind}# Background (for rendering consistency, remove from the scene if unwanted)\n",
+            "{ind}{ind}bg = self.place(Rectangle(width={x}*2,height={y}*2,color=C[\"background\"],fill_opacity=1).move_to(np.array([{x},{y},0])))\n",
+            "{ind}{ind}current = {{key: self.place(obj) for key, obj in frames[0].items()}}\n",
+            "{ind}{ind}self.add(bg, *current.values())\n",
+            "{ind}{ind}self.wait({pause})\n",
+            "{ind}{ind}for frame in frames[1:]:\n",
+            "{ind}{ind}{ind}frame = {{key: self.place(obj) for key, obj in frame.items()}}\n",
+            "{ind}{ind}{ind}# Morph objects depicting the same thing, and fade the others in or out\n",
+            "{ind}{ind}{ind}animations = []\n",
+            "{ind}{ind}{ind}for key, obj in current.items():\n",
+            "{ind}{ind}{ind}{ind}if key in frame:\n",
+            "{ind}{ind}{ind}{ind}{ind}animations.append(ReplacementTransform(obj, frame[key]))\n",
+            "{ind}{ind}{ind}{ind}else:\n",
+            "{ind}{ind}{ind}{ind}{ind}animations.append(FadeOut(obj))\n",
+            "{ind}{ind}{ind}animations += [FadeIn(obj) for key, obj in frame.items() if key not in current]\n",
+            "{ind}{ind}{ind}if animations:\n",
+            "{ind}{ind}{ind}{ind}self.play(*animations, run_time={step_duration})\n",
+            "{ind}{ind}{ind}self.wait({pause})\n",
+            "{ind}{ind}{ind}current = frame\n",
+        ),
+        ind = INDENT,
+        frames = (0..frames.len())
+            .map(|i| format!("self.get_frame_{i}()"))
+            .join(","),
+        x = max_point.x * 0.5,
+        y = max_point.y * 0.5,
+        pause = steps.pause,
+        step_duration = steps.step_duration,
+    )
+    .unwrap();
+
+    manim
+}
+
+/// Write the imports of a scene and the opening of its class, with the helpers shared by every
+/// scene: the colours of the stylesheet, building paths and placing diagrams whose graphics reach
+/// up to `max_point` in the middle of the screen.
+fn write_preamble(
+    manim: &mut String,
+    stylesheet: &str,
+    use_opengl: bool,
+    max_point: Point2D<f32>,
+) {
+    if use_opengl {
+        manim.push_str(
+            "# Render with 'manim --format mp4 --renderer=opengl homotopy_io_export.py'\n",
+        );
+    } else {
+        manim.push_str(
+            "# Render with 'manim --format mp4 --renderer=cairo homotopy_io_export.py'\n",
+        );
+    }
+    manim.push_str("import numpy as np\n");
+    manim.push_str("from manim import *\n");
+    if use_opengl {
+        manim.push_str(
+            "from manim.mobject.opengl.opengl_vectorized_mobject import OpenGLVMobject\n",
+        );
+    }
+
+    writeln!(
+        manim,
+        concat!(
+            "\nclass HomotopyIoManim(Scene):\n",
+            "{ind}def get_colors(self):\n",
+            "{ind}{ind}colors = {{\n",
+            "{stylesheet}",
+            "{ind}{ind}}}\n",
+            "{ind}{ind}return colors\n",
+        ),
+        ind = INDENT,
+        stylesheet = stylesheet
+    )
+    .unwrap();
+
+    let vmobj = if use_opengl {
+        "OpenGLVMobject"
+    } else {
+        "VMobject"
+    };
+    writeln!(
+        manim,
+        concat!(
+            "{ind}def build_path(self, geom, **kwargs):\n",
+            "{ind}{ind}obj = {vmobj}()\n",
+            "{ind}{ind}obj.set_stroke(**kwargs)\n",
+            "{ind}{ind}for c in geom:\n",
+            "{ind}{ind}{ind}if c[0] == 0:\n",
+            "{ind}{ind}{ind}{ind}obj.start_new_path(c[1])\n",
+            "{ind}{ind}{ind}elif c[0] == 1:\n",
+            "{ind}{ind}{ind}{ind}obj.add_line_to(c[1])\n",
+            "{ind}{ind}{ind}elif c[0] == 2:\n",
+            "{ind}{ind}{ind}{ind}obj.add_quadratic_bezier_curve_to(c[1],c[2])\n",
+            "{ind}{ind}{ind}else:\n",
+            "{ind}{ind}{ind}{ind}obj.add_cubic_bezier_curve_to(c[1],c[2],c[3])\n",
+            "{ind}{ind}return obj\n",
+        ),
+        ind = INDENT,
+        vmobj = vmobj,
+    )
+    .unwrap();
+
+    writeln!(
+        manim,
+        concat!(
+            "{ind}# Move the diagrams to the centre of the screen\n",
+            "{ind}def place(self, obj):\n",
+            "{ind}{ind}scale_factor = max(config.frame_size[0]/{x},config.frame_size[1]/{x})*0.002 # Magic number\n",
+            "{ind}{ind}return obj.shift({x}*LEFT+{y}*DOWN).scale(scale_factor,about_point=ORIGIN)\n",
+        ),
+        ind = INDENT,
+        x = max_point.x * 0.5,
+        y = max_point.y * 0.5,
+    )
+    .unwrap();
+}
+
+/// The top right corner of the bounding box of a graphic.
+fn max_point_graphic<'a>(
+    graphic: impl IntoIterator<Item = &'a GraphicElement<2>>,
+) -> Point2D<f32> {
+    graphic
+        .into_iter()
+        .map(|element| match element {
+            GraphicElement::Surface(_, path) | GraphicElement::Wire(_, _, path, _) => {
+                max_point_path(path)
+            }
+            GraphicElement::Point(_, point) => *point,
+        })
+        .fold(Point2D::zero(), Point2D::max)
+}
+
+fn build_graphic(
+    diagram: &Diagram,
+    layout: &Layout<2>,
+) -> Result<Vec<GraphicElement<2>>, DimensionError> {
+    let complex = make_complex(diagram);
+    let depths = Depths::<2>::new(diagram)?;
+    let projection = Projection::<2>::new(diagram, layout, &depths)?;
+    Ok(simplify_graphic(&GraphicElement::build(
+        &complex,
+        layout,
+        &projection,
+        &depths,
+    )))
+}
+
+fn start_point(path: &Path) -> Point2D<f32> {
+    path.iter()
+        .find_map(|event| match event {
+            Event::Begin { at } => Some(at),
+            _ => None,
+        })
+        .unwrap_or_else(Point2D::zero)
+}
+
+/// Quote label source as a Python string, escaping anything that would end it early.
+fn python_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 fn render_point(point: Point2D<f32>) -> String {
     let x = ((point.x) * 100.0).round() / 100.0;
     let y = ((point.y) * 100.0).round() / 100.0;
@@ -325,15 +736,103 @@ fn max_point_path(path: &Path) -> Point2D<f32> {
     max_point
 }
 
-fn render_vertex(generator_style: &impl GeneratorStyle, color: &str) -> String {
-    use VertexShape::{Circle, Square};
-    const CIRCLE_RADIUS: f32 = 0.125 / 2.0;
-    const SQUARE_SIDELENGTH: f32 = 0.125 / 2.;
+fn surface_style(signature_styles: &impl SignatureStyleData, d: Diagram0) -> SurfaceStyle {
+    signature_styles
+        .generator_style(d.generator)
+        .map(GeneratorStyle::surface_style)
+        .unwrap_or_default()
+}
+
+fn wire_style(signature_styles: &impl SignatureStyleData, d: Diagram0) -> WireStyle {
+    signature_styles
+        .generator_style(d.generator)
+        .map(GeneratorStyle::wire_style)
+        .unwrap_or_default()
+}
+
+fn render_surface(path: &Path, color: &str, style: SurfaceStyle) -> String {
+    if style.is_opaque() {
+        format!(
+            "self.build_path({path},width=1).set_fill(C[\"{color}\"],1)",
+            path = render_path(path)
+        )
+    } else {
+        // The outline would show over translucent surfaces.
+        format!(
+            "self.build_path({path},width=0).set_fill(C[\"{color}\"],{opacity})",
+            path = render_path(path),
+            opacity = style.alpha()
+        )
+    }
+}
+
+// Stroke width of wires, and their approximate thickness in layout coordinates.
+const WIRE_WIDTH: f32 = 20.;
+const WIRE_THICKNESS: f32 = 0.15;
 
-    match generator_style.shape() {
-        Circle => format!("Circle(radius={CIRCLE_RADIUS},color=C[\"{color}\"],fill_opacity=1)",),
-        Square => {
-            format!("Square(side_length={SQUARE_SIDELENGTH},color=C[\"{color}\"],fill_opacity=1)",)
+fn render_wire(path: &Path, color: &str, style: WireStyle) -> String {
+    let wire = format!(
+        "self.build_path({path},width={width},color=C[\"{color}\"])",
+        path = render_path(path),
+        width = WIRE_WIDTH * style.thickness_scale(),
+    );
+
+    let lengths = style.dash_array(WIRE_THICKNESS * style.thickness_scale());
+    if lengths.is_empty() {
+        return wire;
+    }
+
+    // Manim spreads a number of dashes evenly along the path, so work out how many fit.
+    let period: f32 = lengths.iter().sum();
+    let length: f32 = path
+        .iter()
+        .flattened(0.01)
+        .map(|event| match event {
+            Event::Line { from, to } => (to - from).length(),
+            _ => 0.,
+        })
+        .sum();
+    let dashes = ((length / period).round() as usize).max(1);
+    // Dots are short dashes, as Manim does not draw dashes of length zero.
+    let ratio = (lengths[0] / period).max(0.05);
+    format!("DashedVMobject({wire},num_dashes={dashes},dashed_ratio={ratio})")
+}
+
+/// A point at the given position in front of the wires.
+fn render_vertex(
+    generator_style: &impl GeneratorStyle,
+    color: &str,
+    point: Point2D<f32>,
+) -> String {
+    const RADIUS: f32 = 0.125 / 2.0;
+    // The outline of hollow shapes, in the units of stroke widths.
+    const OUTLINE: f32 = 6.;
+
+    let shape = generator_style.shape();
+    let style = if shape.is_hollow() {
+        format!("color=C[\"{color}\"],stroke_width={OUTLINE},fill_color=C[\"background\"],fill_opacity=1")
+    } else {
+        format!("color=C[\"{color}\"],fill_opacity=1")
+    };
+    match shape.polygon() {
+        None => format!(
+            "Circle(radius={RADIUS},{style}).move_to(np.array([{x},{y},1]))",
+            x = point.x,
+            y = point.y
+        ),
+        Some(corners) => {
+            // Polygons are given in place, as moving them would centre their bounding box.
+            let corners = corners
+                .into_iter()
+                .map(|[x, y]| {
+                    format!(
+                        "np.array([{},{},1])",
+                        point.x + x * RADIUS,
+                        point.y + y * RADIUS
+                    )
+                })
+                .join(",");
+            format!("Polygon({corners},{style})")
         }
     }
 }
@@ -0,0 +1,147 @@
+use homotopy_common::hash::FastHashMap;
+use homotopy_core::{common::DimensionError, Diagram, Diagram0, Orientation};
+use ultraviolet::Vec3;
+
+use crate::{
+    geom::{CubicalGeometry, SimplicialGeometry, Vert, VertData},
+    style::{Color, GeneratorStyle, SignatureStyleData, Theme},
+};
+
+/// Options controlling how finely a 3-dimensional diagram is meshed for export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshOptions {
+    /// Number of rounds of cubical subdivision.
+    pub subdivision_depth: u8,
+    /// Number of samples used when inflating wires and points into tubes and spheres.
+    pub samples: u8,
+    /// The theme the colours of the mesh are shaded for.
+    pub theme: Theme,
+}
+
+impl Default for MeshOptions {
+    fn default() -> Self {
+        Self {
+            subdivision_depth: 3,
+            samples: 3,
+            theme: Theme::default(),
+        }
+    }
+}
+
+/// A triangle mesh of a 3-dimensional diagram, split into groups of uniform colour.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub groups: Vec<MeshGroup>,
+}
+
+/// The triangles of a mesh belonging to a single generator at a single dimension.
+///
+/// Vertices are not shared between groups, so that every vertex has exactly one colour.
+#[derive(Debug, Clone)]
+pub struct MeshGroup {
+    /// A name unique within the mesh, suitable for naming materials and objects. Groups of
+    /// different meshes of the same diagram share names if and only if they share colours.
+    pub name: String,
+    pub generator: Diagram0,
+    pub color: Color,
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl Mesh {
+    pub fn new(
+        diagram: &Diagram,
+        signature_styles: &impl SignatureStyleData,
+        options: MeshOptions,
+    ) -> Result<Self, DimensionError> {
+        let simplicial = geometry(diagram, signature_styles, options)?;
+        Ok(Self::from_geometry(
+            &simplicial,
+            diagram.dimension(),
+            signature_styles,
+            options.theme,
+        ))
+    }
+
+    /// Build a mesh from the areas of inflated 3-dimensional geometry, coloured as it would be
+    /// in a diagram of the given dimension.
+    pub fn from_geometry(
+        simplicial: &SimplicialGeometry,
+        dimension: usize,
+        signature_styles: &impl SignatureStyleData,
+        theme: Theme,
+    ) -> Self {
+        let normals = simplicial.compute_normals_3d();
+
+        let color_of = |generator: Diagram0, k: usize| -> Color {
+            let c = dimension.saturating_sub(generator.generator.dimension + k);
+            let color = signature_styles
+                .generator_style(generator.generator)
+                .map(GeneratorStyle::color)
+                .unwrap_or_default();
+            theme.shade(&color, c, generator.orientation)
+        };
+
+        let mut mesh = Self::default();
+        let mut group_indices: FastHashMap<(Diagram0, usize), usize> = FastHashMap::default();
+        let mut vert_indices: Vec<FastHashMap<Vert, u32>> = Vec::new();
+
+        for (tri, parity) in simplicial.areas.values().copied() {
+            let VertData { generator, k, .. } = simplicial.verts[tri[0]];
+            let index = *group_indices.entry((generator, k)).or_insert_with(|| {
+                mesh.groups.push(MeshGroup {
+                    name: group_name(generator, k),
+                    generator,
+                    color: color_of(generator, k),
+                    positions: Vec::new(),
+                    normals: Vec::new(),
+                    triangles: Vec::new(),
+                });
+                vert_indices.push(FastHashMap::default());
+                mesh.groups.len() - 1
+            });
+
+            let group = &mut mesh.groups[index];
+            let [a, b, c] = tri.map(|v| {
+                *vert_indices[index].entry(v).or_insert_with(|| {
+                    group.positions.push(simplicial.verts[v].position.xyz());
+                    group.normals.push(normals[v]);
+                    (group.positions.len() - 1) as u32
+                })
+            });
+
+            if parity.is_even() {
+                group.triangles.push([a, b, c]);
+            } else {
+                group.triangles.push([c, b, a]);
+            }
+        }
+
+        mesh
+    }
+}
+
+fn group_name(generator: Diagram0, k: usize) -> String {
+    let orientation = match generator.orientation {
+        Orientation::Positive => "",
+        Orientation::Zero => "_identity",
+        Orientation::Negative => "_inverse",
+    };
+    format!("generator_{}_{k}{orientation}", generator.generator.id)
+}
+
+/// Build the inflated simplicial geometry of a 3-dimensional diagram.
+pub fn geometry(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
+) -> Result<SimplicialGeometry, DimensionError> {
+    let mut cubical = CubicalGeometry::new::<3>(diagram, false)?;
+    cubical.subdivide(false, options.subdivision_depth);
+
+    let mut simplicial = SimplicialGeometry::from(cubical);
+    simplicial.inflate_3d(options.samples, signature_styles);
+
+    Ok(simplicial)
+}
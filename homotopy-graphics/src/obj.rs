@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use homotopy_common::hash::FastHashSet;
+use homotopy_core::{common::DimensionError, Diagram};
+
+use crate::{
+    animation::{self, AnimationOptions},
+    mesh::{Mesh, MeshGroup, MeshOptions},
+    style::SignatureStyleData,
+};
+
+/// Render a 3-dimensional diagram as a Wavefront OBJ file and its accompanying MTL material
+/// library, which the OBJ file refers to by the given file name.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
+    material_library: &str,
+) -> Result<(String, String), DimensionError> {
+    let mesh = Mesh::new(diagram, signature_styles, options)?;
+    Ok((
+        render_mesh(&mesh, Some(material_library)),
+        render_materials(&mesh.groups),
+    ))
+}
+
+/// Render the animated view of a diagram as a sequence of OBJ files, one per frame, together with
+/// a single material library shared by all of them.
+pub fn render_sequence(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: AnimationOptions,
+    material_library: &str,
+) -> Result<(Vec<String>, String), DimensionError> {
+    let frames = animation::frames(diagram, signature_styles, options)?;
+    Ok((
+        frames
+            .iter()
+            .map(|frame| render_mesh(frame, Some(material_library)))
+            .collect(),
+        render_materials(frames.iter().flat_map(|frame| &frame.groups)),
+    ))
+}
+
+/// Render a mesh as a Wavefront OBJ file, optionally referring to a material library for its
+/// colours.
+pub fn render_mesh(mesh: &Mesh, material_library: Option<&str>) -> String {
+    let mut output = String::new();
+
+    if let Some(material_library) = material_library {
+        writeln!(output, "mtllib {material_library}").unwrap();
+    }
+    writeln!(output, "o assoc").unwrap();
+
+    // OBJ indices are 1-based and shared by the whole file.
+    let mut offset = 1;
+
+    for group in &mesh.groups {
+        for v in &group.positions {
+            writeln!(output, "v {} {} {}", v.x, v.y, v.z).unwrap();
+        }
+        for n in &group.normals {
+            writeln!(output, "vn {} {} {}", n.x, n.y, n.z).unwrap();
+        }
+
+        writeln!(output, "g {}", group.name).unwrap();
+        if material_library.is_some() {
+            writeln!(output, "usemtl {}", group.name).unwrap();
+        }
+        for [i, j, k] in group
+            .triangles
+            .iter()
+            .map(|t| t.map(|v| v as usize + offset))
+        {
+            writeln!(output, "f {i}//{i} {j}//{j} {k}//{k}").unwrap();
+        }
+
+        offset += group.positions.len();
+    }
+
+    output
+}
+
+/// Render the materials of the given groups, once for each distinct group name.
+pub fn render_materials<'a>(groups: impl IntoIterator<Item = &'a MeshGroup>) -> String {
+    let mut output = String::new();
+    let mut seen = FastHashSet::default();
+
+    for group in groups {
+        if !seen.insert(&group.name) {
+            continue;
+        }
+
+        let (r, g, b) = group.color.clone().into_linear_f32_components();
+        writeln!(output, "newmtl {}", group.name).unwrap();
+        writeln!(output, "Kd {r} {g} {b}").unwrap();
+        writeln!(output, "Ka 0 0 0").unwrap();
+        writeln!(output, "Ks 0.2 0.2 0.2").unwrap();
+        writeln!(output, "Ns 50").unwrap();
+        writeln!(output, "illum 2").unwrap();
+        writeln!(output).unwrap();
+    }
+
+    output
+}
@@ -0,0 +1,273 @@
+use std::{collections::BTreeSet, fmt::Write as _, io::Write as _};
+
+use flate2::{write::ZlibEncoder, Compression};
+use homotopy_core::{common::DimensionError, Diagram};
+use lyon_path::{Event, Path};
+
+use crate::{
+    figure::{Figure, FigureElement, FigureOptions, SURFACE_OUTLINE},
+    style::{Color, SignatureStyleData, VertexShape},
+    svg::shape::Point,
+};
+
+/// Control point distance for approximating a quarter circle by a cubic Bézier curve.
+const KAPPA: f32 = 0.552_285;
+
+/// Render a 2-dimensional diagram as a single page vector PDF file.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: FigureOptions,
+) -> Result<Vec<u8>, DimensionError> {
+    Ok(render_figure(&Figure::new(
+        diagram,
+        signature_styles,
+        options,
+    )?))
+}
+
+/// Write a figure as a single page PDF file, one point in the figure being one point on the page.
+///
+/// Wires passing behind others are cut by a soft mask, which is painted white everywhere except
+/// for thick black strokes along the wires in front, like the masks of the SVG view.
+pub fn render_figure(figure: &Figure) -> Vec<u8> {
+    let (width, height) = (figure.width, figure.height);
+    let wire_thickness = figure.options.wire_thickness;
+
+    let mut content = String::new();
+    // Flip the page so that the coordinates of the figure can be used as they are.
+    writeln!(content, "1 0 0 -1 0 {height} cm").unwrap();
+    writeln!(content, "1 J 1 j").unwrap();
+    if let Some(background) = &figure.options.background() {
+        writeln!(content, "{} rg 0 0 {width} {height} re f", rgb(background)).unwrap();
+    }
+
+    let mut masks = Vec::new();
+    let mut opacities = BTreeSet::new();
+    for element in &figure.elements {
+        match element {
+            FigureElement::Surface(_, color, style, path) if !style.is_opaque() => {
+                // Translucent surfaces are not outlined, as the outline would show over the fill.
+                opacities.insert(style.opacity);
+                writeln!(content, "q /A{} gs {} rg", style.opacity, rgb(color)).unwrap();
+                write_path(&mut content, path);
+                content.push_str("f Q\n");
+            }
+            FigureElement::Surface(_, color, _, path) => {
+                let color = rgb(color);
+                writeln!(content, "{color} rg {color} RG {SURFACE_OUTLINE} w").unwrap();
+                write_path(&mut content, path);
+                content.push_str("B\n");
+            }
+            FigureElement::Wire(_, color, style, path, mask) => {
+                if !mask.is_empty() {
+                    let mut form = format!("1 g 0 0 {width} {height} re f\n");
+                    writeln!(form, "0 G 1 J {} w", 2. * wire_thickness).unwrap();
+                    for path in mask {
+                        write_path(&mut form, path);
+                        form.push_str("S\n");
+                    }
+                    masks.push(form);
+                    writeln!(content, "q /M{} gs", masks.len() - 1).unwrap();
+                }
+                let width = wire_thickness * style.thickness_scale();
+                let dashes = style
+                    .dash_array(width)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                writeln!(content, "{} RG {width} w [{dashes}] 0 d", rgb(color)).unwrap();
+                write_path(&mut content, path);
+                content.push_str("S\n");
+                if !mask.is_empty() {
+                    content.push_str("Q\n");
+                }
+            }
+            FigureElement::Point(_, color, shape, point) => {
+                let radius = figure.options.point_radius;
+                let mut outline = String::new();
+                match shape.polygon() {
+                    None => write_circle(&mut outline, *point, radius),
+                    Some(corners) => write_polygon(&mut outline, *point, radius, &corners),
+                }
+                if shape.is_hollow() {
+                    writeln!(
+                        content,
+                        "{} rg {} RG {} w [] 0 d",
+                        rgb(&figure.options.theme.paper()),
+                        rgb(color),
+                        VertexShape::OUTLINE * radius
+                    )
+                    .unwrap();
+                    content.push_str(&outline);
+                    content.push_str("B\n");
+                } else {
+                    writeln!(content, "{} rg", rgb(color)).unwrap();
+                    content.push_str(&outline);
+                    content.push_str("f\n");
+                }
+            }
+        }
+    }
+
+    // Objects 1 to 4 are the catalog, page tree, page and its content, followed by a graphics
+    // state and a form for every mask.
+    let mask_object = |i: usize| 5 + 2 * i;
+    let graphics_states: String = (0..masks.len())
+        .map(|i| format!("/M{i} {} 0 R ", mask_object(i)))
+        .chain(
+            opacities
+                .iter()
+                .map(|opacity| format!("/A{opacity} << /ca {} >> ", f32::from(*opacity) / 100.)),
+        )
+        .collect();
+
+    let mut objects = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width} {height}] /Contents 4 0 R \
+             /Resources << /ExtGState << {graphics_states}>> >> >>"
+        )
+        .into_bytes(),
+        stream("", content.as_bytes()),
+    ];
+    for (i, form) in masks.iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /ExtGState /SMask << /Type /Mask /S /Luminosity /G {} 0 R >> >>",
+                mask_object(i) + 1
+            )
+            .into_bytes(),
+        );
+        objects.push(stream(
+            &format!(
+                "/Type /XObject /Subtype /Form /BBox [0 0 {width} {height}] \
+                 /Group << /S /Transparency /CS /DeviceRGB >>"
+            ),
+            form.as_bytes(),
+        ));
+    }
+
+    write_document(&objects)
+}
+
+fn rgb(color: &Color) -> String {
+    let (r, g, b) = color.clone().into_components::<f32>();
+    format!("{r:.3} {g:.3} {b:.3}")
+}
+
+fn write_path(output: &mut String, path: &Path) {
+    for event in path {
+        match event {
+            Event::Begin { at } => writeln!(output, "{:.2} {:.2} m", at.x, at.y),
+            Event::Line { to, .. } => writeln!(output, "{:.2} {:.2} l", to.x, to.y),
+            Event::Quadratic { from, ctrl, to } => {
+                // PDF only has cubic curves, which quadratic ones are a special case of.
+                let ctrl1 = from + (ctrl - from) * (2. / 3.);
+                let ctrl2 = to + (ctrl - to) * (2. / 3.);
+                writeln!(
+                    output,
+                    "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                    ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+                )
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => writeln!(
+                output,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+            ),
+            Event::End { close: true, .. } => writeln!(output, "h"),
+            Event::End { close: false, .. } => Ok(()),
+        }
+        .unwrap();
+    }
+}
+
+/// Write the outline of a vertex shape, whose corners are given in units of the radius with the
+/// y-axis pointing up.
+fn write_polygon(output: &mut String, center: Point, radius: f32, corners: &[[f32; 2]]) {
+    for (i, [x, y]) in corners.iter().enumerate() {
+        let operator = if i == 0 { "m" } else { "l" };
+        writeln!(
+            output,
+            "{:.2} {:.2} {operator}",
+            center.x + x * radius,
+            center.y - y * radius
+        )
+        .unwrap();
+    }
+    output.push_str("h\n");
+}
+
+fn write_circle(output: &mut String, center: Point, radius: f32) {
+    let r = radius;
+    let k = KAPPA * radius;
+    writeln!(output, "{:.2} {:.2} m", center.x + r, center.y).unwrap();
+    for [(x1, y1), (x2, y2), (x3, y3)] in [
+        [(r, k), (k, r), (0., r)],
+        [(-k, r), (-r, k), (-r, 0.)],
+        [(-r, -k), (-k, -r), (0., -r)],
+        [(k, -r), (r, -k), (r, 0.)],
+    ] {
+        writeln!(
+            output,
+            "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+            center.x + x1,
+            center.y + y1,
+            center.x + x2,
+            center.y + y2,
+            center.x + x3,
+            center.y + y3
+        )
+        .unwrap();
+    }
+    output.push_str("h\n");
+}
+
+/// A compressed stream object with the given extra dictionary entries.
+fn stream(dictionary: &str, data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    let data = encoder.finish().unwrap();
+
+    let mut object = format!(
+        "<< {dictionary} /Filter /FlateDecode /Length {} >>\nstream\n",
+        data.len()
+    )
+    .into_bytes();
+    object.extend_from_slice(&data);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+/// Assemble numbered objects, starting from 1, into a PDF file with a cross-reference table.
+fn write_document(objects: &[Vec<u8>]) -> Vec<u8> {
+    // The comment of binary characters marks the file as binary to transfer programs.
+    let mut output = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(output.len());
+        writeln!(output, "{} 0 obj", i + 1).unwrap();
+        output.extend_from_slice(object);
+        output.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = output.len();
+    writeln!(output, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1).unwrap();
+    for offset in offsets {
+        writeln!(output, "{offset:010} 00000 n ").unwrap();
+    }
+    writeln!(
+        output,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF",
+        objects.len() + 1
+    )
+    .unwrap();
+
+    output
+}
@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use homotopy_core::{common::DimensionError, Diagram};
+
+use crate::{
+    mesh::{Mesh, MeshOptions},
+    style::SignatureStyleData,
+};
+
+/// Render a 3-dimensional diagram as an ASCII PLY file with per-vertex normals and colours.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
+) -> Result<String, DimensionError> {
+    let mesh = Mesh::new(diagram, signature_styles, options)?;
+    let mut output = String::new();
+
+    let verts: usize = mesh.groups.iter().map(|group| group.positions.len()).sum();
+    let faces: usize = mesh.groups.iter().map(|group| group.triangles.len()).sum();
+
+    writeln!(output, "ply").unwrap();
+    writeln!(output, "format ascii 1.0").unwrap();
+    writeln!(output, "comment exported from homotopy.io").unwrap();
+    writeln!(output, "element vertex {verts}").unwrap();
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        writeln!(output, "property float {property}").unwrap();
+    }
+    for property in ["red", "green", "blue"] {
+        writeln!(output, "property uchar {property}").unwrap();
+    }
+    writeln!(output, "element face {faces}").unwrap();
+    writeln!(output, "property list uchar uint vertex_indices").unwrap();
+    writeln!(output, "end_header").unwrap();
+
+    for group in &mesh.groups {
+        let (r, g, b) = group.color.clone().into_components::<u8>();
+        for (v, n) in group.positions.iter().zip(&group.normals) {
+            writeln!(
+                output,
+                "{} {} {} {} {} {} {r} {g} {b}",
+                v.x, v.y, v.z, n.x, n.y, n.z
+            )
+            .unwrap();
+        }
+    }
+
+    let mut offset = 0;
+    for group in &mesh.groups {
+        for [i, j, k] in group
+            .triangles
+            .iter()
+            .map(|t| t.map(|v| v as usize + offset))
+        {
+            writeln!(output, "3 {i} {j} {k}").unwrap();
+        }
+        offset += group.positions.len();
+    }
+
+    Ok(output)
+}
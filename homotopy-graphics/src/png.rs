@@ -0,0 +1,100 @@
+use std::io::Write;
+
+use flate2::{write::ZlibEncoder, Compression};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Encode an image of 8-bit RGBA pixels, given row by row, as a PNG file.
+pub fn encode(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    assert_eq!(rgba.len(), 4 * width as usize * height as usize);
+
+    let mut output = SIGNATURE.to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, colour type RGBA, default compression, filtering and no interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut output, b"IHDR", &header);
+
+    // Every scanline is prefixed by its filter type, which is always none.
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    if width > 0 {
+        for row in rgba.chunks(4 * width as usize) {
+            encoder.write_all(&[0]).unwrap();
+            encoder.write_all(row).unwrap();
+        }
+    }
+    write_chunk(&mut output, b"IDAT", &encoder.finish().unwrap());
+
+    write_chunk(&mut output, b"IEND", &[]);
+
+    output
+}
+
+fn write_chunk(output: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(data);
+
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(chunk_type);
+    output.extend_from_slice(data);
+    output.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::{encode, SIGNATURE};
+
+    /// Split a PNG file into its chunks, checking the signature and every checksum.
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        assert_eq!(png[..8], SIGNATURE);
+
+        let mut chunks = Vec::new();
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let chunk_type: [u8; 4] = rest[4..8].try_into().unwrap();
+            let data = &rest[8..8 + length];
+            let crc = u32::from_be_bytes(rest[8 + length..12 + length].try_into().unwrap());
+
+            let mut hasher = crc32fast::Hasher::new();
+            hasher.update(&chunk_type);
+            hasher.update(data);
+            assert_eq!(crc, hasher.finalize());
+
+            chunks.push((chunk_type, data.to_vec()));
+            rest = &rest[12 + length..];
+        }
+        chunks
+    }
+
+    #[test]
+    fn encode_rgba() {
+        let rgba = [255, 0, 0, 255, 0, 255, 0, 128, 0, 0, 255, 0, 10, 20, 30, 40];
+        let png = encode(2, 2, &rgba);
+        let chunks = chunks(&png);
+
+        let types: Vec<_> = chunks.iter().map(|(chunk_type, _)| chunk_type).collect();
+        assert_eq!(types, [b"IHDR", b"IDAT", b"IEND"]);
+
+        let header = &chunks[0].1;
+        assert_eq!(header[..4], 2_u32.to_be_bytes());
+        assert_eq!(header[4..8], 2_u32.to_be_bytes());
+        assert_eq!(header[8..], [8, 6, 0, 0, 0]);
+
+        let mut scanlines = Vec::new();
+        ZlibDecoder::new(&chunks[1].1[..])
+            .read_to_end(&mut scanlines)
+            .unwrap();
+        assert_eq!(scanlines[..9], [0, 255, 0, 0, 255, 0, 255, 0, 128]);
+        assert_eq!(scanlines[9..], [0, 0, 0, 255, 0, 10, 20, 30, 40]);
+
+        assert!(chunks[2].1.is_empty());
+    }
+}
@@ -0,0 +1,408 @@
+use std::f32::consts::PI;
+
+use homotopy_core::{common::DimensionError, Diagram};
+use ultraviolet::{
+    projection::rh_yup::{orthographic_gl, perspective_gl},
+    Mat4, Vec2, Vec3,
+};
+
+use crate::{
+    mesh::{Mesh, MeshOptions},
+    png,
+    style::{SignatureStyleData, Theme},
+};
+
+/// A camera orbiting a target, with the same conventions and defaults as the WebGL view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub phi: f32,
+    pub theta: f32,
+    pub distance: f32,
+    pub target: Vec3,
+    /// Vertical field of view in degrees.
+    pub fov: f32,
+    pub ortho: bool,
+}
+
+impl Camera {
+    pub(crate) const NEAR: f32 = 0.01;
+    const FAR: f32 = 1000.;
+
+    pub fn position(&self) -> Vec3 {
+        let offset = Vec3::new(
+            self.phi.cos() * self.theta.sin(),
+            -self.theta.cos(),
+            -self.phi.sin() * self.theta.sin(),
+        );
+        self.distance * offset + self.target
+    }
+
+    pub fn view_transform(&self) -> Mat4 {
+        Mat4::look_at(self.position(), self.target, Vec3::unit_y())
+    }
+
+    pub fn perspective_transform(&self, aspect: f32) -> Mat4 {
+        if self.ortho {
+            let scale = self.distance / 10.;
+            orthographic_gl(
+                -aspect * scale,
+                aspect * scale,
+                -scale,
+                scale,
+                Self::NEAR,
+                Self::FAR,
+            )
+        } else {
+            perspective_gl(self.fov.to_radians(), aspect, Self::NEAR, Self::FAR)
+        }
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            phi: 0.5 * PI,
+            theta: 0.5 * PI,
+            distance: 12.,
+            target: Vec3::zero(),
+            fov: 30.,
+            ortho: false,
+        }
+    }
+}
+
+/// Lighting parameters, matching the lighting settings of the WebGL view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lighting {
+    pub specularity: f32,
+    pub shininess: f32,
+    pub gamma: f32,
+    pub disable_lighting: bool,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            specularity: 0.25,
+            shininess: 64.,
+            gamma: 2.2,
+            disable_lighting: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterOptions {
+    pub width: u32,
+    pub height: u32,
+    pub camera: Camera,
+    pub lighting: Lighting,
+    /// Leave the background transparent instead of filling it with the paper colour of the
+    /// theme.
+    pub transparent: bool,
+    pub theme: Theme,
+    /// Number of samples taken along each axis of a pixel, for antialiasing.
+    pub supersampling: u32,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            camera: Default::default(),
+            lighting: Default::default(),
+            transparent: false,
+            theme: Theme::default(),
+            supersampling: 2,
+        }
+    }
+}
+
+/// Render a 3-dimensional diagram to a PNG file, without the help of a GPU.
+pub fn render(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    mesh_options: MeshOptions,
+    options: &RasterOptions,
+) -> Result<Vec<u8>, DimensionError> {
+    let mesh = Mesh::new(diagram, signature_styles, mesh_options)?;
+    Ok(png::encode(
+        options.width,
+        options.height,
+        &rasterize(&mesh, options),
+    ))
+}
+
+/// A fragment of a surface visible from the camera, in view space.
+#[derive(Clone, Copy)]
+struct Fragment {
+    depth: f32,
+    position: Vec3,
+    normal: Vec3,
+    albedo: Vec3,
+}
+
+/// Rasterize a mesh into 8-bit RGBA pixels, row by row.
+///
+/// Like the WebGL renderer, this first collects the nearest fragment of every pixel and then
+/// lights each of them once.
+pub fn rasterize(mesh: &Mesh, options: &RasterOptions) -> Vec<u8> {
+    let samples = options.supersampling.max(1);
+    let (width, height) = (options.width * samples, options.height * samples);
+    let size = Vec2::new(width as f32, height as f32);
+
+    let view = options.camera.view_transform();
+    let projection = options
+        .camera
+        .perspective_transform(size.x / size.y.max(1.));
+
+    let mut fragments: Vec<Option<Fragment>> = vec![None; (width * height) as usize];
+
+    for group in &mesh.groups {
+        let albedo = Vec3::from(group.color.clone().into_linear_f32_components());
+
+        let positions: Vec<Vec3> = group
+            .positions
+            .iter()
+            .map(|p| view.transform_point3(*p))
+            .collect();
+        let normals: Vec<Vec3> = group
+            .normals
+            .iter()
+            .map(|n| view.transform_vec3(*n))
+            .collect();
+        let clip: Vec<_> = positions
+            .iter()
+            .map(|p| projection * p.into_homogeneous_point())
+            .collect();
+
+        for triangle in &group.triangles {
+            let tri = triangle.map(|v| v as usize);
+
+            // Triangles reaching behind the camera are dropped rather than clipped.
+            if tri.iter().any(|&v| clip[v].w <= Camera::NEAR) {
+                continue;
+            }
+
+            let screen = tri.map(|v| {
+                let ndc = clip[v].xyz() / clip[v].w;
+                let screen = Vec2::new(0.5 * (ndc.x + 1.), 0.5 * (1. - ndc.y)) * size;
+                (screen, ndc.z)
+            });
+            let [(s_0, _), (s_1, _), (s_2, _)] = screen;
+
+            let area = edge(s_0, s_1, s_2);
+            if area.abs() < f32::EPSILON {
+                continue;
+            }
+            // Flipping the y-axis turns counter-clockwise front faces clockwise.
+            let front_facing = area < 0.;
+
+            let min = s_0.min_by_component(s_1).min_by_component(s_2);
+            let max = s_0.max_by_component(s_1).max_by_component(s_2);
+            let (x_min, y_min) = (min.x.max(0.) as u32, min.y.max(0.) as u32);
+            let (x_max, y_max) = (
+                (max.x.ceil() as u32).min(width),
+                (max.y.ceil() as u32).min(height),
+            );
+
+            for row in y_min..y_max {
+                for column in x_min..x_max {
+                    let pixel = Vec2::new(column as f32 + 0.5, row as f32 + 0.5);
+                    let barycentric = [
+                        edge(s_1, s_2, pixel) / area,
+                        edge(s_2, s_0, pixel) / area,
+                        edge(s_0, s_1, pixel) / area,
+                    ];
+                    if barycentric.iter().any(|&b| b < 0.) {
+                        continue;
+                    }
+
+                    let depth = (0..3).map(|n| barycentric[n] * screen[n].1).sum::<f32>();
+                    let fragment = &mut fragments[(row * width + column) as usize];
+                    if fragment.map_or(false, |f| f.depth <= depth) {
+                        continue;
+                    }
+
+                    // Perspective-correct interpolation of view space attributes.
+                    let weights = [0, 1, 2].map(|n| barycentric[n] / clip[tri[n]].w);
+                    let total: f32 = weights.iter().sum();
+                    let interpolate = |attribute: &[Vec3]| {
+                        (0..3)
+                            .map(|n| weights[n] * attribute[tri[n]])
+                            .fold(Vec3::zero(), |sum, v| sum + v)
+                            / total
+                    };
+
+                    let normal = interpolate(&normals).normalized();
+                    *fragment = Some(Fragment {
+                        depth,
+                        position: interpolate(&positions),
+                        normal: if front_facing { normal } else { -normal },
+                        albedo,
+                    });
+                }
+            }
+        }
+    }
+
+    let camera_distance = options.camera.position().mag();
+    let background = if options.transparent {
+        [0.; 4]
+    } else {
+        let (r, g, b) = options.theme.paper().into_components::<f32>();
+        [r, g, b, 1.]
+    };
+    let colors: Vec<[f32; 4]> = fragments
+        .into_iter()
+        .map(|fragment| {
+            fragment.map_or(background, |f| {
+                let c = shade(&f, &options.lighting, camera_distance);
+                [c.x, c.y, c.z, 1.]
+            })
+        })
+        .collect();
+
+    downsample(&colors, options.width, options.height, samples)
+}
+
+/// Signed area of the parallelogram spanned by `a -> b` and `a -> c`.
+fn edge(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Blinn-Phong shading with the fixed lights of the WebGL lighting pass.
+fn shade(fragment: &Fragment, lighting: &Lighting, camera_distance: f32) -> Vec3 {
+    const AMBIENT: f32 = 0.5;
+
+    let Fragment {
+        position,
+        normal,
+        albedo,
+        ..
+    } = *fragment;
+    let gamma = |c: Vec3| c.map(|x| x.max(0.).powf(1. / lighting.gamma));
+
+    if lighting.disable_lighting {
+        return gamma(albedo);
+    }
+
+    let light_offsets = [
+        Vec3::new(0., 0., 0.25 * 3_f32.sqrt()),
+        Vec3::new(-0.5, 0., -0.25 * 3_f32.sqrt()),
+        Vec3::new(0.5, 0., -0.25 * 3_f32.sqrt()),
+        Vec3::new(0., 1., 0.),
+        Vec3::new(0., -1., 0.),
+    ];
+
+    let mut color = AMBIENT * albedo;
+
+    for offset in light_offsets {
+        let l = (300. * offset + Vec3::new(0., 0., -camera_distance) - position).normalized();
+        let lambertian = l.dot(normal).max(0.);
+        let specular = if lambertian > 0. {
+            let halfway = (l + (-position).normalized()).normalized();
+            halfway.dot(normal).max(0.).powf(lighting.shininess)
+        } else {
+            0.
+        };
+
+        color += 0.4
+            * ((albedo + Vec3::broadcast(0.01)) * lambertian
+                + Vec3::broadcast(lighting.specularity * specular));
+    }
+
+    gamma(color)
+}
+
+/// Average blocks of `samples * samples` colours into 8-bit RGBA pixels.
+pub(crate) fn downsample(colors: &[[f32; 4]], width: u32, height: u32, samples: u32) -> Vec<u8> {
+    let stride = (width * samples) as usize;
+    let mut pixels = Vec::with_capacity((4 * width * height) as usize);
+
+    for row in 0..height as usize {
+        for column in 0..width as usize {
+            // Accumulate premultiplied colours so that the background does not bleed in.
+            let mut sum = [0.; 4];
+            for dy in 0..samples as usize {
+                for dx in 0..samples as usize {
+                    let [red, green, blue, alpha] = colors
+                        [(row * samples as usize + dy) * stride + column * samples as usize + dx];
+                    let premultiplied = [red * alpha, green * alpha, blue * alpha, alpha];
+                    for (total, channel) in sum.iter_mut().zip(premultiplied) {
+                        *total += channel;
+                    }
+                }
+            }
+
+            let alpha = sum[3];
+            let mut pixel = sum.map(|channel| if alpha > 0. { channel / alpha } else { 0. });
+            pixel[3] = alpha / (samples * samples) as f32;
+            pixels.extend(pixel.map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8));
+        }
+    }
+
+    pixels
+}
+
+#[cfg(test)]
+mod test {
+    use homotopy_core::{Diagram0, Generator};
+    use ultraviolet::Vec3;
+
+    use super::{rasterize, RasterOptions};
+    use crate::{
+        mesh::{Mesh, MeshGroup},
+        style::Color,
+    };
+
+    fn triangle() -> Mesh {
+        Mesh {
+            groups: vec![MeshGroup {
+                name: "triangle".to_owned(),
+                generator: Diagram0::from(Generator::new(0, 0)),
+                color: Color::default(),
+                positions: vec![
+                    Vec3::new(-2., -2., 0.),
+                    Vec3::new(2., -2., 0.),
+                    Vec3::new(0., 2., 0.),
+                ],
+                normals: vec![-Vec3::unit_z(); 3],
+                triangles: vec![[0, 1, 2]],
+            }],
+        }
+    }
+
+    #[test]
+    fn rasterize_triangle() {
+        let options = RasterOptions {
+            width: 16,
+            height: 16,
+            transparent: true,
+            supersampling: 1,
+            ..Default::default()
+        };
+        let pixels = rasterize(&triangle(), &options);
+        assert_eq!(pixels.len(), 4 * 16 * 16);
+
+        // The default camera looks straight at the triangle, which covers the centre only.
+        let alpha = |x: usize, y: usize| pixels[4 * (16 * y + x) + 3];
+        assert_eq!(alpha(8, 8), 255);
+        assert_eq!(alpha(0, 0), 0);
+        assert_eq!(alpha(15, 15), 0);
+    }
+
+    #[test]
+    fn rasterize_background() {
+        let options = RasterOptions {
+            width: 4,
+            height: 3,
+            ..Default::default()
+        };
+        let pixels = rasterize(&Mesh::default(), &options);
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert!(pixels.chunks(4).all(|pixel| pixel == &pixels[..4]));
+        assert_eq!(pixels[3], 255);
+    }
+}
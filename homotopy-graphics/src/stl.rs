@@ -3,21 +3,18 @@ use std::fmt::Write;
 use homotopy_core::{common::DimensionError, Diagram};
 
 use crate::{
-    geom::{CubicalGeometry, SimplicialGeometry},
+    mesh::{self, MeshOptions},
     style::SignatureStyleData,
 };
 
 pub fn render(
     diagram: &Diagram,
     signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
 ) -> Result<String, DimensionError> {
     let mut output = String::new();
 
-    let mut cubical = CubicalGeometry::new::<3>(diagram, false)?;
-    cubical.subdivide(false, 3);
-
-    let mut simplicial = SimplicialGeometry::from(cubical);
-    simplicial.inflate_3d(3, signature_styles);
+    let simplicial = mesh::geometry(diagram, signature_styles, options)?;
 
     writeln!(output, "solid assoc").unwrap();
 
@@ -0,0 +1,175 @@
+use homotopy_core::Diagram;
+use itertools::Itertools;
+
+/// Options for drawing the slices of a diagram side by side, connected by arrows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StripOptions {
+    /// Number of times the diagram is sliced. Slices are laid out alternately in rows and
+    /// columns, so slicing twice gives a row of columns.
+    pub depth: usize,
+    /// Include the singular slices between the regular ones.
+    pub singular_slices: bool,
+    /// Space between neighbouring slices, in layout units.
+    pub spacing: f32,
+}
+
+impl Default for StripOptions {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            singular_slices: true,
+            spacing: 1.,
+        }
+    }
+}
+
+/// A diagram sliced up to some depth, with a picture at every leaf.
+#[derive(Debug, Clone)]
+pub enum Strip<T> {
+    Picture(T),
+    Slices(Vec<Strip<T>>),
+}
+
+impl Strip<Diagram> {
+    /// Slice a diagram as many times as requested, but never below dimension 2.
+    pub fn new(diagram: &Diagram, options: &StripOptions) -> Self {
+        match diagram {
+            Diagram::DiagramN(diagram) if options.depth > 0 && diagram.dimension() > 2 => {
+                let options = StripOptions {
+                    depth: options.depth - 1,
+                    ..*options
+                };
+                let step = if options.singular_slices { 1 } else { 2 };
+                Self::Slices(
+                    diagram
+                        .slices()
+                        .step_by(step)
+                        .map(|slice| Self::new(&slice, &options))
+                        .collect(),
+                )
+            }
+            _ => Self::Picture(diagram.clone()),
+        }
+    }
+}
+
+impl<T> Strip<T> {
+    pub fn try_map<U, E>(&self, f: &impl Fn(&T) -> Result<U, E>) -> Result<Strip<U>, E> {
+        match self {
+            Self::Picture(picture) => f(picture).map(Strip::Picture),
+            Self::Slices(slices) => slices
+                .iter()
+                .map(|slice| slice.try_map(f))
+                .collect::<Result<_, _>>()
+                .map(Strip::Slices),
+        }
+    }
+
+    /// Wrap the outermost slices of a strip into rows of the given length, which
+    /// [`Strip::arrange_rows`] lays out as a grid.
+    pub fn wrap(self, columns: usize) -> Self {
+        match self {
+            Self::Slices(slices) if columns > 0 => Self::Slices(
+                slices
+                    .into_iter()
+                    .chunks(columns)
+                    .into_iter()
+                    .map(|row| Self::Slices(row.collect()))
+                    .collect(),
+            ),
+            strip => strip,
+        }
+    }
+
+    /// Lay out the pictures of a strip given their sizes, with the outermost slices in a row.
+    ///
+    /// Coordinates have the origin in the top left corner and the y-axis pointing down.
+    pub fn arrange(&self, size: &impl Fn(&T) -> [f32; 2], spacing: f32) -> Arrangement<'_, T> {
+        self.arrange_along(size, spacing, 0)
+    }
+
+    /// Lay out the pictures of a strip like [`Strip::arrange`], but with the outermost slices in
+    /// a column.
+    pub fn arrange_rows(&self, size: &impl Fn(&T) -> [f32; 2], spacing: f32) -> Arrangement<'_, T> {
+        self.arrange_along(size, spacing, 1)
+    }
+
+    fn arrange_along(
+        &self,
+        size: &impl Fn(&T) -> [f32; 2],
+        spacing: f32,
+        along: usize,
+    ) -> Arrangement<'_, T> {
+        let slices = match self {
+            Self::Picture(picture) => {
+                return Arrangement {
+                    size: size(picture),
+                    pictures: vec![([0., 0.], picture)],
+                    arrows: vec![],
+                }
+            }
+            Self::Slices(slices) => slices,
+        };
+
+        let across = 1 - along;
+        let children: Vec<_> = slices
+            .iter()
+            .map(|slice| slice.arrange_along(size, spacing, across))
+            .collect();
+        let thickness = children
+            .iter()
+            .map(|child| child.size[across])
+            .fold(0., f32::max);
+
+        let mut arrangement = Arrangement {
+            size: [0., 0.],
+            pictures: vec![],
+            arrows: vec![],
+        };
+        let mut offset = 0.;
+        for (i, child) in children.into_iter().enumerate() {
+            if i > 0 {
+                // Arrows take up the middle half of the gap between two slices.
+                let mut from = [0., 0.];
+                from[along] = offset - 0.75 * spacing;
+                from[across] = 0.5 * thickness;
+                let mut to = from;
+                to[along] = offset - 0.25 * spacing;
+                arrangement.arrows.push((from, to));
+            }
+
+            // Slices are centred across the strip.
+            let mut shift = [0., 0.];
+            shift[along] = offset;
+            shift[across] = 0.5 * (thickness - child.size[across]);
+            let translate = |p: [f32; 2]| [p[0] + shift[0], p[1] + shift[1]];
+            arrangement.pictures.extend(
+                child
+                    .pictures
+                    .into_iter()
+                    .map(|(position, picture)| (translate(position), picture)),
+            );
+            arrangement.arrows.extend(
+                child
+                    .arrows
+                    .into_iter()
+                    .map(|(from, to)| (translate(from), translate(to))),
+            );
+
+            offset += child.size[along] + spacing;
+        }
+
+        arrangement.size[along] = (offset - spacing).max(0.);
+        arrangement.size[across] = thickness;
+        arrangement
+    }
+}
+
+/// The positions of the pictures of a strip and of the arrows between them.
+#[derive(Debug, Clone)]
+pub struct Arrangement<'a, T> {
+    pub size: [f32; 2],
+    /// The top left corner of every picture.
+    pub pictures: Vec<([f32; 2], &'a T)>,
+    pub arrows: Vec<([f32; 2], [f32; 2])>,
+}
@@ -1,12 +1,19 @@
-use std::{fmt, str::FromStr};
+use std::{
+    f32::consts::{FRAC_PI_2, PI},
+    fmt,
+    str::FromStr,
+};
 
 use homotopy_core::{signature::Signature, Generator, Orientation};
 use palette::{convert::FromColor, Hsl, Srgb};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 pub trait GeneratorStyle {
     fn color(&self) -> Color;
     fn shape(&self) -> VertexShape;
+    fn wire_style(&self) -> WireStyle;
+    fn surface_style(&self) -> SurfaceStyle;
     fn label(&self) -> Option<String>;
 }
 
@@ -19,10 +26,147 @@ pub trait SignatureStyleData: Signature {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Color(pub(crate) Srgb<u8>);
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum VertexShape {
-    Circle, // circle / sphere
-    Square, // square / cube
+    Circle,      // circle / sphere
+    Square,      // square / cube
+    Diamond,     // diamond / octahedron
+    Triangle,    // triangle / tetrahedron
+    Star,        // five-pointed star / sphere
+    Hollow,      // circle outline / sphere
+    LabelledBox, // box holding the label / cube
+}
+
+impl VertexShape {
+    pub const ALL: [Self; 7] = [
+        Self::Circle,
+        Self::Square,
+        Self::Diamond,
+        Self::Triangle,
+        Self::Star,
+        Self::Hollow,
+        Self::LabelledBox,
+    ];
+
+    /// Width of the outline of hollow shapes, relative to the point radius.
+    pub const OUTLINE: f32 = 0.4;
+
+    /// The corners of the shape around the origin in units of the point radius, with the y-axis
+    /// pointing up, or nothing for round shapes.
+    pub fn polygon(&self) -> Option<Vec<[f32; 2]>> {
+        match self {
+            Self::Circle | Self::Hollow => None,
+            Self::Square => Some(vec![[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]]),
+            Self::Diamond => Some(star(2, 1.4, 1.4)),
+            Self::Triangle => Some(star(3, 1.5, 0.75)),
+            Self::Star => Some(star(5, 1.6, 0.65)),
+            Self::LabelledBox => Some(vec![[-2., -1.3], [2., -1.3], [2., 1.3], [-2., 1.3]]),
+        }
+    }
+
+    /// Hollow shapes are filled with the paper colour of the theme and outlined in the colour of
+    /// their generator.
+    pub fn is_hollow(&self) -> bool {
+        matches!(self, Self::Hollow | Self::LabelledBox)
+    }
+
+    /// The shape of points in 3-dimensional renderings, which is a sphere, cube, octahedron or
+    /// tetrahedron as given by the corresponding flat shape.
+    #[must_use]
+    pub fn solid(&self) -> Self {
+        match self {
+            Self::Circle | Self::Star | Self::Hollow => Self::Circle,
+            Self::Square | Self::LabelledBox => Self::Square,
+            Self::Diamond => Self::Diamond,
+            Self::Triangle => Self::Triangle,
+        }
+    }
+}
+
+/// Corners of a star with `n` points pointing up, alternating between the outer and inner radius.
+/// The two radii being in ratio cos(π/n) gives a regular polygon.
+fn star(n: usize, outer: f32, inner: f32) -> Vec<[f32; 2]> {
+    (0..2 * n)
+        .map(|i| {
+            let angle = FRAC_PI_2 + i as f32 * PI / n as f32;
+            let radius = if i % 2 == 0 { outer } else { inner };
+            [radius * angle.cos(), radius * angle.sin()]
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DashPattern {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl DashPattern {
+    pub const ALL: [Self; 3] = [Self::Solid, Self::Dashed, Self::Dotted];
+
+    /// Lengths of alternating dashes and gaps in units of the wire thickness, which are empty for
+    /// solid wires. Dashes are drawn with round caps, which extend them by half the thickness on
+    /// either side, so dots are dashes of length zero.
+    pub fn lengths(self) -> &'static [f32] {
+        match self {
+            Self::Solid => &[],
+            Self::Dashed => &[2., 2.5],
+            Self::Dotted => &[0., 2.],
+        }
+    }
+}
+
+/// How the wires of a generator are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WireStyle {
+    pub dash: DashPattern,
+    /// Thickness in percent of the default thickness.
+    pub thickness: u16,
+}
+
+impl WireStyle {
+    pub fn thickness_scale(self) -> f32 {
+        f32::from(self.thickness) / 100.
+    }
+
+    /// Lengths of alternating dashes and gaps of a wire of the given width.
+    pub fn dash_array(self, width: f32) -> Vec<f32> {
+        self.dash.lengths().iter().map(|l| l * width).collect()
+    }
+}
+
+impl Default for WireStyle {
+    fn default() -> Self {
+        Self {
+            dash: DashPattern::Solid,
+            thickness: 100,
+        }
+    }
+}
+
+/// How the surfaces of a generator are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SurfaceStyle {
+    /// Opacity in percent.
+    pub opacity: u8,
+}
+
+impl SurfaceStyle {
+    pub fn alpha(self) -> f32 {
+        f32::from(self.opacity.min(100)) / 100.
+    }
+
+    pub fn is_opaque(self) -> bool {
+        self.opacity >= 100
+    }
+}
+
+impl Default for SurfaceStyle {
+    fn default() -> Self {
+        Self { opacity: 100 }
+    }
 }
 
 impl Color {
@@ -68,10 +212,46 @@ impl Color {
     #[inline]
     #[must_use]
     pub fn lighten(&self, c: usize, orientation: Orientation) -> Self {
+        self.map_lightness(|lightness| Self::shift_lightness(lightness, c, orientation))
+    }
+
+    /// The same as [`Color::lighten`] with the lightness scale turned upside down, so that colours
+    /// get darker instead. Colours too dark to stand out against a dark background are first
+    /// turned into their light counterparts.
+    #[must_use]
+    pub fn darken(&self, c: usize, orientation: Orientation) -> Self {
+        self.map_lightness(|lightness| {
+            let lightness = if lightness < Self::MIN_LIGHTNESS_WRAP {
+                1. - lightness
+            } else {
+                lightness
+            };
+            1. - Self::shift_lightness(1. - lightness, c, orientation)
+        })
+    }
+
+    /// The grey of the same relative luminance, so that colours keep their brightness when
+    /// printed in black and white.
+    #[must_use]
+    pub fn greyscale(&self) -> Self {
+        let (r, g, b) = self.clone().into_linear_f32_components();
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let grey: Srgb<f32> =
+            Srgb::from_linear(palette::LinSrgb::new(luminance, luminance, luminance));
+        Self(grey.into_format())
+    }
+
+    fn map_lightness(&self, f: impl FnOnce(f32) -> f32) -> Self {
         let mut hsl: Hsl = FromColor::from_color(self.0.into_format::<f32>());
-        let (min_lightness, max_lightness) = if hsl.lightness < Self::MIN_LIGHTNESS_WRAP {
+        hsl.lightness = f(hsl.lightness);
+        let srgb: Srgb<f32> = FromColor::from_color(hsl);
+        Self(srgb.into_format())
+    }
+
+    fn shift_lightness(lightness: f32, c: usize, orientation: Orientation) -> f32 {
+        let (min_lightness, max_lightness) = if lightness < Self::MIN_LIGHTNESS_WRAP {
             (0., 1.)
-        } else if hsl.lightness > Self::MAX_LIGHTNESS_WRAP {
+        } else if lightness > Self::MAX_LIGHTNESS_WRAP {
             (Self::MIN_LIGHTNESS_WRAP, 1.)
         } else {
             (Self::MIN_LIGHTNESS_WRAP, Self::MAX_LIGHTNESS_WRAP)
@@ -83,12 +263,9 @@ impl Color {
         };
         let offset = 3 * (1 - r) + (c as isize);
         let o = 0.08 * offset as f32;
-        hsl.lightness = (hsl.lightness + o - min_lightness - 0.01)
-            % (max_lightness - min_lightness)
+        (lightness + o - min_lightness - 0.01) % (max_lightness - min_lightness)
             + min_lightness
-            + 0.01;
-        let srgb: Srgb<f32> = FromColor::from_color(hsl);
-        Self(srgb.into_format())
+            + 0.01
     }
 
     pub fn into_components<T>(self) -> (T, T, T)
@@ -133,6 +310,217 @@ impl Default for VertexShape {
     }
 }
 
+/// Colours new generators are given in turn, by their identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    pub colors: Vec<Color>,
+}
+
+#[derive(Debug, Error)]
+pub enum PaletteError {
+    #[error("the palette has no colours")]
+    Empty,
+    #[error("invalid colour {0:?}")]
+    InvalidColor(String),
+    #[error("invalid palette file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The palettes which come with the app. All but the first are designed to stay distinguishable
+/// with the common forms of colour blindness.
+const BUILTIN_PALETTES: &[(&str, &[&str])] = &[
+    (
+        "Flat",
+        &[
+            "#2980b9", // belize blue
+            "#c0392b", // pomegranate
+            "#f39c12", // orange
+            "#8e44ad", // wisteria
+            "#27ae60", // nephritis
+            "#f1c40f", // sunflower
+            "#f6f5f4", // white(ish)
+            "#000000", // black
+        ],
+    ),
+    (
+        "Okabe-Ito",
+        &[
+            "#0072b2", "#d55e00", "#e69f00", "#cc79a7", "#009e73", "#f0e442", "#56b4e9", "#000000",
+        ],
+    ),
+    (
+        "Tol bright",
+        &[
+            "#4477aa", "#ee6677", "#228833", "#ccbb44", "#66ccee", "#aa3377", "#bbbbbb",
+        ],
+    ),
+    (
+        "Tol muted",
+        &[
+            "#332288", "#88ccee", "#44aa99", "#117733", "#999933", "#ddcc77", "#cc6677", "#882255",
+            "#aa4499",
+        ],
+    ),
+    (
+        "IBM",
+        &["#648fff", "#785ef0", "#dc267f", "#fe6100", "#ffb000"],
+    ),
+];
+
+impl Palette {
+    pub fn builtin() -> Vec<Self> {
+        BUILTIN_PALETTES
+            .iter()
+            .map(|(name, colors)| Self {
+                name: (*name).to_owned(),
+                colors: colors.iter().map(|c| c.parse().unwrap()).collect(),
+            })
+            .collect()
+    }
+
+    /// The colour of the generator with the given identifier.
+    pub fn color(&self, id: usize) -> Color {
+        if self.colors.is_empty() {
+            return Color::default();
+        }
+        self.colors[id % self.colors.len()].clone()
+    }
+
+    /// Read a palette from a file, which is either a JSON object with a name and a list of
+    /// colours, or just the colours separated by whitespace, commas or semicolons. Colours are
+    /// given as hex codes, with or without a leading hash.
+    pub fn parse(name: &str, text: &str) -> Result<Self, PaletteError> {
+        #[derive(Deserialize)]
+        struct PaletteFile {
+            name: String,
+            colors: Vec<String>,
+        }
+
+        let (name, codes) = if text.trim_start().starts_with('{') {
+            let file: PaletteFile = serde_json::from_str(text)?;
+            (file.name, file.colors)
+        } else {
+            let codes = text
+                .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                .map(|code| code.trim_matches(|c| c == '"' || c == '\''))
+                .filter(|code| !code.is_empty())
+                .map(ToOwned::to_owned)
+                .collect();
+            (name.to_owned(), codes)
+        };
+
+        let colors = codes
+            .into_iter()
+            .map(|code| {
+                code.trim_start_matches('#')
+                    .parse()
+                    .map_err(|_err| PaletteError::InvalidColor(code.clone()))
+            })
+            .collect::<Result<Vec<Color>, _>>()?;
+
+        if colors.is_empty() {
+            return Err(PaletteError::Empty);
+        }
+
+        Ok(Self { name, colors })
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::builtin().swap_remove(0)
+    }
+}
+
+/// The surroundings a diagram is drawn in, which decide how the colours of generators are shaded
+/// and what they are drawn on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Theme {
+    /// Dark lines on a light, possibly transparent, background.
+    #[default]
+    Light,
+    /// Light lines on a dark background, for dark interfaces and slides.
+    Dark,
+    /// Shades of grey on white paper, for printing in black and white.
+    Print,
+}
+
+#[derive(Debug, Error)]
+#[error("unknown theme `{0}`, expected one of light, dark or print")]
+pub struct ThemeError(String);
+
+impl Theme {
+    pub const ALL: [Self; 3] = [Self::Light, Self::Dark, Self::Print];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+            Self::Print => "Print",
+        }
+    }
+
+    /// The colour filling the background, or `None` if the background may be left transparent
+    /// since it is assumed to be white.
+    pub fn background(self) -> Option<Color> {
+        match self {
+            Self::Light | Self::Print => None,
+            Self::Dark => Some(Color(Srgb::new(0x1e, 0x1f, 0x22))),
+        }
+    }
+
+    /// The colour diagrams are seen on, whether or not the background is filled.
+    pub fn paper(self) -> Color {
+        self.background()
+            .unwrap_or_else(|| Color(Srgb::new(0xff, 0xff, 0xff)))
+    }
+
+    /// The colour of text and other decorations drawn on the background.
+    pub fn foreground(self) -> Color {
+        match self {
+            Self::Light | Self::Print => Color::default(),
+            Self::Dark => Color(Srgb::new(0xee, 0xee, 0xec)),
+        }
+    }
+
+    /// The colour a generator is drawn in, given the selector `c` and orientation described in
+    /// [`Color::lighten`]. Shades move away from the foreground towards the background.
+    pub fn shade(self, color: &Color, c: usize, orientation: Orientation) -> Color {
+        match self {
+            Self::Light => color.lighten(c, orientation),
+            Self::Dark => color.darken(c, orientation),
+            Self::Print => color.lighten(c, orientation).greyscale(),
+        }
+    }
+
+    /// Whether a colour is too close to the background to stand out on its own, so that it
+    /// needs an outline or should be replaced by the foreground colour.
+    pub fn is_faint(self, color: &Color) -> bool {
+        palette::RelativeContrast::get_contrast_ratio(
+            self.paper().0.into_format::<f32>(),
+            color.0.into_format::<f32>(),
+        ) < 1.5
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|theme| theme.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ThemeError(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub enum GeneratorRepresentation {
@@ -1,12 +1,15 @@
 use std::fmt::Write;
 
-use homotopy_core::{Generator, Orientation};
+use homotopy_core::{Diagram0, Generator, Orientation};
 
-use crate::style::{GeneratorRepresentation, GeneratorStyle, SignatureStyleData};
+use crate::style::{Color, GeneratorRepresentation, GeneratorStyle, SignatureStyleData, Theme};
 
+mod export;
 pub mod render;
 pub mod shape;
 
+pub use export::{export, export_strip, SvgOptions};
+
 macro_rules! write_styles_for {
     (
         @c_r
@@ -14,32 +17,34 @@ macro_rules! write_styles_for {
         $orientation:expr,
         $generator:expr,
         $color:expr,
+        $theme:expr,
         $stylesheet:expr
      ) => {{
         writeln!(
             $stylesheet,
             ".{name} {{ fill: {color}; stroke: {color}; }}",
             name = generator_class($generator, $c, $orientation),
-            color = $color.lighten($c, $orientation).hex(),
+            color = $theme.shade(&$color, $c, $orientation).hex(),
         )
         .unwrap()
     }};
     (
         $generator:expr,
         $styles:expr,
+        $theme:expr,
         $stylesheet:expr
     ) => {{
         let color = $styles.generator_style($generator).unwrap().color();
 
         for c in 0..3 {
-            write_styles_for!(@c_r c, Orientation::Positive, $generator, color, $stylesheet);
-            write_styles_for!(@c_r c, Orientation::Zero, $generator, color, $stylesheet);
-            write_styles_for!(@c_r c, Orientation::Negative, $generator, color, $stylesheet);
+            write_styles_for!(@c_r c, Orientation::Positive, $generator, color, $theme, $stylesheet);
+            write_styles_for!(@c_r c, Orientation::Zero, $generator, color, $theme, $stylesheet);
+            write_styles_for!(@c_r c, Orientation::Negative, $generator, color, $theme, $stylesheet);
         }
     }};
 }
 
-pub fn stylesheet(styles: &impl SignatureStyleData) -> String {
+pub fn stylesheet(styles: &impl SignatureStyleData, theme: Theme) -> String {
     let mut stylesheet = String::new();
 
     writeln!(
@@ -49,7 +54,7 @@ pub fn stylesheet(styles: &impl SignatureStyleData) -> String {
     .unwrap();
 
     for generator in styles.generators() {
-        write_styles_for!(generator, styles, stylesheet);
+        write_styles_for!(generator, styles, theme, stylesheet);
     }
 
     stylesheet
@@ -75,6 +80,37 @@ pub fn generator_class_from_diagram_dim(
     )
 }
 
+/// The colour the stylesheet assigns to a generator drawn in the given representation.
+pub fn generator_color(
+    styles: &impl SignatureStyleData,
+    diagram: Diagram0,
+    diagram_dimension: usize,
+    representation: GeneratorRepresentation,
+    theme: Theme,
+) -> Color {
+    let d = diagram_dimension;
+    let n = diagram.generator.dimension;
+    let k = representation as usize;
+
+    let c = d.saturating_sub(n + k) % 3;
+
+    let color = styles
+        .generator_style(diagram.generator)
+        .map(GeneratorStyle::color)
+        .unwrap_or_default();
+    theme.shade(&color, c, diagram.orientation)
+}
+
+/// The `points` attribute of an SVG polygon drawing a vertex shape around the given centre, in
+/// coordinates with the y-axis pointing down.
+pub fn polygon_points(center: shape::Point, radius: f32, corners: &[[f32; 2]]) -> String {
+    corners
+        .iter()
+        .map(|[x, y]| format!("{},{}", center.x + x * radius, center.y - y * radius))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[inline]
 fn generator_class(generator: Generator, c: usize, orientation: Orientation) -> String {
     format!(
@@ -0,0 +1,323 @@
+use std::fmt::Write;
+
+use homotopy_core::{common::DimensionError, Diagram, DiagramN};
+
+use super::{generator_class_from_diagram_dim, polygon_points, shape::path_to_svg, stylesheet};
+use crate::{
+    figure::{Figure, FigureElement, FigureOptions, SURFACE_OUTLINE},
+    label::plain_text,
+    strip::{Strip, StripOptions},
+    style::{Color, GeneratorRepresentation, SignatureStyleData, VertexShape},
+};
+
+/// Options for exporting a diagram as a standalone SVG file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    pub figure: FigureOptions,
+    /// Colour elements through classes defined in an embedded copy of the signature stylesheet,
+    /// rather than by presentation attributes on every element.
+    pub stylesheet: bool,
+    /// Write the labels of generators as plain text, as SVG viewers cannot typeset LaTeX.
+    pub show_labels: bool,
+    /// Animate 3-diagrams by scrubbing through their slices, spending this many seconds on each.
+    pub slice_duration: Option<f32>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            figure: Default::default(),
+            stylesheet: true,
+            show_labels: false,
+            slice_duration: None,
+        }
+    }
+}
+
+/// Export the 2-dimensional graphic of a diagram as a standalone SVG file.
+///
+/// Unlike the SVG view of the web app, the output does not depend on any external stylesheet.
+/// With a slice duration, a 3-diagram is exported as an animation showing one slice at a time,
+/// and otherwise as its 2-dimensional projection.
+pub fn export(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: &SvgOptions,
+) -> Result<String, DimensionError> {
+    let figures = match options.slice_duration {
+        Some(_) if diagram.dimension() == 3 => <&DiagramN>::try_from(diagram)?
+            .slices()
+            .map(|slice| Figure::new(&slice, signature_styles, options.figure.clone()))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => vec![Figure::new(
+            diagram,
+            signature_styles,
+            options.figure.clone(),
+        )?],
+    };
+
+    let width = figures.iter().map(|f| f.width).fold(0., f32::max);
+    let height = figures.iter().map(|f| f.height).fold(0., f32::max);
+
+    let mut svg = header(width, height, signature_styles, options);
+
+    if figures.len() == 1 {
+        write_figure(&mut svg, &figures[0], 0, options);
+    } else {
+        let duration = options.slice_duration.unwrap_or_default() * figures.len() as f32;
+        for (i, figure) in figures.iter().enumerate() {
+            writeln!(svg, r#"<g visibility="hidden">"#).unwrap();
+            write_visibility_animation(&mut svg, i, figures.len(), duration);
+            write_figure(&mut svg, figure, i, options);
+            svg.push_str("</g>\n");
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Export the slices of a diagram side by side as a standalone SVG file, with arrows between
+/// consecutive slices.
+///
+/// Slices which are not sliced any further are drawn as their 2-dimensional projection. The
+/// slice duration of the SVG options is ignored.
+pub fn export_strip(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    strip_options: &StripOptions,
+    options: &SvgOptions,
+) -> Result<String, DimensionError> {
+    let strip = Strip::new(diagram, strip_options)
+        .try_map(&|slice| Figure::new(slice, signature_styles, options.figure.clone()))?;
+    let arrangement = strip.arrange(
+        &|figure| [figure.width, figure.height],
+        strip_options.spacing * options.figure.scale,
+    );
+    let [width, height] = arrangement.size;
+
+    let mut svg = header(width, height, signature_styles, options);
+    writeln!(
+        svg,
+        r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{}" /></marker></defs>"#,
+        options.figure.theme.foreground().hex()
+    )
+    .unwrap();
+
+    for (i, ([x, y], figure)) in arrangement.pictures.into_iter().enumerate() {
+        writeln!(svg, r#"<g transform="translate({x} {y})">"#).unwrap();
+        write_figure(&mut svg, figure, i, options);
+        svg.push_str("</g>\n");
+    }
+
+    for ([x1, y1], [x2, y2]) in arrangement.arrows {
+        writeln!(
+            svg,
+            r#"<path d="M {x1} {y1} L {x2} {y2}" stroke="{foreground}" stroke-width="{}" marker-end="url(#arrow)" />"#,
+            0.5 * options.figure.wire_thickness,
+            foreground = options.figure.theme.foreground().hex(),
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Open an SVG document with the stylesheet and background.
+fn header(
+    width: f32,
+    height: f32,
+    signature_styles: &impl SignatureStyleData,
+    options: &SvgOptions,
+) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+
+    if options.stylesheet {
+        writeln!(
+            svg,
+            "<style>\n{}</style>",
+            stylesheet(signature_styles, options.figure.theme)
+        )
+        .unwrap();
+    }
+
+    if let Some(background) = &options.figure.background() {
+        writeln!(
+            svg,
+            r#"<rect width="100%" height="100%" fill="{}" />"#,
+            background.hex()
+        )
+        .unwrap();
+    }
+
+    svg
+}
+
+/// Make the `i`th of `n` groups visible during its share of the animation, in a loop.
+fn write_visibility_animation(svg: &mut String, i: usize, n: usize, duration: f32) {
+    let start = i as f32 / n as f32;
+    let end = (i + 1) as f32 / n as f32;
+    let (values, key_times) = if i == 0 {
+        ("visible;hidden", format!("0;{end}"))
+    } else {
+        ("hidden;visible;hidden", format!("0;{start};{end}"))
+    };
+    writeln!(
+        svg,
+        r#"<animate attributeName="visibility" values="{values}" keyTimes="{key_times}" dur="{duration}s" calcMode="discrete" repeatCount="indefinite" />"#
+    )
+    .unwrap();
+}
+
+fn write_figure(svg: &mut String, figure: &Figure, index: usize, options: &SvgOptions) {
+    let wire_thickness = figure.options.wire_thickness;
+
+    for (i, element) in figure.elements.iter().enumerate() {
+        let (diagram, color, representation) = match element {
+            FigureElement::Surface(d, color, _, _) => (d, color, GeneratorRepresentation::Surface),
+            FigureElement::Wire(d, color, _, _, _) => (d, color, GeneratorRepresentation::Wire),
+            FigureElement::Point(d, color, _, _) => (d, color, GeneratorRepresentation::Point),
+        };
+        let style = if options.stylesheet {
+            format!(
+                r#"class="{}""#,
+                generator_class_from_diagram_dim(
+                    diagram.generator,
+                    diagram.orientation,
+                    figure.dimension,
+                    representation,
+                )
+            )
+        } else {
+            presentation_attributes(color, representation)
+        };
+
+        match element {
+            FigureElement::Surface(_, _, surface, path) => {
+                // Inline styles take precedence over the stylesheet.
+                let opacity = if surface.is_opaque() {
+                    String::new()
+                } else {
+                    format!(
+                        r#" style="stroke: none; fill-opacity: {}""#,
+                        surface.alpha()
+                    )
+                };
+                writeln!(
+                    svg,
+                    r#"<path d="{}" {style} stroke-width="{SURFACE_OUTLINE}"{opacity} />"#,
+                    path_to_svg(path)
+                )
+                .unwrap();
+            }
+            FigureElement::Wire(_, _, wire, path, mask) => {
+                let mask_attribute = if mask.is_empty() {
+                    String::new()
+                } else {
+                    let id = format!("mask-{index}-{i}");
+                    writeln!(
+                        svg,
+                        r#"<defs><mask maskUnits="userSpaceOnUse" id="{id}"><rect width="100%" height="100%" fill="white" />"#
+                    )
+                    .unwrap();
+                    for mask_path in mask {
+                        writeln!(
+                            svg,
+                            r#"<path d="{}" stroke-width="{}" fill="none" stroke="black" stroke-linecap="round" />"#,
+                            path_to_svg(mask_path),
+                            wire_thickness * 2.,
+                        )
+                        .unwrap();
+                    }
+                    svg.push_str("</mask></defs>\n");
+                    format!(r#" mask="url(#{id})""#)
+                };
+                let width = wire_thickness * wire.thickness_scale();
+                let dashes = wire.dash_array(width);
+                let dash_attribute = if dashes.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        r#" stroke-dasharray="{}" stroke-linecap="round""#,
+                        dashes
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    )
+                };
+                writeln!(
+                    svg,
+                    r#"<path d="{}" {style} stroke-width="{width}"{dash_attribute}{mask_attribute} />"#,
+                    path_to_svg(path)
+                )
+                .unwrap();
+            }
+            FigureElement::Point(_, color, shape, point) => {
+                let radius = figure.options.point_radius;
+                // The stylesheet removes the stroke of points, so hollow ones are outlined by
+                // attributes alone.
+                let style = if shape.is_hollow() {
+                    format!(
+                        r#"fill="{}" stroke="{}" stroke-width="{}""#,
+                        figure.options.theme.paper().hex(),
+                        color.hex(),
+                        VertexShape::OUTLINE * radius
+                    )
+                } else {
+                    style
+                };
+                match shape.polygon() {
+                    None => writeln!(
+                        svg,
+                        r#"<circle r="{radius}" cx="{}" cy="{}" {style} />"#,
+                        point.x, point.y
+                    ),
+                    Some(corners) => writeln!(
+                        svg,
+                        r#"<polygon points="{}" {style} />"#,
+                        polygon_points(*point, radius, &corners)
+                    ),
+                }
+                .unwrap();
+            }
+        }
+    }
+
+    if options.show_labels {
+        let font_size = 0.4 * figure.options.scale;
+        for label in &figure.labels {
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="serif" font-size="{font_size}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                label.position.x,
+                label.position.y,
+                figure.options.theme.foreground().hex(),
+                escape(&plain_text(&label.text))
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Attributes colouring an element like the rules of the stylesheet would.
+fn presentation_attributes(color: &Color, representation: GeneratorRepresentation) -> String {
+    let color = color.hex();
+    match representation {
+        GeneratorRepresentation::Surface => format!(r#"fill="{color}" stroke="{color}""#),
+        GeneratorRepresentation::Wire => format!(r#"fill="none" stroke="{color}""#),
+        GeneratorRepresentation::Point => format!(r#"fill="{color}" stroke="none""#),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
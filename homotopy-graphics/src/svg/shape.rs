@@ -106,7 +106,7 @@ impl Stroke {
     }
 }
 
-fn distance_to_line_segment(point: Point, from: Point, to: Point) -> f32 {
+pub(crate) fn distance_to_line_segment(point: Point, from: Point, to: Point) -> f32 {
     let vec = to - from;
     let square_length = vec.square_length();
 
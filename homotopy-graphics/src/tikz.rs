@@ -1,4 +1,4 @@
-use std::fmt::Write;
+use std::{cell::RefCell, fmt::Write};
 
 use euclid::default::Point2D;
 use homotopy_common::hash::{FastHashMap, FastHashSet};
@@ -8,14 +8,22 @@ use homotopy_core::{
     diagram::Diagram0,
     layout::Layout,
     projection::{Depths, Projection},
-    Diagram, Generator, Orientation,
+    Boundary, Diagram, DiagramN, Generator, Orientation,
 };
 use itertools::Itertools;
 use lyon_path::{Event, Path};
+use ultraviolet::{Vec2, Vec3};
 
 use crate::{
+    label::{place_labels, LABEL_DISTANCE},
+    mesh::{Mesh, MeshOptions},
     path_util::simplify_graphic,
-    style::{Color, GeneratorRepresentation, GeneratorStyle, SignatureStyleData, VertexShape},
+    raster::Camera,
+    strip::{Arrangement, Strip, StripOptions},
+    style::{
+        Color, GeneratorRepresentation, GeneratorStyle, SignatureStyleData, Theme, VertexShape,
+        WireStyle,
+    },
     svg::render::GraphicElement,
 };
 
@@ -24,9 +32,23 @@ pub fn stylesheet(
     styles: &impl SignatureStyleData,
     dimension: usize,
     diagrams: &FastHashSet<(Diagram0, GeneratorRepresentation)>,
+    theme: Theme,
 ) -> String {
     let mut stylesheet = String::new();
 
+    writeln!(
+        stylesheet,
+        "\\definecolor{{diagram-background}}{}",
+        rgb(theme.paper())
+    )
+    .unwrap();
+    writeln!(
+        stylesheet,
+        "\\definecolor{{diagram-foreground}}{}",
+        rgb(theme.foreground())
+    )
+    .unwrap();
+
     for diagram in diagrams {
         let generator = diagram.0.generator;
         let orientation = diagram.0.orientation;
@@ -37,7 +59,7 @@ pub fn stylesheet(
             stylesheet,
             "\\definecolor{{{generator}}}{color}",
             generator = name(generator, selector, orientation),
-            color = rgb(color.lighten(selector, orientation).clone()),
+            color = rgb(theme.shade(&color, selector, orientation)),
         )
         .unwrap();
     }
@@ -89,14 +111,272 @@ fn rgb(color: Color) -> String {
     format!("{{RGB}}{{{r}, {g}, {b}}}")
 }
 
+/// Open a picture, filling its background if the theme has one. This needs the `backgrounds`
+/// library of TikZ.
+fn begin_picture(theme: Theme) -> String {
+    if theme.background().is_some() {
+        "\\begin{tikzpicture}[show background rectangle, background rectangle/.style={fill=diagram-background}]\n".to_owned()
+    } else {
+        "\\begin{tikzpicture}\n".to_owned()
+    }
+}
+
 pub fn render(
     diagram: &Diagram,
     signature_styles: &impl SignatureStyleData,
     leftright_mode: bool,
     show_braids: bool,
+    show_labels: bool,
+    theme: Theme,
+) -> Result<String, DimensionError> {
+    let mut diagrams = FastHashSet::default();
+    let (picture, _) = render_picture(
+        diagram,
+        signature_styles,
+        leftright_mode,
+        show_braids,
+        show_labels,
+        &mut diagrams,
+    )?;
+
+    let mut tikz = String::new();
+    tikz.push_str(&begin_picture(theme));
+    tikz.push_str(&stylesheet(
+        signature_styles,
+        diagram.dimension(),
+        &diagrams,
+        theme,
+    ));
+    tikz.push_str(&picture);
+    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+
+    Ok(tikz)
+}
+
+/// Render the slices of a 3-diagram side by side in a single picture, as a grid read from left to
+/// right and top to bottom. Without a given number of columns, the grid is roughly square.
+pub fn render_slices(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    leftright_mode: bool,
+    show_braids: bool,
+    show_labels: bool,
+    columns: Option<usize>,
+    theme: Theme,
+) -> Result<String, DimensionError> {
+    let diagram = <&DiagramN>::try_from(diagram)?;
+    let diagrams = RefCell::new(FastHashSet::default());
+    let slices: Vec<_> = diagram.slices().map(Strip::Picture).collect();
+    let columns = columns
+        .unwrap_or_else(|| (slices.len() as f32).sqrt().ceil() as usize)
+        .max(1);
+    let strip = Strip::Slices(slices).wrap(columns).try_map(&|slice| {
+        render_picture(
+            slice,
+            signature_styles,
+            leftright_mode,
+            show_braids,
+            show_labels,
+            &mut diagrams.borrow_mut(),
+        )
+    })?;
+    let arrangement = strip.arrange_rows(&|(_, size)| *size, SLICE_SPACING);
+
+    let mut tikz = String::new();
+    tikz.push_str(&begin_picture(theme));
+    tikz.push_str(&stylesheet(
+        signature_styles,
+        diagram.dimension() - 1,
+        &diagrams.into_inner(),
+        theme,
+    ));
+    write_arrangement(&mut tikz, &arrangement, leftright_mode);
+    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+
+    Ok(tikz)
+}
+
+/// Deepest subdivision of projected pictures, each triangle of which becomes a path for TeX.
+pub const MAX_PROJECTED_SUBDIVISION: u8 = 2;
+
+/// Render a 3-diagram as seen from a camera, as flat shaded triangles drawn from back to front.
+///
+/// Surfaces, wires and points are all meshed as in the 3D view, with the subdivision depth capped
+/// at [`MAX_PROJECTED_SUBDIVISION`] for the picture to remain small enough for TeX. The picture is
+/// `size` centimetres across.
+pub fn render_projected(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: MeshOptions,
+    camera: &Camera,
+    size: f32,
 ) -> Result<String, DimensionError> {
+    let options = MeshOptions {
+        subdivision_depth: options.subdivision_depth.min(MAX_PROJECTED_SUBDIVISION),
+        ..options
+    };
+    let mesh = Mesh::new(diagram, signature_styles, options)?;
+    let view = camera.view_transform();
+    let projection = camera.perspective_transform(1.);
+
+    let mut triangles = Vec::new();
+    for group in &mesh.groups {
+        let albedo = Vec3::from(group.color.clone().into_linear_f32_components());
+        let positions: Vec<Vec3> = group
+            .positions
+            .iter()
+            .map(|p| view.transform_point3(*p))
+            .collect();
+
+        for triangle in &group.triangles {
+            let vertices = triangle.map(|v| positions[v as usize]);
+            // Triangles reaching behind the camera are dropped rather than clipped.
+            if vertices.iter().any(|p| -p.z <= Camera::NEAR) {
+                continue;
+            }
+
+            let [first, second, third] = vertices;
+            let normal = (second - first).cross(third - first);
+            if normal.mag() < f32::EPSILON {
+                continue;
+            }
+            let centroid = (first + second + third) / 3.;
+
+            // A headlight, so triangles are brighter the more directly they face the camera.
+            let facing = normal.normalized().dot(centroid.normalized()).abs();
+            let color = (albedo * (0.5 + 0.5 * facing))
+                .map(|x| (x.powf(1. / 2.2).clamp(0., 1.) * 255.).round());
+
+            let corners = vertices.map(|p| {
+                let clip = projection * p.into_homogeneous_point();
+                Vec2::new(clip.x, clip.y) / clip.w * 0.5 * size
+            });
+
+            triangles.push((centroid.z, corners, color));
+        }
+    }
+
+    // The furthest triangles have the most negative depth and are drawn first.
+    triangles.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut tikz = String::new();
+    tikz.push_str(&begin_picture(options.theme));
+    if let Some(background) = options.theme.background() {
+        writeln!(
+            tikz,
+            "\\definecolor{{diagram-background}}{}",
+            rgb(background)
+        )
+        .unwrap();
+    }
+    for (_, corners, color) in triangles {
+        let path = corners
+            .map(|p| {
+                let x = (p.x * 1000.).round() / 1000.;
+                let y = (p.y * 1000.).round() / 1000.;
+                format!("({x},{y})")
+            })
+            .join(" -- ");
+        writeln!(
+            tikz,
+            "\\filldraw[line width=0.1pt, color={{rgb,255:red,{};green,{};blue,{}}}] {path} -- cycle;",
+            color.x, color.y, color.z
+        )
+        .unwrap();
+    }
+    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+
+    Ok(tikz)
+}
+
+/// Render the slices of a diagram side by side in a single picture, with arrows between
+/// consecutive slices. Slices which are not sliced any further are drawn as their 2-dimensional
+/// projection.
+pub fn render_strip(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: &StripOptions,
+    leftright_mode: bool,
+    show_braids: bool,
+    show_labels: bool,
+    theme: Theme,
+) -> Result<String, DimensionError> {
+    let diagrams = RefCell::new(FastHashSet::default());
+    let strip = Strip::new(diagram, options).try_map(&|slice| {
+        render_picture(
+            slice,
+            signature_styles,
+            leftright_mode,
+            show_braids,
+            show_labels,
+            &mut diagrams.borrow_mut(),
+        )
+    })?;
+    let arrangement = strip.arrange(&|(_, size)| *size, options.spacing);
+
+    // All slices are sliced the same number of times, so they share their dimension.
+    let dimension = if diagram.dimension() > 2 {
+        diagram.dimension().saturating_sub(options.depth).max(2)
+    } else {
+        diagram.dimension()
+    };
+
+    let mut tikz = String::new();
+    tikz.push_str(&begin_picture(theme));
+    tikz.push_str(&stylesheet(
+        signature_styles,
+        dimension,
+        &diagrams.into_inner(),
+        theme,
+    ));
+    write_arrangement(&mut tikz, &arrangement, leftright_mode);
+    for ([x1, y1], [x2, y2]) in arrangement.arrows {
+        writeln!(
+            tikz,
+            "\\draw[->, thick, diagram-foreground] ({x1},{}) -- ({x2},{});",
+            -y1, -y2
+        )
+        .unwrap();
+    }
+    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+
+    Ok(tikz)
+}
+
+/// Write every picture of an arrangement in a scope of its own, which also keeps the masking
+/// macros of each picture local.
+fn write_arrangement(
+    tikz: &mut String,
+    arrangement: &Arrangement<'_, (String, [f32; 2])>,
+    leftright_mode: bool,
+) {
+    for (i, ([x, y], (picture, size))) in arrangement.pictures.iter().enumerate() {
+        // Arrangements grow downwards, while pictures are drawn upwards from their bottom edge,
+        // except in left-right mode where they hang down from their top edge.
+        let y = if leftright_mode { -y } else { -y - size[1] };
+        writeln!(tikz, "% Slice {i}").unwrap();
+        writeln!(tikz, "\\begin{{scope}}[shift={{({x},{y})}}]").unwrap();
+        tikz.push_str(picture);
+        writeln!(tikz, "\\end{{scope}}").unwrap();
+    }
+}
+
+// Space between the slices of a grid.
+const SLICE_SPACING: f32 = 1.0;
+
+/// Render the contents of a picture of a diagram, recording the colours it uses. Also returns
+/// the size of the picture in the horizontal and vertical directions.
+fn render_picture(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    leftright_mode: bool,
+    show_braids: bool,
+    show_labels: bool,
+    diagrams: &mut FastHashSet<(Diagram0, GeneratorRepresentation)>,
+) -> Result<(String, [f32; 2]), DimensionError> {
     let dimension = diagram.dimension();
     let layout = Layout::<2>::new(diagram)?;
+    let [width, height] = layout[&[Boundary::Target.into(); 2]];
     let complex = make_complex(diagram);
     let depths = Depths::<2>::new(diagram)?;
     let projection = Projection::<2>::new(diagram, &layout, &depths)?;
@@ -106,11 +386,15 @@ pub fn render(
         &projection,
         &depths,
     ));
+    let labels = if show_labels {
+        place_labels(&graphic, signature_styles, LABEL_DISTANCE)
+    } else {
+        Vec::new()
+    };
 
     let mut surfaces = Vec::default();
     let mut wires: FastHashMap<usize, Vec<(Diagram0, Path)>> = FastHashMap::default();
     let mut points = Vec::default();
-    let mut diagrams: FastHashSet<_> = Default::default();
     for element in graphic {
         match element {
             GraphicElement::Surface(g, path) => {
@@ -129,12 +413,10 @@ pub fn render(
     }
 
     let mut tikz = String::new();
-    writeln!(tikz, "\\begin{{tikzpicture}}").unwrap();
-    tikz.push_str(&stylesheet(signature_styles, dimension, &diagrams));
-
     tikz.push_str(&render_inner(
         &surfaces,
         wires,
+        signature_styles,
         leftright_mode,
         show_braids,
         dimension,
@@ -144,27 +426,36 @@ pub fn render(
     for (d, point) in points {
         let vertex = render_vertex(
             signature_styles.generator_style(d.generator).unwrap(),
+            &name_from_diagram_dim(d, dimension, GeneratorRepresentation::Point),
             point,
             leftright_mode,
         );
+        writeln!(tikz, "{vertex}").unwrap();
+    }
+
+    for label in labels {
         writeln!(
             tikz,
-            "\\fill[{}] {}",
-            name_from_diagram_dim(d, dimension, GeneratorRepresentation::Point),
-            vertex
+            "\\node[text=diagram-foreground] at {} {{${}$}};",
+            render_point(label.position, leftright_mode),
+            label.text
         )
         .unwrap();
     }
 
-    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+    let size = if leftright_mode {
+        [height, width]
+    } else {
+        [width, height]
+    };
 
-    Ok(tikz)
+    Ok((tikz, size))
 }
 
 // This contains all the "magic" commands we need to inject
 // in the case we want to show braidings.
-const MAGIC_MACRO: &str = "\n\\newcommand{\\wire}[2]{
-  \\ifdefined\\recolor\\draw[color=\\recolor, line width=10pt]\\else\\draw[color=#1, line width=5pt]\\fi #2;
+const MAGIC_MACRO: &str = "\n\\newcommand{\\wire}[3]{
+  \\ifdefined\\recolor\\draw[color=\\recolor, line width=10pt]\\else\\draw[color=#1, line width=5pt#3]\\fi #2;
 }
 \\newcommand{\\clipped}[3]{
 \\begin{scope}
@@ -177,6 +468,7 @@ const MAGIC_MACRO: &str = "\n\\newcommand{\\wire}[2]{
 fn render_inner(
     surfaces: &[(Diagram0, Path)],
     wires: FastHashMap<usize, Vec<(Diagram0, Path)>>,
+    signature_styles: &impl SignatureStyleData,
     leftright_mode: bool,
     show_braids: bool,
     diagram_dimension: usize,
@@ -199,9 +491,18 @@ fn render_inner(
 
     tikz.push_str("% Background surfaces\n");
     for (g, path) in surfaces.iter() {
+        let style = signature_styles
+            .generator_style(g.generator)
+            .map(GeneratorStyle::surface_style)
+            .unwrap_or_default();
+        let opacity = if style.is_opaque() {
+            String::new()
+        } else {
+            format!(", opacity={}", style.alpha())
+        };
         writeln!(
             tikz,
-            "\\fill[{color}] {path};",
+            "\\fill[{color}{opacity}] {path};",
             color = name_from_diagram_dim(*g, diagram_dimension, GeneratorRepresentation::Surface),
             path = &render_path(path, leftright_mode)
         )
@@ -246,12 +547,18 @@ fn render_inner(
             tikz.push_str("\\layer{\n");
         }
         for (g, path) in &layer {
+            let options = wire_options(
+                signature_styles
+                    .generator_style(g.generator)
+                    .map(GeneratorStyle::wire_style)
+                    .unwrap_or_default(),
+            );
             if needs_masking {
                 // We pass the geometry of the wire directly to the current layer.
                 // This is to avoid naming annoyances.
                 writeln!(
                     tikz,
-                    "\\wire{{{color}}}{{{path}}};",
+                    "\\wire{{{color}}}{{{path}}}{{{options}}};",
                     color =
                         name_from_diagram_dim(*g, diagram_dimension, GeneratorRepresentation::Wire),
                     path = &render_path(path, leftright_mode)
@@ -260,7 +567,7 @@ fn render_inner(
             } else {
                 writeln!(
                     tikz,
-                    "\\draw[color={color}, line width=5pt]{path};",
+                    "\\draw[color={color}, line width=5pt{options}]{path};",
                     color =
                         name_from_diagram_dim(*g, diagram_dimension, GeneratorRepresentation::Wire),
                     path = &render_path(path, leftright_mode)
@@ -288,40 +595,60 @@ fn render_point(point: Point2D<f32>, leftright_mode: bool) -> String {
     }
 }
 
+/// Extra options for drawing a wire in the given style, each preceded by a comma.
+fn wire_options(style: WireStyle) -> String {
+    const WIRE_WIDTH: f32 = 5.; // pt
+
+    let mut options = String::new();
+    if style.thickness != 100 {
+        write!(
+            options,
+            ", line width={}pt",
+            WIRE_WIDTH * style.thickness_scale()
+        )
+        .unwrap();
+    }
+    let dashes = style.dash_array(WIRE_WIDTH * style.thickness_scale());
+    if !dashes.is_empty() {
+        options.push_str(", line cap=round, dash pattern=");
+        for (i, length) in dashes.iter().enumerate() {
+            let kind = if i % 2 == 0 { "on" } else { "off" };
+            write!(options, "{kind} {length}pt ").unwrap();
+        }
+        options.pop();
+    }
+    options
+}
+
 fn render_vertex(
     generator_style: &impl GeneratorStyle,
+    color: &str,
     point: Point2D<f32>,
     leftright_mode: bool,
 ) -> String {
-    use VertexShape::{Circle, Square};
-
-    const CIRCLE_RADIUS: f32 = 0.14; // r = 4pt
-    const SQUARE_SIDELENGTH: f32 = 0.28; // 8pt x 8pt
+    const RADIUS: f32 = 0.14; // r = 4pt
 
     let shape = generator_style.shape();
-    let shape_str = match shape {
-        Circle => "circle",
-        Square => "rectangle",
-    };
-    let (xo, yo) = match shape {
-        Circle => (0.0, 0.0),
-        Square => (-SQUARE_SIDELENGTH / 2., -SQUARE_SIDELENGTH / 2.),
-    };
-    let x1 = ((xo + point.x) * 100.0).round() / 100.0;
-    let y1 = ((yo + point.y) * 100.0).round() / 100.0;
-    let sz = match shape {
-        Circle => vec![CIRCLE_RADIUS],
-        Square => vec![SQUARE_SIDELENGTH + x1, SQUARE_SIDELENGTH + y1],
-    }
-    .iter()
-    .map(|&s| s.to_string())
-    .collect::<Vec<String>>()
-    .join(", ");
-
-    if leftright_mode {
-        format!("({y1},{}) {shape_str} ({sz});", -x1)
+    let command = if shape.is_hollow() {
+        format!(
+            "\\filldraw[fill=diagram-background, draw={color}, line width={}cm]",
+            VertexShape::OUTLINE * RADIUS
+        )
     } else {
-        format!("({x1},{y1}) {shape_str} ({sz});")
+        format!("\\fill[{color}]")
+    };
+    match shape.polygon() {
+        None => format!(
+            "{command} {} circle ({RADIUS});",
+            render_point(point, leftright_mode)
+        ),
+        Some(corners) => {
+            let corners = corners
+                .into_iter()
+                .map(|[x, y]| render_point(point + euclid::vec2(x, y) * RADIUS, leftright_mode))
+                .join(" -- ");
+            format!("{command} {corners} -- cycle;")
+        }
     }
 }
 
@@ -104,6 +104,31 @@ impl History {
         &self.snapshots[self.current]
     }
 
+    /// The proofs along the history up to the current one, oldest first.
+    pub fn proofs(&self) -> Vec<&Proof> {
+        let mut proofs: Vec<_> = self
+            .snapshots
+            .ancestors_of(self.current)
+            .map(|n| &self.snapshots[n])
+            .collect();
+        proofs.reverse();
+        proofs
+    }
+
+    /// How many steps the history goes back from the current proof.
+    pub fn depth(&self) -> usize {
+        self.snapshots
+            .ancestors_of(self.current)
+            .count()
+            .saturating_sub(1)
+    }
+
+    /// The proof the given number of steps back along the history, if it goes back that far.
+    pub fn earlier(&self, steps: usize) -> Option<&Proof> {
+        let node = self.snapshots.ancestors_of(self.current).nth(steps)?;
+        Some(&self.snapshots[node])
+    }
+
     pub fn add(&mut self, action: super::proof::Action, proof: Proof) {
         if let Some(child) = self.snapshots.push_onto(
             self.current,
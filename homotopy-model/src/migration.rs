@@ -2,7 +2,9 @@ use homotopy_core::migration::OldProof;
 use homotopy_graphics::style::{Color, VertexShape};
 use serde::Deserialize;
 
-use crate::proof::{generators::GeneratorInfo, Metadata, Signature, SignatureItem, Workspace};
+use crate::proof::{
+    generators::GeneratorInfo, shelf::Shelf, Metadata, Signature, SignatureItem, Tabs, Workspace,
+};
 
 #[derive(Deserialize)]
 struct Export {
@@ -21,7 +23,7 @@ struct OldMetadata {
     user_abstract: String,
 }
 
-pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metadata)> {
+pub fn deserialize(data: &[u8]) -> Option<((Signature, Tabs, Shelf), Metadata)> {
     // Deserialize
     let export: Export = match serde_json::from_slice(data) {
         Err(error) => {
@@ -45,11 +47,11 @@ pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metad
         abstr: (!export.metadata.user_abstract.is_empty()).then_some(export.metadata.user_abstract),
     };
 
-    let sw = load(proof)?;
-    Some((sw, metadata))
+    let (signature, tabs) = load(proof)?;
+    Some(((signature, tabs, Default::default()), metadata))
 }
 
-fn load(proof: OldProof) -> Option<(Signature, Option<Workspace>)> {
+fn load(proof: OldProof) -> Option<(Signature, Tabs)> {
     let mut signature: Signature = Default::default();
 
     for v in proof.generator_info {
@@ -62,12 +64,18 @@ fn load(proof: OldProof) -> Option<(Signature, Option<Workspace>)> {
             single_preview: true,
             color,
             shape: VertexShape::default(),
+            wire: Default::default(),
+            surface: Default::default(),
             diagram: v.diagram.clone(),
+            label: None,
+            description: None,
+            citations: Vec::new(),
+            tags: Vec::new(),
         };
         signature.insert_item(SignatureItem::Item(info));
     }
 
     let workspace = proof.workspace.map(|w| Workspace::new(w.diagram));
 
-    Some((signature, workspace))
+    Some((signature, workspace.into()))
 }
@@ -2,7 +2,9 @@ use std::convert::{Into, TryFrom};
 
 use homotopy::Homotopy;
 use homotopy_core::{
-    common::{Boundary, BoundaryPath, Direction, Generator, Height, Mode, SliceIndex},
+    common::{
+        Boundary, BoundaryPath, Direction, Generator, Height, Mode, RegularHeight, SliceIndex,
+    },
     contraction::ContractionError,
     diagram::{AttachmentError, NewDiagramError},
     expansion::ExpansionError,
@@ -14,13 +16,17 @@ use serde::{Deserialize, Serialize};
 pub use signature::*;
 use thiserror::Error;
 
-use self::homotopy::{Contract, Expand};
+use self::{
+    homotopy::{Contract, Expand},
+    shelf::{Shelf, ShelfEdit},
+};
 use crate::{migration, serialize};
 
 mod signature;
 
 pub mod generators;
 pub mod homotopy;
+pub mod shelf;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct View {
@@ -35,11 +41,20 @@ impl View {
     }
 }
 
+/// A slice path and view dimension pinned under a name, to return to later.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Viewpoint {
+    pub name: String,
+    pub path: Vector<SliceIndex>,
+    pub view: View,
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Workspace {
     pub view: View,
     pub diagram: Diagram,
     pub path: Vector<SliceIndex>,
+    pub viewpoints: Vector<Viewpoint>,
 }
 
 impl Workspace {
@@ -50,20 +65,52 @@ impl Workspace {
             view: View { dimension },
             diagram,
             path: Default::default(),
+            viewpoints: Default::default(),
         }
     }
 
     pub fn visible_diagram(&self) -> Diagram {
-        self.path
-            .iter()
-            .fold(self.diagram.clone(), |diagram, index| {
-                DiagramN::try_from(diagram).unwrap().slice(*index).unwrap()
-            })
+        self.ancestor_diagram(self.path.len())
     }
 
     pub fn visible_dimension(&self) -> usize {
         self.diagram.dimension() - self.path.len()
     }
+
+    /// Show the slice at the given path, with the given view dimension lowered as far as the
+    /// slice requires. Returns false, leaving the workspace as it is, if there is no such slice.
+    pub fn move_to(&mut self, path: Vector<SliceIndex>, view: View) -> bool {
+        let depth = path.len();
+        let previous = std::mem::replace(&mut self.path, path);
+        let Some(visible) = self.try_ancestor_diagram(depth) else {
+            self.path = previous;
+            return false;
+        };
+
+        self.view = View {
+            dimension: view
+                .dimension
+                .min(std::cmp::min(visible.dimension() as u8, View::MAX)),
+        };
+
+        true
+    }
+
+    /// The diagram seen after descending into the first `depth` slices of the path.
+    pub fn ancestor_diagram(&self, depth: usize) -> Diagram {
+        self.try_ancestor_diagram(depth).unwrap()
+    }
+
+    /// Like [Self::ancestor_diagram], but `None` if the path does not lead to a slice of the
+    /// diagram.
+    pub fn try_ancestor_diagram(&self, depth: usize) -> Option<Diagram> {
+        self.path
+            .iter()
+            .take(depth)
+            .try_fold(self.diagram.clone(), |diagram, index| {
+                DiagramN::try_from(diagram).ok()?.slice(*index)
+            })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -72,12 +119,89 @@ pub struct SelectedBoundary {
     pub diagram: Diagram,
 }
 
+/// A named workspace together with its selected boundary.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Tab {
+    pub name: String,
+    pub workspace: Option<Workspace>,
+    pub boundary: Option<SelectedBoundary>,
+}
+
+impl Tab {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            workspace: None,
+            boundary: None,
+        }
+    }
+}
+
+/// The workspace tabs of a proof, all of which share the signature.
+///
+/// Always contains at least one tab.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Tabs {
+    tabs: Vector<Tab>,
+    active: usize,
+}
+
+impl Tabs {
+    /// Create a collection of tabs. Returns `None` if there are no tabs or if the index of the
+    /// active tab is out of bounds.
+    pub fn new(tabs: Vector<Tab>, active: usize) -> Option<Self> {
+        (active < tabs.len()).then_some(Self { tabs, active })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tab> {
+        self.tabs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub(crate) fn default_name(index: usize) -> String {
+        format!("Workspace {}", index + 1)
+    }
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::from(None)
+    }
+}
+
+impl From<Option<Workspace>> for Tabs {
+    fn from(workspace: Option<Workspace>) -> Self {
+        Self {
+            tabs: Vector::unit(Tab {
+                workspace,
+                ..Tab::new(Self::default_name(0))
+            }),
+            active: 0,
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
 pub struct ProofState {
     pub signature: Signature,
     pub workspace: Option<Workspace>,
     pub metadata: Metadata,
     pub boundary: Option<SelectedBoundary>,
+    pub shelf: Shelf,
+    /// The workspace tabs. The workspace and boundary of the active tab are kept in the fields
+    /// above while it is active, so the corresponding slot here is empty.
+    tabs: Tabs,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -120,6 +244,16 @@ pub enum Action {
     IncreaseView(u8),
     DecreaseView(u8),
 
+    /// Pin the current slice path and view of the workspace as a viewpoint with the given name.
+    PinViewpoint(String),
+
+    /// Return to the pinned viewpoint with the given index. If the diagram has changed so that
+    /// its slice path no longer exists, an error will be shown.
+    GoToViewpoint(usize),
+
+    /// Forget the pinned viewpoint with the given index.
+    UnpinViewpoint(usize),
+
     Attach(AttachOption),
 
     Homotopy(Homotopy),
@@ -136,6 +270,10 @@ pub enum Action {
 
     ImportProof(SerializedData),
 
+    /// Merge the signature of another proof into the current one as a new folder with the given
+    /// name, keeping everything else as it is.
+    ImportLibrary(String, SerializedData),
+
     EditSignature(SignatureEdit),
 
     EditMetadata(MetadataEdit),
@@ -144,6 +282,27 @@ pub enum Action {
 
     RecoverBoundary,
 
+    /// Open a new empty workspace tab and switch to it.
+    CreateWorkspace,
+
+    /// Switch to the workspace tab with the given index.
+    SwitchWorkspace(usize),
+
+    /// Close the workspace tab with the given index. The last remaining tab cannot be closed.
+    CloseWorkspace(usize),
+
+    /// Rename the workspace tab with the given index.
+    RenameWorkspace(usize, String),
+
+    /// Copy the currently visible diagram in the workspace to the shelf. Does nothing if the
+    /// workspace is empty.
+    CopyToShelf,
+
+    /// Load a diagram from the shelf into the workspace.
+    PasteFromShelf(usize),
+
+    EditShelf(ShelfEdit),
+
     Nothing,
 }
 
@@ -190,6 +349,16 @@ impl Action {
                         .as_ref()
                         .map_or(false, |ws| ws.view.dimension > 0)
             }
+            Self::PinViewpoint(_) => proof.workspace.is_some(),
+            Self::GoToViewpoint(index) => proof.workspace.as_ref().map_or(false, |ws| {
+                ws.viewpoints.get(*index).map_or(false, |viewpoint| {
+                    viewpoint.path != ws.path || viewpoint.view != ws.view
+                })
+            }),
+            Self::UnpinViewpoint(index) => proof
+                .workspace
+                .as_ref()
+                .map_or(false, |ws| *index < ws.viewpoints.len()),
             Self::Attach(option) => proof.workspace.as_ref().map_or(false, |ws| {
                 option.boundary_path.is_none() || ws.diagram.dimension() > 0
             }),
@@ -225,9 +394,20 @@ impl Action {
                 .workspace
                 .as_ref()
                 .map_or(false, |ws| ws.diagram.dimension() > 0),
-            Self::ImportProof(_) => true,
+            Self::ImportProof(_) | Self::ImportLibrary(_, _) => true,
             Self::EditSignature(_) | Self::EditMetadata(_) => true, /* technically the edits could be trivial but do not worry about that for now */
             Self::FlipBoundary | Self::RecoverBoundary => proof.boundary.is_some(),
+            Self::CreateWorkspace => true,
+            Self::SwitchWorkspace(index) => {
+                *index < proof.tabs.len() && *index != proof.tabs.active
+            }
+            Self::CloseWorkspace(index) => *index < proof.tabs.len() && proof.tabs.len() > 1,
+            Self::RenameWorkspace(index, _) => *index < proof.tabs.len(),
+            Self::CopyToShelf => proof.workspace.is_some(),
+            Self::PasteFromShelf(index) => *index < proof.shelf.len(),
+            Self::EditShelf(ShelfEdit::Rename(index, _) | ShelfEdit::Remove(index)) => {
+                *index < proof.shelf.len()
+            }
             Self::Nothing => false,
         }
     }
@@ -241,6 +421,8 @@ pub enum ProofError {
     AttachmentError(#[from] AttachmentError),
     #[error("selected a generator that is not in the signature")]
     UnknownGeneratorSelected,
+    #[error("selected a diagram that is not on the shelf")]
+    UnknownShelfItemSelected,
     #[error("tried to descend into an invalid diagram slice")]
     InvalidSlice,
     #[error("the diagram cannot be inverted because not all generators are defined as invertible")]
@@ -270,6 +452,9 @@ impl ProofState {
             Action::SwitchSlice(direction) => self.switch_slice(*direction),
             Action::IncreaseView(count) => self.increase_view(*count),
             Action::DecreaseView(count) => self.decrease_view(*count),
+            Action::PinViewpoint(name) => self.pin_viewpoint(name),
+            Action::GoToViewpoint(index) => self.go_to_viewpoint(*index)?,
+            Action::UnpinViewpoint(index) => self.unpin_viewpoint(*index),
             Action::Attach(option) => self.attach(option)?,
             Action::Homotopy(Homotopy::Expand(homotopy)) => self.homotopy_expand(homotopy)?,
             Action::Homotopy(Homotopy::Contract(homotopy)) => self.homotopy_contract(homotopy)?,
@@ -282,7 +467,15 @@ impl ProofState {
             Action::FlipBoundary => self.flip_boundary(),
             Action::RecoverBoundary => self.recover_boundary(),
             Action::ImportProof(data) => self.import_proof(data)?,
+            Action::ImportLibrary(name, data) => self.import_library(name, data)?,
             Action::EditMetadata(edit) => self.edit_metadata(edit),
+            Action::CreateWorkspace => self.create_workspace(),
+            Action::SwitchWorkspace(index) => self.switch_workspace(*index),
+            Action::CloseWorkspace(index) => self.close_workspace(*index),
+            Action::RenameWorkspace(index, name) => self.rename_workspace(*index, name),
+            Action::CopyToShelf => self.copy_to_shelf(),
+            Action::PasteFromShelf(index) => self.paste_from_shelf(*index)?,
+            Action::EditShelf(edit) => self.shelf.update(edit),
             Action::Nothing => false,
         };
         Ok(result)
@@ -297,9 +490,14 @@ impl ProofState {
                 })
             }
             Action::AscendSlice(i) => i > 0,
+            Action::CloseWorkspace(i) => i == self.tabs.active,
             Action::SelectGenerator(_)
+            | Action::PasteFromShelf(_)
+            | Action::CreateWorkspace
+            | Action::SwitchWorkspace(_)
             | Action::ClearWorkspace
             | Action::DescendSlice(_)
+            | Action::GoToViewpoint(_)
             | Action::IncreaseView(_)
             | Action::DecreaseView(_) => true,
             _ => false,
@@ -475,6 +673,58 @@ impl ProofState {
         true
     }
 
+    /// Handler for [Action::PinViewpoint].
+    ///
+    /// Invalid if the workspace is empty.
+    fn pin_viewpoint(&mut self, name: &str) -> bool {
+        let Some(ws) = &mut self.workspace else { return false };
+
+        ws.viewpoints.push_back(Viewpoint {
+            name: name.to_owned(),
+            path: ws.path.clone(),
+            view: ws.view,
+        });
+
+        true
+    }
+
+    /// Handler for [Action::GoToViewpoint].
+    ///
+    /// Invalid if the workspace is empty, there is no viewpoint with the given index, or the
+    /// workspace is already at the viewpoint.
+    ///
+    /// Returns an error if the path of the viewpoint is not a valid slice path of the diagram.
+    fn go_to_viewpoint(&mut self, index: usize) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else { return Ok(false) };
+        let Some(viewpoint) = ws.viewpoints.get(index) else { return Ok(false) };
+
+        if viewpoint.path == ws.path && viewpoint.view == ws.view {
+            return Ok(false);
+        }
+
+        let (path, view) = (viewpoint.path.clone(), viewpoint.view);
+        if !ws.move_to(path, view) {
+            return Err(ProofError::InvalidSlice);
+        }
+
+        Ok(true)
+    }
+
+    /// Handler for [Action::UnpinViewpoint].
+    ///
+    /// Invalid if the workspace is empty or there is no viewpoint with the given index.
+    fn unpin_viewpoint(&mut self, index: usize) -> bool {
+        let Some(ws) = &mut self.workspace else { return false };
+
+        if index >= ws.viewpoints.len() {
+            return false;
+        }
+
+        ws.viewpoints.remove(index);
+
+        true
+    }
+
     /// Handler for [Action::Attach].
     ///
     /// Invalid if the workspace is empty or has dimension 0 (if the boundary path is not null).
@@ -721,7 +971,26 @@ impl ProofState {
 
     /// Handler for [Action::ImportProof].
     fn import_proof(&mut self, data: &SerializedData) -> Result<bool, ProofError> {
-        let ((signature, workspace), metadata) = serialize::deserialize(&data.0)
+        let ((signature, tabs, shelf), metadata) = Self::read_proof(data)?;
+        self.signature = signature;
+        self.shelf = shelf;
+        self.metadata = metadata;
+        self.set_tabs(tabs);
+        Ok(true)
+    }
+
+    /// Handler for [Action::ImportLibrary].
+    fn import_library(&mut self, name: &str, data: &SerializedData) -> Result<bool, ProofError> {
+        let ((signature, _, _), _) = Self::read_proof(data)?;
+        self.signature.merge(&signature, name);
+        Ok(true)
+    }
+
+    /// Deserialize a proof, checking all of its diagrams.
+    fn read_proof(
+        data: &SerializedData,
+    ) -> Result<((Signature, Tabs, Shelf), Metadata), ProofError> {
+        let ((signature, tabs, shelf), metadata) = serialize::deserialize(&data.0)
             .or_else(|| migration::deserialize(&data.0))
             .ok_or(ProofError::Import)?;
         for info in signature.iter() {
@@ -729,48 +998,74 @@ impl ProofState {
                 .check(Mode::Deep)
                 .map_err(|_err| ProofError::Import)?;
         }
-        if let Some(workspace) = workspace.as_ref() {
+        for workspace in tabs.iter().filter_map(|tab| tab.workspace.as_ref()) {
             workspace
                 .diagram
                 .check(Mode::Deep)
                 .map_err(|_err| ProofError::Import)?;
         }
-        self.signature = signature;
-        self.workspace = workspace;
-        self.metadata = metadata;
-        self.boundary = None;
-        Ok(true)
+        for item in shelf.iter() {
+            item.diagram
+                .check(Mode::Deep)
+                .map_err(|_err| ProofError::Import)?;
+        }
+        Ok(((signature, tabs, shelf), metadata))
     }
 
     /// Handler for [Action::EditSignature].
     fn edit_signature(&mut self, edit: &SignatureEdit) -> bool {
         // intercept remove events in order to clean-up workspace and boundaries
         if let SignatureEdit::Remove(node) = edit {
-            // remove from the workspace
-            if let Some(ws) = &self.workspace {
-                if self.signature.has_descendents_in(*node, &ws.diagram) {
-                    self.workspace = None;
+            let signature = &self.signature;
+            let tabs = self
+                .tabs
+                .tabs
+                .iter_mut()
+                .map(|tab| (&mut tab.workspace, &mut tab.boundary));
+            for (workspace, boundary) in
+                std::iter::once((&mut self.workspace, &mut self.boundary)).chain(tabs)
+            {
+                // remove from the workspace
+                if let Some(ws) = workspace {
+                    if signature.has_descendents_in(*node, &ws.diagram) {
+                        *workspace = None;
+                    }
                 }
-            }
-            // remove from the boundary
-            if let Some(selected) = &self.boundary {
-                if self.signature.has_descendents_in(*node, &selected.diagram) {
-                    self.boundary = None;
+                // remove from the boundary
+                if let Some(selected) = boundary {
+                    if signature.has_descendents_in(*node, &selected.diagram) {
+                        *boundary = None;
+                    }
                 }
             }
+            // remove from the shelf
+            self.shelf
+                .retain(|item| !signature.has_descendents_in(*node, &item.diagram));
         }
 
         if let SignatureEdit::Edit(node, SignatureItemEdit::MakeOriented(true)) = edit {
             if let Some(generator) = self.signature.find_generator(*node) {
-                // remove framing from the workspace
-                if let Some(ws) = &mut self.workspace {
-                    ws.diagram = ws.diagram.remove_framing(generator);
+                let tabs = self
+                    .tabs
+                    .tabs
+                    .iter_mut()
+                    .map(|tab| (&mut tab.workspace, &mut tab.boundary));
+                for (workspace, boundary) in
+                    std::iter::once((&mut self.workspace, &mut self.boundary)).chain(tabs)
+                {
+                    // remove framing from the workspace
+                    if let Some(ws) = workspace {
+                        ws.diagram = ws.diagram.remove_framing(generator);
+                    }
+
+                    // remove framing from the boundary
+                    if let Some(selected) = boundary {
+                        selected.diagram = selected.diagram.remove_framing(generator);
+                    }
                 }
 
-                // remove framing from the boundary
-                if let Some(selected) = &mut self.boundary {
-                    selected.diagram = selected.diagram.remove_framing(generator);
-                }
+                // remove framing from the shelf
+                self.shelf.remove_framing(generator);
             } else {
                 return false;
             }
@@ -809,6 +1104,306 @@ impl ProofState {
         self.workspace = Some(Workspace::new(selected.diagram.clone()));
         true
     }
+
+    /// Handler for [Action::CreateWorkspace].
+    fn create_workspace(&mut self) -> bool {
+        let index = self.tabs.len();
+        self.tabs
+            .tabs
+            .push_back(Tab::new(Tabs::default_name(index)));
+        self.switch_workspace(index)
+    }
+
+    /// Handler for [Action::SwitchWorkspace].
+    ///
+    /// Invalid if the index is out of bounds or the tab is already active.
+    fn switch_workspace(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() || index == self.tabs.active {
+            return false;
+        }
+
+        let active = &mut self.tabs.tabs[self.tabs.active];
+        active.workspace = self.workspace.take();
+        active.boundary = self.boundary.take();
+
+        let next = &mut self.tabs.tabs[index];
+        self.workspace = next.workspace.take();
+        self.boundary = next.boundary.take();
+        self.tabs.active = index;
+
+        true
+    }
+
+    /// Handler for [Action::CloseWorkspace].
+    ///
+    /// Invalid if the index is out of bounds or if this is the only tab.
+    fn close_workspace(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() || self.tabs.len() == 1 {
+            return false;
+        }
+
+        if index == self.tabs.active {
+            // Activate a neighbouring tab first, preferring the one to the left.
+            self.switch_workspace(index.checked_sub(1).unwrap_or(1));
+        }
+
+        self.tabs.tabs.remove(index);
+        if index < self.tabs.active {
+            self.tabs.active -= 1;
+        }
+
+        true
+    }
+
+    /// Handler for [Action::RenameWorkspace].
+    ///
+    /// Invalid if the index is out of bounds.
+    fn rename_workspace(&mut self, index: usize, name: &str) -> bool {
+        let Some(tab) = self.tabs.tabs.get_mut(index) else { return false };
+        tab.name = name.to_owned();
+        true
+    }
+
+    /// Handler for [Action::CopyToShelf].
+    ///
+    /// Invalid if the workspace is empty.
+    fn copy_to_shelf(&mut self) -> bool {
+        let Some(ws) = &self.workspace else { return false };
+        self.shelf.push(ws.visible_diagram());
+        true
+    }
+
+    /// Handler for [Action::PasteFromShelf].
+    ///
+    /// Returns an error if the diagram is not on the shelf.
+    fn paste_from_shelf(&mut self, index: usize) -> Result<bool, ProofError> {
+        let item = self
+            .shelf
+            .get(index)
+            .ok_or(ProofError::UnknownShelfItemSelected)?;
+
+        self.workspace = Some(Workspace::new(item.diagram.clone()));
+
+        Ok(true)
+    }
+
+    /// All workspace tabs of the proof, including the active one.
+    pub fn tabs(&self) -> Tabs {
+        let mut tabs = self.tabs.clone();
+        let active = &mut tabs.tabs[tabs.active];
+        active.workspace = self.workspace.clone();
+        active.boundary = self.boundary.clone();
+        tabs
+    }
+
+    /// Replace all workspace tabs of the proof, making the active tab current.
+    pub fn set_tabs(&mut self, mut tabs: Tabs) {
+        let active = &mut tabs.tabs[tabs.active];
+        self.workspace = active.workspace.take();
+        self.boundary = active.boundary.take();
+        self.tabs = tabs;
+    }
+
+    /// The names of the workspace tabs, in order.
+    pub fn tab_names(&self) -> impl Iterator<Item = &str> {
+        self.tabs.iter().map(|tab| tab.name.as_str())
+    }
+
+    /// The index of the active workspace tab.
+    pub fn active_tab(&self) -> usize {
+        self.tabs.active
+    }
+
+    /// Diagrams of the given dimension which could be attached to the workspace: the generators
+    /// of the signature and the inverses of invertible ones, the diagrams on the shelf, and
+    /// bubbles of invertible generators of lower dimension. With `weak_units`, the identities of
+    /// generators of lower dimension are included too.
+    pub fn attach_candidates(&self, dimension: usize, weak_units: bool) -> Vec<AttachCandidate> {
+        use std::cmp::Ordering;
+
+        let mut candidates = Vec::new();
+        let mut push = |generator, diagram, tag: Option<String>| {
+            candidates.push(AttachCandidate {
+                generator,
+                diagram,
+                tag,
+            });
+        };
+
+        for info in self.signature.iter() {
+            match info.generator.dimension.cmp(&dimension) {
+                Ordering::Less => {
+                    if weak_units {
+                        let mut diagram = info.diagram.clone();
+                        while diagram.dimension() < dimension {
+                            diagram = diagram.weak_identity().into();
+                        }
+                        let diagram = DiagramN::try_from(diagram).unwrap();
+                        push(info.generator, diagram, Some("identity".to_owned()));
+                    }
+
+                    if let Diagram::DiagramN(d) = &info.diagram {
+                        if info.invertible {
+                            let bubble = |mut diagram: DiagramN| {
+                                while diagram.dimension() < dimension {
+                                    diagram = diagram.bubble();
+                                }
+                                diagram
+                            };
+
+                            push(info.generator, bubble(d.clone()), Some("bubble".to_owned()));
+                            push(
+                                info.generator,
+                                bubble(d.inverse()),
+                                Some("inverse bubble".to_owned()),
+                            );
+                        }
+                    }
+                }
+                Ordering::Equal => {
+                    if let Diagram::DiagramN(d) = &info.diagram {
+                        push(info.generator, d.clone(), None);
+                        if info.invertible {
+                            push(info.generator, d.inverse(), Some("inverse".to_owned()));
+                        }
+                    }
+                }
+                Ordering::Greater => (),
+            }
+        }
+
+        for item in self.shelf.iter() {
+            let Diagram::DiagramN(diagram) = &item.diagram else { continue };
+            if diagram.dimension() == dimension {
+                push(
+                    diagram.max_generator().generator,
+                    diagram.clone(),
+                    Some(format!("shelf: {}", item.name)),
+                );
+            }
+        }
+
+        candidates
+    }
+
+    /// All ways in which `candidate` can be attached to the workspace diagram, either in its
+    /// interior or on any of its boundaries.
+    pub fn attach_options(&self, candidate: &AttachCandidate) -> Vector<AttachOption> {
+        self.workspace
+            .as_ref()
+            .map_or_else(Default::default, |ws| candidate.options(&ws.diagram))
+    }
+
+    /// All ways of attaching a diagram to the workspace which cover the given point of the
+    /// visible diagram, or `None` if there is no workspace or the point is not in it.
+    pub fn attach_options_at(
+        &self,
+        point: &[SliceIndex],
+        weak_units: bool,
+    ) -> Option<Vector<AttachOption>> {
+        let ws = self.workspace.as_ref()?;
+        let point: Vec<SliceIndex> = ws.path.iter().chain(point).copied().collect();
+        let (boundary_path, point) = BoundaryPath::split(&point);
+
+        let haystack = match boundary_path {
+            None => ws.diagram.clone(),
+            Some(boundary_path) => DiagramN::try_from(ws.diagram.clone())
+                .ok()?
+                .boundary(boundary_path)?,
+        };
+
+        Some(
+            self.attach_candidates(haystack.dimension() + 1, weak_units)
+                .into_iter()
+                .flat_map(|candidate| {
+                    candidate.options_on(&haystack, boundary_path, Some(point.as_slice()))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A diagram which can be attached to the workspace, see [ProofState::attach_options].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AttachCandidate {
+    /// The generator used to present the candidate to the user.
+    pub generator: Generator,
+    pub diagram: DiagramN,
+    pub tag: Option<String>,
+}
+
+impl AttachCandidate {
+    /// All ways in which the candidate can be attached to `diagram`, either in its interior or
+    /// on any of its boundaries.
+    pub fn options(&self, diagram: &Diagram) -> Vector<AttachOption> {
+        let mut options = self.options_on(diagram, None, None);
+
+        if let Diagram::DiagramN(diagram) = diagram {
+            for depth in 0..diagram.dimension() {
+                for boundary in [Boundary::Source, Boundary::Target] {
+                    let boundary_path = BoundaryPath(boundary, depth);
+                    if let Some(haystack) = diagram.boundary(boundary_path) {
+                        options.append(self.options_on(&haystack, Some(boundary_path), None));
+                    }
+                }
+            }
+        }
+
+        options
+    }
+
+    /// The ways of attaching the candidate to `haystack`, which is the workspace diagram or its
+    /// boundary at `boundary_path`, covering `point` of the haystack if one is given.
+    fn options_on(
+        &self,
+        haystack: &Diagram,
+        boundary_path: Option<BoundaryPath>,
+        point: Option<&[Height]>,
+    ) -> Vector<AttachOption> {
+        if self.diagram.dimension() != haystack.dimension() + 1 {
+            return Vector::new();
+        }
+
+        let boundary = boundary_path.map_or(Boundary::Target, BoundaryPath::boundary);
+        let needle = self.diagram.slice(boundary.flip()).unwrap();
+        haystack
+            .embeddings(&needle)
+            .filter(|embedding| {
+                point.map_or(true, |point| contains_point(&needle, point, embedding))
+            })
+            .map(|embedding| AttachOption {
+                generator: self.generator,
+                boundary_path,
+                embedding: embedding.into_iter().collect(),
+                tag: self.tag.clone(),
+                diagram: self.diagram.clone(),
+            })
+            .collect()
+    }
+}
+
+fn contains_point(diagram: &Diagram, point: &[Height], embedding: &[RegularHeight]) -> bool {
+    use Diagram::{Diagram0, DiagramN};
+
+    match (point.split_first(), diagram) {
+        (None, _) => true,
+        (Some(_), Diagram0(_)) => false,
+        (Some((height, point)), DiagramN(diagram)) => {
+            let (shift, embedding) = embedding.split_first().unwrap_or((&0, &[]));
+            let shift = Height::Regular(*shift);
+
+            if usize::from(*height) < usize::from(shift) {
+                return false;
+            }
+
+            let height = Height::from(usize::from(*height) - usize::from(shift));
+
+            match diagram.slice(height) {
+                Some(slice) => contains_point(&slice, point, embedding),
+                None => false,
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -1,5 +1,5 @@
 use homotopy_core::{common::Generator, Diagram};
-use homotopy_graphics::style::{Color, GeneratorStyle, VertexShape};
+use homotopy_graphics::style::{Color, GeneratorStyle, SurfaceStyle, VertexShape, WireStyle};
 use serde::Serialize;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -10,15 +10,20 @@ pub struct GeneratorInfo {
     pub single_preview: bool,
     pub color: Color,
     pub shape: VertexShape,
+    pub wire: WireStyle,
+    pub surface: SurfaceStyle,
     pub diagram: Diagram,
     pub name: String,
+    /// LaTeX used by renderers to label the generator.
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub citations: Vec<String>,
+    pub tags: Vec<String>,
 }
 
 impl GeneratorStyle for GeneratorInfo {
     fn label(&self) -> Option<String> {
-        // TODO(thud): Decide whether to show a label
-        // Some(self.name.clone())
-        None
+        self.label.clone()
     }
 
     fn shape(&self) -> VertexShape {
@@ -28,6 +33,14 @@ impl GeneratorStyle for GeneratorInfo {
     fn color(&self) -> Color {
         self.color.clone()
     }
+
+    fn wire_style(&self) -> WireStyle {
+        self.wire
+    }
+
+    fn surface_style(&self) -> SurfaceStyle {
+        self.surface
+    }
 }
 
 impl homotopy_core::signature::GeneratorInfo for GeneratorInfo {
@@ -39,3 +52,76 @@ impl homotopy_core::signature::GeneratorInfo for GeneratorInfo {
         self.invertible
     }
 }
+
+/// A search for generators in the signature, parsed from a query such as
+/// `monoid dim:2 is:invertible #axiom`.
+///
+/// Plain words must all occur in the name or a tag of a generator. `dim:n` restricts the
+/// dimension, `is:invertible` and `is:oriented` (or `not:…`) the kind of generator, and `#tag` or
+/// `tag:tag` requires a tag.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GeneratorFilter {
+    pub words: Vec<String>,
+    pub dimension: Option<usize>,
+    pub invertible: Option<bool>,
+    pub oriented: Option<bool>,
+    pub tags: Vec<String>,
+}
+
+impl GeneratorFilter {
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Self::default();
+        for token in query.split_whitespace() {
+            let token = token.to_lowercase();
+            if let Some(tag) = token.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+                filter.tags.push(tag.to_owned());
+                continue;
+            }
+            let (key, value) = token.split_once(':').unwrap_or(("", token.as_str()));
+            match (key, value, value.parse()) {
+                ("dim" | "dimension", _, Ok(dimension)) => filter.dimension = Some(dimension),
+                ("is" | "not", "invertible", _) => filter.invertible = Some(key == "is"),
+                ("is" | "not", "oriented", _) => filter.oriented = Some(key == "is"),
+                ("tag", _, _) if !value.is_empty() => filter.tags.push(value.to_owned()),
+                _ => filter.words.push(token.clone()),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether the filter only searches for words, so that it can also match folders by name.
+    pub fn is_textual(&self) -> bool {
+        self.dimension.is_none()
+            && self.invertible.is_none()
+            && self.oriented.is_none()
+            && self.tags.is_empty()
+    }
+
+    pub fn matches_name(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.words.iter().all(|word| name.contains(word.as_str()))
+    }
+
+    pub fn matches(&self, info: &GeneratorInfo) -> bool {
+        let name = info.name.to_lowercase();
+        let tags: Vec<_> = info.tags.iter().map(|tag| tag.to_lowercase()).collect();
+        let words = self.words.iter().all(|word| {
+            name.contains(word.as_str()) || tags.iter().any(|tag| tag.contains(word.as_str()))
+        });
+        words
+            && self
+                .dimension
+                .map_or(true, |dimension| info.generator.dimension == dimension)
+            && self
+                .invertible
+                .map_or(true, |invertible| info.invertible == invertible)
+            && self
+                .oriented
+                .map_or(true, |oriented| info.oriented == oriented)
+            && self.tags.iter().all(|tag| tags.contains(tag))
+    }
+}
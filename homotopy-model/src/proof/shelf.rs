@@ -0,0 +1,75 @@
+use homotopy_core::{common::Generator, Diagram};
+use im::Vector;
+use serde::{Deserialize, Serialize};
+
+/// A diagram kept on the shelf, which is not part of the signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShelfItem {
+    pub name: String,
+    pub diagram: Diagram,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShelfEdit {
+    Rename(usize, String),
+    Remove(usize),
+}
+
+/// Scratch diagrams that are kept alongside the proof without polluting the signature.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Shelf(Vector<ShelfItem>);
+
+impl Shelf {
+    pub fn iter(&self) -> impl Iterator<Item = &ShelfItem> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ShelfItem> {
+        self.0.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, diagram: Diagram) {
+        let name = format!("Diagram {}", self.0.len() + 1);
+        self.0.push_back(ShelfItem { name, diagram });
+    }
+
+    pub fn insert_item(&mut self, item: ShelfItem) {
+        self.0.push_back(item);
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&ShelfItem) -> bool) {
+        self.0.retain(f);
+    }
+
+    pub fn remove_framing(&mut self, generator: Generator) {
+        for item in self.0.iter_mut() {
+            item.diagram = item.diagram.remove_framing(generator);
+        }
+    }
+
+    /// Apply an edit to the shelf. Returns `false` if the index is out of bounds.
+    pub fn update(&mut self, edit: &ShelfEdit) -> bool {
+        match edit {
+            ShelfEdit::Rename(index, name) => {
+                let Some(item) = self.0.get_mut(*index) else { return false };
+                item.name = name.clone();
+            }
+            ShelfEdit::Remove(index) => {
+                if *index >= self.0.len() {
+                    return false;
+                }
+                self.0.remove(*index);
+            }
+        }
+
+        true
+    }
+}
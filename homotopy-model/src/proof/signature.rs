@@ -1,27 +1,19 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::collections::{HashSet, VecDeque};
 
-use homotopy_common::tree::{Node, Tree};
+use homotopy_common::{
+    hash::FastHashMap,
+    tree::{Node, Tree},
+};
 use homotopy_core::{
     common::Generator, diagram::NewDiagramError, signature::Signature as S, Diagram, Diagram0,
     DiagramN,
 };
-use homotopy_graphics::style::{Color, SignatureStyleData, VertexShape};
+use homotopy_graphics::style::{
+    Color, Palette, SignatureStyleData, SurfaceStyle, VertexShape, WireStyle,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::proof::generators::GeneratorInfo;
-
-pub const COLORS: &[&str] = &[
-    "#2980b9", // belize blue
-    "#c0392b", // pomegranate
-    "#f39c12", // orange
-    "#8e44ad", // wisteria
-    "#27ae60", // nephritis
-    "#f1c40f", // sunflower
-    "#f6f5f4", // white(ish)
-    "#000000", // black
-];
-
-pub const VERTEX_SHAPES: &[VertexShape] = &[VertexShape::Circle, VertexShape::Square];
+use crate::proof::generators::{GeneratorFilter, GeneratorInfo};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum SignatureItem {
@@ -44,9 +36,19 @@ pub enum SignatureItemEdit {
     Rename(String),
     Recolor(Color),
     Reshape(VertexShape),
+    SetWireStyle(WireStyle),
+    SetSurfaceStyle(SurfaceStyle),
     MakeOriented(bool),
     MakeInvertible(bool),
     ShowSourceTarget(bool),
+    /// Set the LaTeX label of a generator, or clear it if empty.
+    Relabel(String),
+    /// Set the description of a generator, or clear it if empty.
+    Describe(String),
+    AddCitation(String),
+    RemoveCitation(usize),
+    AddTag(String),
+    RemoveTag(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -57,12 +59,31 @@ pub enum SignatureEdit {
     ToggleFolder(Node),
     NewFolder(Node),
     Remove(Node),
+    /// Use a different palette for new generators.
+    SetPalette(Palette),
+    /// Recolour every generator from the current palette.
+    RecolorAll,
+}
+
+/// The outcome of [Signature::search].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SignatureSearch {
+    pub matches: HashSet<Node>,
+    pub visible: HashSet<Node>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
-pub struct Signature(Tree<SignatureItem>);
+pub struct Signature(Tree<SignatureItem>, Palette);
 
 impl Signature {
+    pub fn new(tree: Tree<SignatureItem>, palette: Palette) -> Self {
+        Self(tree, palette)
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.1
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &GeneratorInfo> {
         self.0.iter().filter_map(|(_, data)| match data.inner() {
             SignatureItem::Item(info) => Some(info),
@@ -70,6 +91,43 @@ impl Signature {
         })
     }
 
+    /// The folders of the signature, not including the root.
+    pub fn folders(&self) -> impl Iterator<Item = (Node, &FolderInfo)> {
+        let root = self.0.root();
+        self.0
+            .iter()
+            .filter(move |(node, _)| *node != root)
+            .filter_map(|(node, data)| match data.inner() {
+                SignatureItem::Item(_) => None,
+                SignatureItem::Folder(info) => Some((node, info)),
+            })
+    }
+
+    /// Search the signature, keeping its folder structure: the result holds the generators that
+    /// match, together with every folder on the way to one. A purely textual search also matches
+    /// folders by name, which then keep all their contents.
+    pub fn search(&self, filter: &GeneratorFilter) -> SignatureSearch {
+        let mut search = SignatureSearch::default();
+        for (node, data) in self.0.iter() {
+            let matches = match data.inner() {
+                SignatureItem::Item(info) => filter.matches(info),
+                SignatureItem::Folder(info) => {
+                    node != self.0.root()
+                        && !filter.words.is_empty()
+                        && filter.is_textual()
+                        && filter.matches_name(&info.name)
+                }
+            };
+            if matches {
+                search.matches.insert(node);
+                search.visible.extend(self.0.ancestors_of(node));
+                search.visible.extend(self.0.descendents_of(node));
+            }
+        }
+        search.visible.insert(self.0.root());
+        search
+    }
+
     fn next_generator_id(&self) -> usize {
         self.iter()
             .map(|info| info.generator.id)
@@ -103,9 +161,15 @@ impl Signature {
             oriented: false,
             invertible,
             single_preview: true,
-            color: Color::from_str(COLORS[generator.id % COLORS.len()]).unwrap(),
+            color: self.1.color(generator.id),
             shape: Default::default(),
+            wire: Default::default(),
+            surface: Default::default(),
             diagram,
+            label: None,
+            description: None,
+            citations: Vec::new(),
+            tags: Vec::new(),
         };
 
         self.0.push_onto(self.0.root(), SignatureItem::Item(info));
@@ -133,15 +197,37 @@ impl Signature {
 
     fn edit(&mut self, node: Node, edit: SignatureItemEdit) {
         use SignatureItemEdit::{
-            MakeInvertible, MakeOriented, Recolor, Rename, Reshape, ShowSourceTarget,
+            AddCitation, AddTag, Describe, MakeInvertible, MakeOriented, Recolor, Relabel,
+            RemoveCitation, RemoveTag, Rename, Reshape, SetSurfaceStyle, SetWireStyle,
+            ShowSourceTarget,
         };
         self.0.with_mut(node, move |n| match (n.inner_mut(), edit) {
             (SignatureItem::Item(info), Rename(name)) => info.name = name,
             (SignatureItem::Item(info), Recolor(color)) => info.color = color,
             (SignatureItem::Item(info), Reshape(shape)) => info.shape = shape,
+            (SignatureItem::Item(info), SetWireStyle(style)) => info.wire = style,
+            (SignatureItem::Item(info), SetSurfaceStyle(style)) => info.surface = style,
             (SignatureItem::Item(info), MakeOriented(true)) => info.oriented = true,
             (SignatureItem::Item(info), MakeInvertible(true)) => info.invertible = true,
             (SignatureItem::Item(info), ShowSourceTarget(show)) => info.single_preview = !show,
+            (SignatureItem::Item(info), Relabel(label)) => {
+                info.label = (!label.is_empty()).then_some(label);
+            }
+            (SignatureItem::Item(info), Describe(description)) => {
+                info.description = (!description.is_empty()).then_some(description);
+            }
+            (SignatureItem::Item(info), AddCitation(citation)) => info.citations.push(citation),
+            (SignatureItem::Item(info), RemoveCitation(index)) => {
+                if index < info.citations.len() {
+                    info.citations.remove(index);
+                }
+            }
+            (SignatureItem::Item(info), AddTag(tag)) => {
+                if !info.tags.contains(&tag) {
+                    info.tags.push(tag);
+                }
+            }
+            (SignatureItem::Item(info), RemoveTag(tag)) => info.tags.retain(|t| *t != tag),
             (SignatureItem::Folder(info), Rename(name)) => info.name = name,
             (_, _) => {}
         });
@@ -258,9 +344,156 @@ impl Signature {
                     self.0.remove(removing);
                 }
             }
+            SignatureEdit::SetPalette(palette) => self.1 = palette.clone(),
+            SignatureEdit::RecolorAll => {
+                let palette = &self.1;
+                self.0 = self.0.clone().map(|item| match item {
+                    SignatureItem::Item(info) => SignatureItem::Item(GeneratorInfo {
+                        color: palette.color(info.generator.id),
+                        ..info
+                    }),
+                    SignatureItem::Folder(_) => item,
+                });
+            }
         }
     }
 
+    /// Merge another signature into this one as a new folder called `name`, keeping the folder
+    /// structure of `other`. Its generators are renumbered after the existing ones, except for
+    /// those with the same name and dimension as an existing generator whose diagram agrees
+    /// after renumbering: these are identified with the existing generator instead of being
+    /// added twice.
+    pub fn merge(&mut self, other: &Self, name: &str) -> Node {
+        let mut generators: Vec<_> = other.iter().collect();
+        generators.sort_by_key(|info| (info.generator.dimension, info.generator.id));
+
+        // Generators only mention generators of lower dimension, besides themselves, so
+        // renumbering by dimension means every diagram can be compared once its boundary is
+        // renumbered.
+        let mut map = FastHashMap::default();
+        // Existing generators already taken by another generator of the library, which must not
+        // be merged into them too.
+        let mut taken = HashSet::new();
+        let mut next_id = self.next_generator_id();
+        for info in generators {
+            let existing = self.iter().find(|existing| {
+                existing.generator.dimension == info.generator.dimension
+                    && existing.name == info.name
+                    && !taken.contains(&existing.generator)
+                    && {
+                        let mut map = map.clone();
+                        map.insert(info.generator, existing.generator);
+                        info.diagram.replace_generators(&map) == existing.diagram
+                    }
+            });
+            let generator = existing.map_or_else(
+                || {
+                    next_id += 1;
+                    Generator::new(next_id - 1, info.generator.dimension)
+                },
+                |existing| {
+                    taken.insert(existing.generator);
+                    existing.generator
+                },
+            );
+            map.insert(info.generator, generator);
+        }
+
+        let folder = self
+            .0
+            .push_onto(
+                self.0.root(),
+                SignatureItem::Folder(FolderInfo {
+                    id: self.next_folder_id(),
+                    name: name.to_owned(),
+                    open: true,
+                }),
+            )
+            .unwrap();
+        let existing: HashSet<_> = self.iter().map(|info| info.generator).collect();
+        self.merge_children(other, other.0.root(), folder, &map, &existing);
+        folder
+    }
+
+    fn merge_children(
+        &mut self,
+        other: &Self,
+        from: Node,
+        into: Node,
+        map: &FastHashMap<Generator, Generator>,
+        existing: &HashSet<Generator>,
+    ) {
+        for child in other.0[from].children() {
+            match other.0[child].inner() {
+                SignatureItem::Folder(info) => {
+                    let folder = self
+                        .0
+                        .push_onto(
+                            into,
+                            SignatureItem::Folder(FolderInfo {
+                                id: self.next_folder_id(),
+                                ..info.clone()
+                            }),
+                        )
+                        .unwrap();
+                    self.merge_children(other, child, folder, map, existing);
+                }
+                SignatureItem::Item(info) => {
+                    let generator = map[&info.generator];
+                    if !existing.contains(&generator) {
+                        let info = GeneratorInfo {
+                            generator,
+                            diagram: info.diagram.replace_generators(map),
+                            ..info.clone()
+                        };
+                        self.0.push_onto(into, SignatureItem::Item(info));
+                    }
+                }
+            }
+        }
+    }
+
+    /// The part of the signature needed by the given nodes: the generators in them and
+    /// everything their diagrams depend on, in the folders they were in. Folders left without
+    /// generators are dropped.
+    pub fn library(&self, nodes: &[Node]) -> Self {
+        let mut required: HashSet<Generator> = nodes
+            .iter()
+            .flat_map(|&node| self.0.descendents_of(node))
+            .filter_map(|node| self.find_generator(node))
+            .collect();
+        let mut queue: Vec<_> = required.iter().copied().collect();
+        while let Some(generator) = queue.pop() {
+            if let Some(info) = self.generator_info(generator) {
+                for dependency in info.diagram.generators() {
+                    if required.insert(dependency) {
+                        queue.push(dependency);
+                    }
+                }
+            }
+        }
+
+        let mut tree = self.0.clone();
+        for (node, data) in self.0.iter() {
+            if let SignatureItem::Item(info) = data.inner() {
+                if !required.contains(&info.generator) {
+                    tree.remove(node);
+                }
+            }
+        }
+        for (node, _) in self.folders() {
+            let empty = !tree
+                .descendents_of(node)
+                .any(|descendent| matches!(tree[descendent].inner(), SignatureItem::Item(_)));
+            if empty {
+                tree.remove(node);
+            }
+        }
+        tree.clean_up();
+
+        Self::new(tree, self.1.clone())
+    }
+
     pub fn as_tree(&self) -> Tree<SignatureItem> {
         self.0.clone()
     }
@@ -304,7 +537,7 @@ impl homotopy_core::signature::Signature for Signature {
 
 impl From<Tree<SignatureItem>> for Signature {
     fn from(tree: Tree<SignatureItem>) -> Self {
-        Self(tree)
+        Self::new(tree, Palette::default())
     }
 }
 
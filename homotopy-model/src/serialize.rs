@@ -4,12 +4,14 @@ use homotopy_core::{
     serialize::{Key, Store},
     Diagram,
 };
-use homotopy_graphics::style::Color;
+use homotopy_graphics::style::{Color, Palette, SurfaceStyle, VertexShape, WireStyle};
 use im::Vector;
 use obake::AnyVersion;
 
 use super::proof::{
-    generators::GeneratorInfo, FolderInfo, Metadata, Signature, SignatureItem, View, Workspace,
+    generators::GeneratorInfo,
+    shelf::{Shelf, ShelfItem},
+    FolderInfo, Metadata, Signature, SignatureItem, Tab, Tabs, View, Viewpoint, Workspace,
 };
 
 #[obake::versioned]
@@ -21,20 +23,111 @@ struct WorkspaceData {
     diagram: Key<Diagram>,
     path: Vector<SliceIndex>,
     view: View,
+    #[serde(default)]
+    viewpoints: Vector<Viewpoint>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+struct TabData {
+    name: String,
+    workspace: Option<WorkspaceData>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct ShelfData {
+    name: String,
+    diagram: Key<Diagram>,
 }
 
 #[obake::versioned]
 #[obake(version("0.1.3"))]
+#[obake(version("0.1.4"))]
+#[obake(version("0.1.5"))]
+#[obake(version("0.1.6"))]
+#[obake(version("0.1.7"))]
 #[obake(derive(serde::Serialize, serde::Deserialize))]
 #[obake(serde(tag = "version"))]
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct Data {
     store: Store,
+    // Generators were saved without their label, description, citations and tags before 0.1.7.
+    #[obake(cfg("<0.1.7"))]
+    #[serde(rename = "signature")]
+    old_signature: Tree<SignatureData<GeneratorData!["0.1.6"]>>,
+    #[obake(cfg(">=0.1.7"))]
     signature: Tree<SignatureData>,
+    #[obake(cfg("0.1.3"))]
     workspace: Option<WorkspaceData>,
+    #[obake(cfg(">=0.1.4"))]
+    tabs: Vec<TabData>,
+    #[obake(cfg(">=0.1.4"))]
+    active_tab: usize,
+    #[obake(cfg(">=0.1.5"))]
+    shelf: Vec<ShelfData>,
+    #[obake(cfg(">=0.1.6"))]
+    palette: Palette,
     metadata: Metadata,
 }
 
+impl From<Data!["0.1.3"]> for Data!["0.1.4"] {
+    fn from(data: Data!["0.1.3"]) -> Self {
+        Self {
+            store: data.store,
+            old_signature: data.old_signature,
+            tabs: vec![TabData {
+                name: Tabs::default_name(0),
+                workspace: data.workspace,
+            }],
+            active_tab: 0,
+            metadata: data.metadata,
+        }
+    }
+}
+
+impl From<Data!["0.1.4"]> for Data!["0.1.5"] {
+    fn from(data: Data!["0.1.4"]) -> Self {
+        Self {
+            store: data.store,
+            old_signature: data.old_signature,
+            tabs: data.tabs,
+            active_tab: data.active_tab,
+            shelf: Default::default(),
+            metadata: data.metadata,
+        }
+    }
+}
+
+impl From<Data!["0.1.5"]> for Data!["0.1.6"] {
+    fn from(data: Data!["0.1.5"]) -> Self {
+        Self {
+            store: data.store,
+            old_signature: data.old_signature,
+            tabs: data.tabs,
+            active_tab: data.active_tab,
+            shelf: data.shelf,
+            palette: Default::default(),
+            metadata: data.metadata,
+        }
+    }
+}
+
+impl From<Data!["0.1.6"]> for Data!["0.1.7"] {
+    fn from(data: Data!["0.1.6"]) -> Self {
+        Self {
+            store: data.store,
+            signature: data.old_signature.map(|item| match item {
+                SignatureData::Folder(name, open) => SignatureData::Folder(name, open),
+                SignatureData::Item(generator) => SignatureData::Item(generator.into()),
+            }),
+            tabs: data.tabs,
+            active_tab: data.active_tab,
+            shelf: data.shelf,
+            palette: data.palette,
+            metadata: data.metadata,
+        }
+    }
+}
+
 impl std::fmt::Debug for Data {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Data").finish()
@@ -42,17 +135,20 @@ impl std::fmt::Debug for Data {
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
-enum SignatureData {
+enum SignatureData<G = GeneratorData> {
     Folder(String, bool),
-    Item(GeneratorData),
+    Item(G),
 }
 
-impl Default for SignatureData {
+impl<G> Default for SignatureData<G> {
     fn default() -> Self {
         Self::Folder(Default::default(), true)
     }
 }
 
+#[obake::versioned]
+#[obake(version("0.1.6"))]
+#[obake(version("0.1.7"))]
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct GeneratorData {
     generator: Generator,
@@ -61,17 +157,50 @@ struct GeneratorData {
     oriented: bool,
     invertible: bool,
     diagram: Key<Diagram>,
+    #[serde(default)]
+    shape: VertexShape,
+    #[serde(default)]
+    wire: WireStyle,
+    #[serde(default)]
+    surface: SurfaceStyle,
+    #[obake(cfg(">=0.1.7"))]
+    label: Option<String>,
+    #[obake(cfg(">=0.1.7"))]
+    description: Option<String>,
+    #[obake(cfg(">=0.1.7"))]
+    citations: Vec<String>,
+    #[obake(cfg(">=0.1.7"))]
+    tags: Vec<String>,
 }
 
-pub fn serialize(
-    signature: Signature,
-    workspace: Option<Workspace>,
-    metadata: Metadata,
-) -> Vec<u8> {
+impl From<GeneratorData!["0.1.6"]> for GeneratorData!["0.1.7"] {
+    fn from(data: GeneratorData!["0.1.6"]) -> Self {
+        Self {
+            generator: data.generator,
+            name: data.name,
+            color: data.color,
+            oriented: data.oriented,
+            invertible: data.invertible,
+            diagram: data.diagram,
+            shape: data.shape,
+            wire: data.wire,
+            surface: data.surface,
+            label: None,
+            description: None,
+            citations: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+pub fn serialize(signature: Signature, tabs: Tabs, shelf: Shelf, metadata: Metadata) -> Vec<u8> {
     let mut data = Data {
         store: Default::default(),
         signature: Default::default(),
-        workspace: Default::default(),
+        tabs: Default::default(),
+        active_tab: tabs.active(),
+        shelf: Default::default(),
+        palette: signature.palette().clone(),
         metadata,
     };
 
@@ -86,26 +215,48 @@ pub fn serialize(
             diagram: data.store.pack_diagram(&info.diagram),
             name: info.name,
             color: info.color,
+            shape: info.shape,
+            wire: info.wire,
+            surface: info.surface,
             oriented: info.oriented,
             invertible: info.invertible,
+            label: info.label,
+            description: info.description,
+            citations: info.citations,
+            tags: info.tags,
         }),
     });
 
-    if let Some(workspace) = workspace {
-        data.workspace = Some(WorkspaceData {
+    // Pack workspace tabs (selected boundaries are not persisted)
+    for tab in tabs.iter() {
+        let workspace = tab.workspace.as_ref().map(|workspace| WorkspaceData {
             diagram: data.store.pack_diagram(&workspace.diagram),
-            path: workspace.path,
+            path: workspace.path.clone(),
             view: workspace.view,
+            viewpoints: workspace.viewpoints.clone(),
+        });
+        data.tabs.push(TabData {
+            name: tab.name.clone(),
+            workspace,
         });
     }
 
+    // Pack shelf
+    data.shelf = shelf
+        .iter()
+        .map(|item| ShelfData {
+            name: item.name.clone(),
+            diagram: data.store.pack_diagram(&item.diagram),
+        })
+        .collect();
+
     // Tag data with version
     let data: AnyVersion<Data> = data.into();
     // Serialize
     rmp_serde::encode::to_vec_named(&data).unwrap()
 }
 
-pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metadata)> {
+pub fn deserialize(data: &[u8]) -> Option<((Signature, Tabs, Shelf), Metadata)> {
     // Deserialize with version tag
     let data: AnyVersion<Data> = match rmp_serde::decode::from_slice(data) {
         Err(error) => {
@@ -119,7 +270,7 @@ pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metad
     let mut store = data.store;
 
     let mut folder_index = 0;
-    let signature = data
+    let tree = data
         .signature
         .map(|s| {
             Some(match s {
@@ -135,25 +286,46 @@ pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metad
                     generator: gd.generator,
                     name: gd.name,
                     color: gd.color,
-                    shape: Default::default(),
+                    shape: gd.shape,
+                    wire: gd.wire,
+                    surface: gd.surface,
                     diagram: store.unpack_diagram(gd.diagram)?,
                     oriented: gd.oriented,
                     invertible: gd.invertible,
                     // TODO: `single_preview` should be properly serialized
                     single_preview: true,
+                    label: gd.label,
+                    description: gd.description,
+                    citations: gd.citations,
+                    tags: gd.tags,
                 }),
             })
         })
-        .transpose()?
-        .into();
-
-    let mut workspace = None;
-    if let Some(workspace_data) = data.workspace {
-        workspace = Some(Workspace {
-            diagram: store.unpack_diagram(workspace_data.diagram)?,
-            path: workspace_data.path,
-            view: workspace_data.view,
+        .transpose()?;
+    let signature = Signature::new(tree, data.palette);
+
+    let mut tabs = Vector::new();
+    for tab_data in data.tabs {
+        let mut tab = Tab::new(tab_data.name);
+        if let Some(workspace_data) = tab_data.workspace {
+            tab.workspace = Some(Workspace {
+                diagram: store.unpack_diagram(workspace_data.diagram)?,
+                path: workspace_data.path,
+                view: workspace_data.view,
+                viewpoints: workspace_data.viewpoints,
+            });
+        }
+        tabs.push_back(tab);
+    }
+    let tabs = Tabs::new(tabs, data.active_tab)?;
+
+    let mut shelf = Shelf::default();
+    for shelf_data in data.shelf {
+        shelf.insert_item(ShelfItem {
+            name: shelf_data.name,
+            diagram: store.unpack_diagram(shelf_data.diagram)?,
         });
     }
-    Some(((signature, workspace), data.metadata))
+
+    Some(((signature, tabs, shelf), data.metadata))
 }
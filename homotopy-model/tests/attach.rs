@@ -0,0 +1,76 @@
+use homotopy_core::{Boundary, Diagram, Generator, Height, SliceIndex};
+use homotopy_model::proof::{Action, ProofState};
+
+/// A signature with a single endomorphism `f: x -> x`, with `f` in the workspace.
+fn endomorphism() -> ProofState {
+    let x = Generator::new(0, 0);
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof.update(&Action::SelectGenerator(x)).unwrap();
+    proof
+        .update(&Action::SetBoundary(Boundary::Source))
+        .unwrap();
+    proof.update(&Action::SelectGenerator(x)).unwrap();
+    proof
+        .update(&Action::SetBoundary(Boundary::Target))
+        .unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(1, 1)))
+        .unwrap();
+    proof
+}
+
+fn workspace_size(proof: &ProofState) -> Option<usize> {
+    let Diagram::DiagramN(diagram) = &proof.workspace.as_ref()?.diagram else { return None };
+    Some(diagram.size())
+}
+
+#[test]
+fn attach_shelf_diagram() {
+    let mut proof = endomorphism();
+    let f = proof
+        .attach_candidates(1, false)
+        .into_iter()
+        .find(|candidate| candidate.generator == Generator::new(1, 1))
+        .unwrap();
+    assert_eq!(proof.attach_options(&f).len(), 2);
+
+    let option = proof.attach_options(&f).into_iter().next().unwrap();
+    proof.update(&Action::Attach(option)).unwrap();
+    assert_eq!(workspace_size(&proof), Some(2));
+
+    proof.update(&Action::CopyToShelf).unwrap();
+    let shelved = proof
+        .attach_candidates(1, false)
+        .into_iter()
+        .find(|candidate| candidate.tag.is_some())
+        .unwrap();
+    assert_eq!(shelved.diagram.size(), 2);
+
+    let option = proof.attach_options(&shelved).into_iter().next().unwrap();
+    proof.update(&Action::Attach(option)).unwrap();
+    assert_eq!(workspace_size(&proof), Some(4));
+}
+
+#[test]
+fn attach_lower_dimensional_candidates() {
+    let proof = endomorphism();
+    let f = Generator::new(1, 1);
+
+    let candidates = proof.attach_candidates(2, true);
+    assert!(candidates.iter().all(|c| c.diagram.dimension() == 2));
+    assert!(candidates
+        .iter()
+        .any(|c| c.generator == f && c.tag.as_deref() == Some("identity")));
+    assert!(proof
+        .attach_candidates(2, false)
+        .iter()
+        .all(|c| c.tag.as_deref() != Some("identity")));
+
+    // Only the identity on f can be attached to the point of f itself.
+    let point = [SliceIndex::Interior(Height::Singular(0))];
+    let options = proof.attach_options_at(&point, true).unwrap();
+    assert_eq!(options.len(), 1);
+    assert_eq!(options[0].generator, f);
+    assert!(proof.attach_options_at(&point, false).unwrap().is_empty());
+}
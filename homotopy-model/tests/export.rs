@@ -0,0 +1,336 @@
+use homotopy_core::{
+    complex::make_complex,
+    layout::Layout,
+    projection::{Depths, Projection},
+    Diagram,
+};
+use homotopy_graphics::{
+    figure::{Figure, FigureOptions},
+    label::{place_labels, LABEL_DISTANCE},
+    manim::{self, StepOptions},
+    mesh::MeshOptions,
+    pdf,
+    raster::Camera,
+    strip::StripOptions,
+    style::Theme,
+    svg::{self, render::GraphicElement, SvgOptions},
+    tikz,
+};
+use homotopy_model::{
+    history::Proof,
+    proof::{Action, SignatureEdit, SignatureItem, SignatureItemEdit},
+};
+
+fn associator() -> Proof {
+    let (_safe, actions): (bool, Vec<Action>) =
+        serde_json::from_str(include_str!("examples/associator.json")).unwrap();
+    let mut proof: Proof = Default::default();
+
+    for a in &actions {
+        proof
+            .update(a)
+            .expect("Actions should replay without errors.");
+    }
+
+    proof
+}
+
+fn generator_diagram(proof: &Proof, dimension: usize) -> Diagram {
+    proof
+        .signature
+        .iter()
+        .map(|info| info.diagram.clone())
+        .find(|diagram| diagram.dimension() == dimension)
+        .unwrap()
+}
+
+/// Give the same label to every generator in the signature.
+fn relabel(proof: &mut Proof, label: &str) {
+    let nodes: Vec<_> = proof
+        .signature
+        .as_tree()
+        .iter()
+        .filter_map(|(node, item)| matches!(item.inner(), SignatureItem::Item(_)).then_some(node))
+        .collect();
+
+    for node in nodes {
+        let edit = SignatureItemEdit::Relabel(label.to_owned());
+        proof
+            .update(&Action::EditSignature(SignatureEdit::Edit(node, edit)))
+            .unwrap();
+    }
+}
+
+fn graphic(diagram: &Diagram) -> Vec<GraphicElement<2>> {
+    let layout = Layout::new(diagram).unwrap();
+    let complex = make_complex(diagram);
+    let depths = Depths::new(diagram).unwrap();
+    let projection = Projection::new(diagram, &layout, &depths).unwrap();
+    GraphicElement::build(&complex, &layout, &projection, &depths)
+}
+
+#[test]
+fn place_labels_only_for_labelled_generators() {
+    let mut proof = associator();
+    let diagram = generator_diagram(&proof, 2);
+    let graphic = graphic(&diagram);
+    assert!(place_labels(&graphic, &proof.signature, LABEL_DISTANCE).is_empty());
+
+    relabel(&mut proof, r"\mu");
+    let labels = place_labels(&graphic, &proof.signature, LABEL_DISTANCE);
+    let anchors = graphic
+        .iter()
+        .filter(|element| !matches!(element, GraphicElement::Surface(..)))
+        .count();
+    assert_eq!(labels.len(), anchors);
+    assert!(labels.iter().all(|label| label.text == r"\mu"));
+}
+
+#[test]
+fn place_labels_apart() {
+    let mut proof = associator();
+    relabel(&mut proof, "x");
+    let diagram = generator_diagram(&proof, 2);
+    let labels = place_labels(&graphic(&diagram), &proof.signature, LABEL_DISTANCE);
+
+    for (i, label) in labels.iter().enumerate() {
+        let distance = (label.position - label.anchor).length();
+        assert!((distance - LABEL_DISTANCE).abs() < 1e-4);
+        assert!(labels[i + 1..]
+            .iter()
+            .all(|other| other.position != label.position));
+    }
+}
+
+#[test]
+fn export_figure() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 2);
+    let options = FigureOptions {
+        padding: 10.,
+        ..Default::default()
+    };
+    let figure = Figure::new(&diagram, &proof.signature, options).unwrap();
+    assert_eq!(figure.elements.len(), graphic(&diagram).len());
+
+    let (width, height) = figure.pixel_size(144.);
+    assert_eq!(width, (2. * figure.width).ceil() as u32);
+    assert_eq!(height, (2. * figure.height).ceil() as u32);
+
+    // The light theme has no background, so only the diagram itself is opaque.
+    let pixels = figure.rasterize(144.);
+    assert_eq!(pixels.len(), (4 * width * height) as usize);
+    assert_eq!(pixels[3], 0);
+    assert!(pixels.chunks(4).any(|pixel| pixel[3] == 255));
+}
+
+#[test]
+fn export_pdf() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 2);
+
+    let pdf = pdf::render(&diagram, &proof.signature, FigureOptions::default()).unwrap();
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+
+    // The cross-reference table gives the offset of every object in turn.
+    let start = pdf
+        .windows(10)
+        .rposition(|window| window == b"startxref\n")
+        .unwrap();
+    let xref: usize = std::str::from_utf8(&pdf[start + 10..])
+        .unwrap()
+        .lines()
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let mut table = std::str::from_utf8(&pdf[xref..]).unwrap().lines();
+    assert_eq!(table.next(), Some("xref"));
+    let count: usize = table.next().unwrap()[2..].parse().unwrap();
+    assert_eq!(table.next(), Some("0000000000 65535 f "));
+    for (i, entry) in table.take(count - 1).enumerate() {
+        let offset: usize = entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+    }
+}
+
+#[test]
+fn export_standalone_svg() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 2);
+
+    let styled = svg::export(&diagram, &proof.signature, &SvgOptions::default()).unwrap();
+    assert!(styled.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(styled.contains("<style>"));
+    assert!(styled.contains("class=\"generator__"));
+
+    let options = SvgOptions {
+        stylesheet: false,
+        ..Default::default()
+    };
+    let unstyled = svg::export(&diagram, &proof.signature, &options).unwrap();
+    assert!(!unstyled.contains("<style>"));
+    assert!(!unstyled.contains("class="));
+}
+
+#[test]
+fn export_themed_svg() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 2);
+    let export = |theme| {
+        let options = SvgOptions {
+            figure: FigureOptions {
+                theme,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        svg::export(&diagram, &proof.signature, &options).unwrap()
+    };
+
+    // Only the dark theme fills in the background.
+    let background = format!("fill=\"{}\"", Theme::Dark.background().unwrap().hex());
+    let light = export(Theme::Light);
+    let dark = export(Theme::Dark);
+    assert!(!light.contains(&background));
+    assert!(dark.contains(&background));
+
+    // Printed diagrams are grey throughout.
+    let print = svg::stylesheet(&proof.signature, Theme::Print);
+    for hex in print.split("fill: #").skip(1).map(|color| &color[..6]) {
+        assert_eq!(hex[0..2], hex[2..4]);
+        assert_eq!(hex[2..4], hex[4..6]);
+    }
+}
+
+#[test]
+fn export_svg_plain_labels() {
+    let mut proof = associator();
+    relabel(&mut proof, r"\alpha_{<}");
+    let diagram = generator_diagram(&proof, 2);
+
+    let options = SvgOptions {
+        show_labels: true,
+        ..Default::default()
+    };
+    let svg = svg::export(&diagram, &proof.signature, &options).unwrap();
+    assert!(svg.contains(">α&lt;</text>"));
+    assert!(!svg.contains("alpha"));
+}
+
+#[test]
+fn export_slice_scrub_svg() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 3);
+    let slices = 2 * diagram.size().unwrap() + 1;
+
+    let options = SvgOptions {
+        slice_duration: Some(1.),
+        ..Default::default()
+    };
+    let svg = svg::export(&diagram, &proof.signature, &options).unwrap();
+    assert_eq!(svg.matches("<animate ").count(), slices);
+}
+
+#[test]
+fn export_slice_strip_svg() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 3);
+    let slices = 2 * diagram.size().unwrap() + 1;
+
+    let svg = svg::export_strip(
+        &diagram,
+        &proof.signature,
+        &StripOptions::default(),
+        &SvgOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(svg.matches("<g transform=").count(), slices);
+    assert_eq!(svg.matches("marker-end=").count(), slices - 1);
+}
+
+#[test]
+fn export_tikz_slices() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 3);
+    let slices = 2 * diagram.size().unwrap() + 1;
+
+    let tikz = tikz::render_slices(
+        &diagram,
+        &proof.signature,
+        false,
+        true,
+        false,
+        Some(2),
+        Theme::default(),
+    )
+    .unwrap();
+    assert_eq!(tikz.matches("% Slice ").count(), slices);
+    // Slices in a grid are read in order, without arrows between them.
+    assert!(!tikz.contains("->"));
+}
+
+#[test]
+fn export_tikz_projected() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 3);
+    let render = |subdivision_depth, camera: &Camera| {
+        let options = MeshOptions {
+            subdivision_depth,
+            ..Default::default()
+        };
+        tikz::render_projected(&diagram, &proof.signature, options, camera, 10.).unwrap()
+    };
+
+    let front = render(tikz::MAX_PROJECTED_SUBDIVISION, &Camera::default());
+    assert!(front.contains("\\filldraw"));
+    assert!(front.ends_with("\\end{tikzpicture}\n"));
+
+    // Finer subdivisions are capped rather than producing ever larger pictures.
+    assert_eq!(render(6, &Camera::default()), front);
+
+    let side = Camera {
+        phi: 0.,
+        ..Default::default()
+    };
+    assert_ne!(render(tikz::MAX_PROJECTED_SUBDIVISION, &side), front);
+}
+
+#[test]
+fn export_manim_slices() {
+    let proof = associator();
+    let diagram = generator_diagram(&proof, 3);
+    let slices = 2 * diagram.size().unwrap() + 1;
+
+    let stylesheet = manim::stylesheet(&proof.signature, Theme::default());
+    let scene = manim::render_slices(
+        &diagram,
+        &proof.signature,
+        &stylesheet,
+        false,
+        false,
+        StepOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(scene.matches("def get_frame_").count(), slices);
+    // The wire of the source boundary is morphed through every slice.
+    let wire = scene
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("\"wire_"))
+        .and_then(|line| line.split('"').next())
+        .unwrap();
+    assert_eq!(scene.matches(&format!("\"wire_{wire}\"")).count(), slices);
+}
+
+#[test]
+fn export_manim_quotes_labels() {
+    let mut proof = associator();
+    relabel(&mut proof, r#"\text{"}"#);
+    let diagram = generator_diagram(&proof, 2);
+
+    let stylesheet = manim::stylesheet(&proof.signature, Theme::default());
+    let scene = manim::render(&diagram, &proof.signature, &stylesheet, false, true).unwrap();
+    assert!(scene.contains(r#"MathTex("\\text{\"}", "#));
+    assert!(!scene.contains("MathTex(r"));
+}
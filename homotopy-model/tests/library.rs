@@ -0,0 +1,199 @@
+use homotopy_core::{Diagram, Generator};
+use homotopy_model::{
+    proof::{Action, ProofState, SignatureEdit, SignatureItem, SignatureItemEdit},
+    serialize,
+};
+
+fn library() -> Vec<u8> {
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("Cell");
+    let y = proof.signature.create_generator_zero("Cell");
+    proof
+        .signature
+        .create_generator(x.into(), y.into(), "Arrow", false)
+        .unwrap();
+    serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    )
+}
+
+fn rename(proof: &mut ProofState, generator: Generator, name: &str) {
+    let node = proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| match item.inner() {
+            SignatureItem::Item(info) if info.generator == generator => Some(node),
+            _ => None,
+        })
+        .unwrap();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::Edit(
+            node,
+            SignatureItemEdit::Rename(name.to_owned()),
+        )))
+        .unwrap();
+}
+
+#[test]
+fn import_library_as_folder() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(0, 0)))
+        .unwrap();
+    let workspace = proof.workspace.clone();
+
+    proof
+        .update(&Action::ImportLibrary(
+            "Arrows".to_owned(),
+            library().into(),
+        ))
+        .unwrap();
+    assert_eq!(proof.workspace, workspace);
+
+    // The first point is identical to the existing one, so only the second point and the arrow
+    // are added.
+    let generators: Vec<_> = proof.signature.iter().map(|info| info.generator).collect();
+    assert_eq!(
+        generators,
+        vec![
+            Generator::new(0, 0),
+            Generator::new(1, 0),
+            Generator::new(2, 1)
+        ]
+    );
+    let arrow = proof
+        .signature
+        .iter()
+        .find(|info| info.name == "Arrow 2")
+        .unwrap();
+    assert_eq!(
+        arrow.diagram.generators(),
+        generators.iter().copied().collect()
+    );
+    assert!(matches!(arrow.diagram, Diagram::DiagramN(_)));
+
+    let tree = proof.signature.as_tree();
+    let (folder, info) = proof.signature.folders().next().unwrap();
+    assert_eq!(info.name, "Arrows");
+    assert_eq!(tree[folder].children().count(), 2);
+    assert!(tree[folder]
+        .children()
+        .all(|child| matches!(tree[child].inner(), SignatureItem::Item(_))));
+
+    // Importing the same library again adds nothing new.
+    proof
+        .update(&Action::ImportLibrary(
+            "Arrows".to_owned(),
+            library().into(),
+        ))
+        .unwrap();
+    assert_eq!(proof.signature.iter().count(), 3);
+}
+
+#[test]
+fn export_folder_with_dependencies() {
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("Cell");
+    let y = proof.signature.create_generator_zero("Cell");
+    proof.signature.create_generator_zero("Cell");
+    proof
+        .signature
+        .create_generator(x.into(), y.into(), "Arrow", false)
+        .unwrap();
+    let root = proof.signature.as_tree().root();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::NewFolder(root)))
+        .unwrap();
+    let (folder, _) = proof.signature.folders().next().unwrap();
+    let arrow = proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| match item.inner() {
+            SignatureItem::Item(info) if info.generator.dimension == 1 => Some(node),
+            _ => None,
+        })
+        .unwrap();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::MoveInto(
+            arrow, folder,
+        )))
+        .unwrap();
+
+    // The arrow needs both of its endpoints, but not the third point.
+    let library = proof.signature.library(&[folder]);
+    let generators: Vec<_> = library.iter().map(|info| info.generator).collect();
+    assert_eq!(
+        generators,
+        vec![
+            Generator::new(0, 0),
+            Generator::new(1, 0),
+            Generator::new(3, 1)
+        ]
+    );
+    assert_eq!(library.folders().count(), 1);
+
+    let data = serialize::serialize(
+        library,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+    let mut imported = ProofState::default();
+    imported
+        .update(&Action::ImportLibrary("Arrow".to_owned(), data.into()))
+        .unwrap();
+    assert_eq!(imported.signature.iter().count(), 3);
+}
+
+#[test]
+fn import_same_named_generators() {
+    let mut library = ProofState::default();
+    let x = library.signature.create_generator_zero("Point");
+    let y = library.signature.create_generator_zero("Point");
+    library
+        .signature
+        .create_generator(x.into(), y.into(), "Arrow", false)
+        .unwrap();
+    rename(&mut library, Generator::new(0, 0), "Point");
+    rename(&mut library, Generator::new(1, 0), "Point");
+    let data = serialize::serialize(
+        library.signature,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+
+    let mut proof = ProofState::default();
+    proof.signature.create_generator_zero("Point");
+    rename(&mut proof, Generator::new(0, 0), "Point");
+    proof
+        .update(&Action::ImportLibrary("Points".to_owned(), data.into()))
+        .unwrap();
+
+    // Only one of the points is merged into the existing one, so the arrow still joins two
+    // distinct points.
+    let generators: Vec<_> = proof.signature.iter().map(|info| info.generator).collect();
+    assert_eq!(
+        generators,
+        vec![
+            Generator::new(0, 0),
+            Generator::new(1, 0),
+            Generator::new(2, 1)
+        ]
+    );
+    let arrow = proof
+        .signature
+        .iter()
+        .find(|info| info.generator.dimension == 1)
+        .unwrap();
+    assert_eq!(
+        arrow.diagram.generators(),
+        generators.iter().copied().collect()
+    );
+}
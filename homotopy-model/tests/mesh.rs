@@ -0,0 +1,147 @@
+use homotopy_core::Diagram;
+use homotopy_graphics::{
+    gltf,
+    mesh::{Mesh, MeshOptions},
+    obj, ply,
+};
+use homotopy_model::{history::Proof, proof::Action};
+use serde_json::Value;
+
+/// The associator with its 3-dimensional generator.
+fn associator() -> (Proof, Diagram) {
+    let (_safe, actions): (bool, Vec<Action>) =
+        serde_json::from_str(include_str!("examples/associator.json")).unwrap();
+    let mut proof: Proof = Default::default();
+
+    for a in &actions {
+        proof
+            .update(a)
+            .expect("Actions should replay without errors.");
+    }
+
+    let diagram = proof
+        .signature
+        .iter()
+        .map(|info| info.diagram.clone())
+        .find(|diagram| diagram.dimension() == 3)
+        .unwrap();
+
+    (proof, diagram)
+}
+
+fn options() -> MeshOptions {
+    MeshOptions {
+        subdivision_depth: 1,
+        ..Default::default()
+    }
+}
+
+/// The number of vertices and triangles in a mesh.
+fn size(mesh: &Mesh) -> (usize, usize) {
+    let verts = mesh.groups.iter().map(|group| group.positions.len()).sum();
+    let faces = mesh.groups.iter().map(|group| group.triangles.len()).sum();
+    (verts, faces)
+}
+
+#[test]
+fn export_obj() {
+    let (proof, diagram) = associator();
+    let mesh = Mesh::new(&diagram, &proof.signature, options()).unwrap();
+    let (verts, faces) = size(&mesh);
+    assert!(faces > 0);
+
+    let (obj, mtl) = obj::render(&diagram, &proof.signature, options(), "assoc.mtl").unwrap();
+    let count = |prefix: &str| obj.lines().filter(|line| line.starts_with(prefix)).count();
+    assert_eq!(count("v "), verts);
+    assert_eq!(count("vn "), verts);
+    assert_eq!(count("f "), faces);
+
+    // Indices are 1-based and refer to vertices in the file.
+    for face in obj.lines().filter_map(|line| line.strip_prefix("f ")) {
+        for corner in face.split(' ') {
+            let (v, n) = corner.split_once("//").unwrap();
+            assert_eq!(v, n);
+            assert!((1..=verts).contains(&v.parse().unwrap()));
+        }
+    }
+
+    // Every material used is defined exactly once.
+    for name in obj.lines().filter_map(|line| line.strip_prefix("usemtl ")) {
+        assert_eq!(mtl.matches(&format!("newmtl {name}\n")).count(), 1);
+    }
+}
+
+#[test]
+fn export_ply() {
+    let (proof, diagram) = associator();
+    let mesh = Mesh::new(&diagram, &proof.signature, options()).unwrap();
+    let (verts, faces) = size(&mesh);
+
+    let ply = ply::render(&diagram, &proof.signature, options()).unwrap();
+    let (header, body) = ply.split_once("end_header\n").unwrap();
+    assert!(header.starts_with("ply\nformat ascii 1.0\n"));
+    assert!(header.contains(&format!("element vertex {verts}\n")));
+    assert!(header.contains(&format!("element face {faces}\n")));
+
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), verts + faces);
+    assert!(lines[..verts]
+        .iter()
+        .all(|vertex| vertex.split(' ').count() == 9));
+    for face in &lines[verts..] {
+        let indices: Vec<usize> = face.split(' ').map(|i| i.parse().unwrap()).collect();
+        assert_eq!(indices[0], 3);
+        assert!(indices[1..].iter().all(|&i| i < verts));
+    }
+}
+
+#[test]
+fn export_glb() {
+    let (proof, diagram) = associator();
+    let mesh = Mesh::new(&diagram, &proof.signature, options()).unwrap();
+    let (verts, faces) = size(&mesh);
+
+    let glb = gltf::render(&diagram, &proof.signature, options()).unwrap();
+    let word = |i: usize| u32::from_le_bytes(glb[i..i + 4].try_into().unwrap()) as usize;
+
+    // The header, then a JSON chunk and a binary chunk, each 4-byte aligned.
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(word(4), 2);
+    assert_eq!(word(8), glb.len());
+    let json_length = word(12);
+    assert_eq!(json_length % 4, 0);
+    assert_eq!(&glb[16..20], b"JSON");
+    let bin = 20 + json_length;
+    let bin_length = word(bin);
+    assert_eq!(bin_length % 4, 0);
+    assert_eq!(&glb[bin + 4..bin + 8], b"BIN\0");
+    assert_eq!(bin + 8 + bin_length, glb.len());
+
+    let document: Value = serde_json::from_slice(&glb[20..bin]).unwrap();
+    let buffer_length = document["buffers"][0]["byteLength"].as_u64().unwrap() as usize;
+    assert!(buffer_length <= bin_length);
+    for view in document["bufferViews"].as_array().unwrap() {
+        let end = view["byteOffset"].as_u64().unwrap() + view["byteLength"].as_u64().unwrap();
+        assert!(end as usize <= buffer_length);
+    }
+
+    // One primitive for each group of the mesh, with all of its vertices and triangles.
+    let accessors = document["accessors"].as_array().unwrap();
+    let count = |accessor: &Value| {
+        accessors[accessor.as_u64().unwrap() as usize]["count"]
+            .as_u64()
+            .unwrap() as usize
+    };
+    let primitives = document["meshes"][0]["primitives"].as_array().unwrap();
+    assert_eq!(primitives.len(), mesh.groups.len());
+    let positions: usize = primitives
+        .iter()
+        .map(|primitive| count(&primitive["attributes"]["POSITION"]))
+        .sum();
+    let indices: usize = primitives
+        .iter()
+        .map(|primitive| count(&primitive["indices"]))
+        .sum();
+    assert_eq!(positions, verts);
+    assert_eq!(indices, 3 * faces);
+}
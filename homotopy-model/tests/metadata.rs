@@ -0,0 +1,107 @@
+use homotopy_graphics::style::{DashPattern, Palette, SurfaceStyle, VertexShape, WireStyle};
+use homotopy_model::{
+    proof::{Action, ProofState, SignatureEdit, SignatureItem, SignatureItemEdit},
+    serialize,
+};
+
+#[test]
+fn generator_metadata_roundtrip() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    let node = proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| matches!(item.inner(), SignatureItem::Item(_)).then_some(node))
+        .unwrap();
+
+    for edit in [
+        SignatureItemEdit::Relabel(r"\mu".to_owned()),
+        SignatureItemEdit::Describe("Multiplication".to_owned()),
+        SignatureItemEdit::AddCitation(
+            "Mac Lane, Categories for the Working Mathematician".to_owned(),
+        ),
+        SignatureItemEdit::AddTag("monoid".to_owned()),
+        SignatureItemEdit::AddTag("monoid".to_owned()),
+    ] {
+        proof
+            .update(&Action::EditSignature(SignatureEdit::Edit(node, edit)))
+            .unwrap();
+    }
+
+    let info = proof.signature.iter().next().unwrap();
+    assert_eq!(info.label.as_deref(), Some(r"\mu"));
+    assert_eq!(info.tags, vec!["monoid".to_owned()]);
+
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let ((signature, _, _), _) = serialize::deserialize(&data).unwrap();
+
+    assert_eq!(signature.iter().next(), Some(info));
+}
+
+#[test]
+fn generator_style_and_palette_roundtrip() {
+    let mut proof = ProofState::default();
+    let palette = Palette::parse("Greys", "#111111, #555555; #999999").unwrap();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::SetPalette(
+            palette.clone(),
+        )))
+        .unwrap();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    let node = proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| matches!(item.inner(), SignatureItem::Item(_)).then_some(node))
+        .unwrap();
+
+    for edit in [
+        SignatureItemEdit::Reshape(VertexShape::Star),
+        SignatureItemEdit::SetWireStyle(WireStyle {
+            dash: DashPattern::Dotted,
+            thickness: 150,
+        }),
+        SignatureItemEdit::SetSurfaceStyle(SurfaceStyle { opacity: 40 }),
+    ] {
+        proof
+            .update(&Action::EditSignature(SignatureEdit::Edit(node, edit)))
+            .unwrap();
+    }
+
+    let info = proof.signature.iter().next().unwrap();
+    assert_eq!(info.color, palette.colors[0]);
+
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let ((signature, _, _), _) = serialize::deserialize(&data).unwrap();
+
+    assert_eq!(signature.palette(), &palette);
+    assert_eq!(signature.iter().next(), Some(info));
+}
+
+#[test]
+fn palette_files() {
+    let json = Palette::parse(
+        "ignored",
+        r##"{ "name": "Pair", "colors": ["#000000", "ffffff"] }"##,
+    )
+    .unwrap();
+    assert_eq!(json.name, "Pair");
+    assert_eq!(json.colors.len(), 2);
+
+    let list = Palette::parse("List", "#000000\n#ffffff\n").unwrap();
+    assert_eq!(list.colors, json.colors);
+
+    assert!(Palette::parse("Empty", " ").is_err());
+    assert!(Palette::parse("Invalid", "#00000g").is_err());
+}
@@ -0,0 +1,72 @@
+use homotopy_common::tree::Node;
+use homotopy_core::Generator;
+use homotopy_model::proof::{
+    generators::GeneratorFilter, Action, ProofState, SignatureEdit, SignatureItem,
+    SignatureItemEdit,
+};
+
+fn generator_node(proof: &ProofState, generator: Generator) -> Node {
+    proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| match item.inner() {
+            SignatureItem::Item(info) if info.generator == generator => Some(node),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn parse_query() {
+    let filter = GeneratorFilter::parse("Unit dim:2 not:invertible is:oriented #Axiom tag:monoid");
+    assert_eq!(filter.words, vec!["unit"]);
+    assert_eq!(filter.dimension, Some(2));
+    assert_eq!(filter.invertible, Some(false));
+    assert_eq!(filter.oriented, Some(true));
+    assert_eq!(filter.tags, vec!["axiom", "monoid"]);
+
+    assert!(GeneratorFilter::parse("  ").is_empty());
+    assert_eq!(GeneratorFilter::parse("dim:x").words, vec!["dim:x"]);
+}
+
+#[test]
+fn search_keeps_folders() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    let root = proof.signature.as_tree().root();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::NewFolder(root)))
+        .unwrap();
+    let (folder, _) = proof.signature.folders().next().unwrap();
+
+    let tagged = generator_node(&proof, Generator::new(1, 0));
+    let other = generator_node(&proof, Generator::new(0, 0));
+    proof
+        .update(&Action::EditSignature(SignatureEdit::MoveInto(
+            tagged, folder,
+        )))
+        .unwrap();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::Edit(
+            tagged,
+            SignatureItemEdit::AddTag("Axiom".to_owned()),
+        )))
+        .unwrap();
+
+    let search = proof
+        .signature
+        .search(&GeneratorFilter::parse("#axiom dim:0"));
+    assert!(search.matches.contains(&tagged));
+    assert!(!search.matches.contains(&other));
+    assert!(search.visible.contains(&folder));
+    assert!(search.visible.contains(&root));
+    assert!(!search.visible.contains(&other));
+
+    // Folders match by name, and keep everything inside them.
+    let search = proof.signature.search(&GeneratorFilter::parse("new fold"));
+    assert!(search.matches.contains(&folder));
+    assert!(search.visible.contains(&tagged));
+    assert!(!search.visible.contains(&other));
+}
@@ -0,0 +1,62 @@
+use homotopy_core::Generator;
+use homotopy_model::{
+    proof::{shelf::ShelfEdit, Action, ProofState, SignatureEdit, SignatureItem},
+    serialize,
+};
+
+#[test]
+fn shelf_paste_and_prune() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(1, 0)))
+        .unwrap();
+    assert!(proof.update(&Action::CopyToShelf).unwrap());
+    proof.update(&Action::ClearWorkspace).unwrap();
+
+    assert!(proof.update(&Action::PasteFromShelf(0)).unwrap());
+    let workspace = proof.workspace.as_ref().unwrap();
+    assert_eq!(
+        workspace.diagram.max_generator().generator,
+        Generator::new(1, 0)
+    );
+    assert!(!Action::PasteFromShelf(1).is_valid(&proof));
+
+    let node = proof
+        .signature
+        .as_tree()
+        .iter()
+        .find_map(|(node, item)| match item.inner() {
+            SignatureItem::Item(info) if info.generator == Generator::new(1, 0) => Some(node),
+            _ => None,
+        })
+        .unwrap();
+    proof
+        .update(&Action::EditSignature(SignatureEdit::Remove(node)))
+        .unwrap();
+    assert!(proof.shelf.is_empty());
+}
+
+#[test]
+fn shelf_roundtrip() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(0, 0)))
+        .unwrap();
+    proof.update(&Action::CopyToShelf).unwrap();
+    proof
+        .update(&Action::EditShelf(ShelfEdit::Rename(0, "Point".to_owned())))
+        .unwrap();
+
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let ((_, _, shelf), _) = serialize::deserialize(&data).unwrap();
+
+    assert_eq!(shelf, proof.shelf);
+}
@@ -0,0 +1,58 @@
+use homotopy_core::Generator;
+use homotopy_model::{
+    proof::{Action, ProofState},
+    serialize,
+};
+
+fn visible_generator(proof: &ProofState) -> Generator {
+    let workspace = proof.workspace.as_ref().unwrap();
+    workspace.diagram.max_generator().generator
+}
+
+#[test]
+fn tabs_keep_separate_workspaces() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(0, 0)))
+        .unwrap();
+
+    assert!(proof.update(&Action::CreateWorkspace).unwrap());
+    assert_eq!(proof.active_tab(), 1);
+    assert!(proof.workspace.is_none());
+
+    proof
+        .update(&Action::SelectGenerator(Generator::new(1, 0)))
+        .unwrap();
+    assert!(proof.update(&Action::SwitchWorkspace(0)).unwrap());
+    assert_eq!(visible_generator(&proof), Generator::new(0, 0));
+
+    assert!(proof.update(&Action::CloseWorkspace(0)).unwrap());
+    assert_eq!(proof.tab_names().count(), 1);
+    assert_eq!(visible_generator(&proof), Generator::new(1, 0));
+    assert!(!Action::CloseWorkspace(0).is_valid(&proof));
+}
+
+#[test]
+fn tabs_roundtrip() {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(0, 0)))
+        .unwrap();
+    proof.update(&Action::CreateWorkspace).unwrap();
+    proof
+        .update(&Action::RenameWorkspace(1, "Lemma".to_owned()))
+        .unwrap();
+
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let ((_, tabs, _), _) = serialize::deserialize(&data).unwrap();
+
+    assert_eq!(tabs, proof.tabs());
+}
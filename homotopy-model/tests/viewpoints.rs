@@ -0,0 +1,74 @@
+use homotopy_core::{Generator, Height, SliceIndex};
+use homotopy_model::{
+    proof::{Action, ProofState},
+    serialize,
+};
+
+fn identity_square() -> ProofState {
+    let mut proof = ProofState::default();
+    proof.update(&Action::CreateGeneratorZero).unwrap();
+    proof
+        .update(&Action::SelectGenerator(Generator::new(0, 0)))
+        .unwrap();
+    proof.update(&Action::TakeIdentityDiagram).unwrap();
+    proof.update(&Action::TakeIdentityDiagram).unwrap();
+    proof
+}
+
+#[test]
+fn go_to_pinned_viewpoint() {
+    let mut proof = identity_square();
+    let slice = SliceIndex::Interior(Height::Regular(0));
+    proof.update(&Action::DescendSlice(slice)).unwrap();
+    assert!(proof
+        .update(&Action::PinViewpoint("Bottom".to_owned()))
+        .unwrap());
+    assert!(!Action::GoToViewpoint(0).is_valid(&proof));
+
+    proof.update(&Action::AscendSlice(1)).unwrap();
+    let top = proof.workspace.clone().unwrap();
+    assert!(top.path.is_empty());
+
+    assert!(proof.update(&Action::GoToViewpoint(0)).unwrap());
+    let workspace = proof.workspace.as_ref().unwrap();
+    assert_eq!(workspace.path, im::vector![slice]);
+    assert_eq!(workspace.view.dimension(), 1);
+    assert_eq!(workspace.ancestor_diagram(0), top.visible_diagram());
+
+    assert!(proof.update(&Action::UnpinViewpoint(0)).unwrap());
+    assert!(!Action::GoToViewpoint(0).is_valid(&proof));
+    assert!(!Action::UnpinViewpoint(0).is_valid(&proof));
+}
+
+#[test]
+fn move_to_missing_slice() {
+    let proof = identity_square();
+    let mut workspace = proof.workspace.unwrap();
+    let before = workspace.clone();
+    let missing = SliceIndex::Interior(Height::Regular(5));
+
+    assert!(!workspace.move_to(im::vector![missing], before.view));
+    assert_eq!(workspace, before);
+
+    workspace.path = im::vector![missing];
+    assert_eq!(workspace.try_ancestor_diagram(0), Some(before.diagram));
+    assert_eq!(workspace.try_ancestor_diagram(1), None);
+}
+
+#[test]
+fn viewpoints_roundtrip() {
+    let mut proof = identity_square();
+    proof
+        .update(&Action::PinViewpoint("Whole".to_owned()))
+        .unwrap();
+
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let ((_, tabs, _), _) = serialize::deserialize(&data).unwrap();
+
+    assert_eq!(tabs, proof.tabs());
+}
@@ -1,4 +1,7 @@
 use boundary::BoundaryPreview;
+use command_palette::CommandPalette;
+use gloo_timers::callback::Interval;
+use homotopy_graphics::style::Theme;
 use settings::{AppSettings, AppSettingsKey, AppSettingsMsg};
 use sidebar::Sidebar;
 use signature_stylesheet::SignatureStylesheet;
@@ -9,26 +12,32 @@ use yew::prelude::*;
 use self::diagram_gl::GlViewControl;
 use crate::{
     components::{
+        delta::CallbackIdx,
+        document,
         icon::{Icon, IconSize},
         modal::Modal,
         panzoom::PanZoom,
         toast::{toast, Toast, ToasterComponent},
     },
     model,
+    panic::{self, CrashReport},
 };
 
 mod account;
 mod attach;
 mod boundary;
+mod command_palette;
 #[cfg(any(debug_assertions, feature = "show_debug_panel"))]
 mod debug;
-mod diagram_gl;
+pub(crate) mod diagram_gl;
 mod diagram_svg;
 mod image_export;
 mod info;
 mod keybindings;
 mod project;
+pub(crate) mod projects;
 mod settings;
+mod shelf;
 mod sidebar;
 mod signature;
 mod signature_stylesheet;
@@ -39,13 +48,20 @@ pub enum Message {
     BlockingDispatch(model::Action),
     #[allow(dead_code)]
     Dispatch(model::Action),
+    Setting(AppSettingsMsg),
+    Recover,
+    DismissCrashReport,
 }
 
 pub struct App {
     state: model::State,
     loading: bool,
     signature_stylesheet: SignatureStylesheet,
+    theme_callback: CallbackIdx,
     before_unload: Option<Closure<dyn FnMut(web_sys::BeforeUnloadEvent)>>,
+    _autosave: Interval,
+    /// The crash of an earlier session, offered for recovery until it is dismissed.
+    crash_report: Option<CrashReport>,
 }
 
 impl Component for App {
@@ -56,18 +72,26 @@ impl Component for App {
     fn create(ctx: &Context<Self>) -> Self {
         let state = model::State::default();
         // Install the signature stylesheet
-        let signature_stylesheet = SignatureStylesheet::new();
+        let mut signature_stylesheet = SignatureStylesheet::new();
         signature_stylesheet.mount();
+        Self::apply_theme(&mut signature_stylesheet, AppSettings::get_theme());
+        let theme_callback = AppSettings::subscribe(
+            &[AppSettingsKey::theme],
+            ctx.link().callback(Message::Setting),
+        )[0];
 
         Self {
             state,
             loading: false,
             signature_stylesheet,
+            theme_callback,
             before_unload: None,
+            _autosave: Interval::new(projects::AUTOSAVE_INTERVAL, projects::flush),
+            crash_report: panic::crash_report(),
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Message::BlockingDispatch(action) | Message::Dispatch(action) => {
                 tracing::info!("Received action: {:?}", action);
@@ -85,10 +109,10 @@ impl Component for App {
 
                 // Determine if the action needs to reset the panzoom
                 // but do not reset it until we have performed the action.
-                let resets_panzoom = if let model::Action::Proof(action) = &action {
-                    self.state.proof().resets_panzoom(action)
-                } else {
-                    false
+                let resets_panzoom = match &action {
+                    model::Action::Proof(action) => self.state.proof().resets_panzoom(action),
+                    model::Action::OpenProof(_, _) => true,
+                    _ => false,
                 };
 
                 let performance = web_sys::window().unwrap().performance().unwrap();
@@ -134,6 +158,7 @@ impl Component for App {
 
                     self.signature_stylesheet
                         .update(self.state.proof().signature.clone());
+                    projects::mark_changed(&self.state.history);
                 } else if let Err(error) = result {
                     tracing::error!("Error occured: {}", error);
                     toast(Toast::error(error.to_string()));
@@ -141,19 +166,67 @@ impl Component for App {
 
                 true
             }
+            Message::Setting(AppSettingsMsg::theme(theme)) => {
+                Self::apply_theme(&mut self.signature_stylesheet, theme);
+                false
+            }
+            Message::Setting(_) => false,
+            Message::Recover => {
+                let proof = self.crash_report.as_ref().and_then(CrashReport::proof);
+                if let Some(proof) = proof {
+                    ctx.link()
+                        .send_message(Message::BlockingDispatch(model::Action::Proof(
+                            model::proof::Action::ImportProof(proof.into()),
+                        )));
+                }
+                ctx.link().send_message(Message::DismissCrashReport);
+                false
+            }
+            Message::DismissCrashReport => {
+                panic::dismiss_crash_report();
+                self.crash_report = None;
+                Self::show_modal("#");
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        Self::render(ctx, &self.state, self.loading)
+        Self::render(ctx, &self.state, self.loading, self.crash_report.as_ref())
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if first_render && self.crash_report.is_some() {
+            Self::show_modal("#recover");
+        }
     }
 
     fn destroy(&mut self, _ctx: &Context<Self>) {
+        AppSettings::unsubscribe(&[AppSettingsKey::theme], &[self.theme_callback]);
         self.signature_stylesheet.unmount();
     }
 }
 
 impl App {
+    /// Restyle the interface, which takes its colours from the `data-theme` attribute of the
+    /// document, and the diagrams in it.
+    fn apply_theme(signature_stylesheet: &mut SignatureStylesheet, theme: Theme) {
+        document()
+            .document_element()
+            .unwrap()
+            .set_attribute("data-theme", &theme.name().to_lowercase())
+            .unwrap();
+        signature_stylesheet.set_theme(theme);
+    }
+
+    fn show_modal(href: &str) {
+        if let Some(location) = document().location() {
+            if location.set_href(href).is_err() {
+                tracing::error!("Failed to navigate to {href}");
+            }
+        }
+    }
+
     fn install_unload_hook(&mut self) {
         let before_unload = Closure::wrap(Box::new(move |event: web_sys::BeforeUnloadEvent| {
             event.set_return_value("Are you sure you want to leave? Unsaved changes will be lost!");
@@ -166,19 +239,28 @@ impl App {
         self.before_unload = Some(before_unload);
     }
 
-    fn render(ctx: &Context<Self>, state: &model::State, loading: bool) -> Html {
+    fn render(
+        ctx: &Context<Self>,
+        state: &model::State,
+        loading: bool,
+        crash_report: Option<&CrashReport>,
+    ) -> Html {
         let proof = state.proof();
         let dispatch = ctx.link().callback(Message::BlockingDispatch);
 
         let workspace = html! {
             <WorkspaceView
                 workspace={proof.workspace.clone()}
+                tabs={proof.tab_names().map(ToOwned::to_owned).collect::<Vec<_>>()}
+                active_tab={proof.active_tab()}
                 signature={proof.signature.clone()}
                 metadata={proof.metadata.clone()}
                 dispatch={dispatch.clone()}
                 attach={state.attach.clone()}
                 attachment_highlight={state.attachment_highlight.clone()}
                 slice_highlight={state.slice_highlight}
+                comparison={state.comparison()}
+                history_len={state.history.depth()}
             />
         };
 
@@ -193,6 +275,18 @@ impl App {
             None => Default::default(),
         };
 
+        let recovery = crash_report.map_or_else(Default::default, |report| {
+            html! {
+                <Modal id="recover" header="Recover from crash" persistent=true>
+                    {info::get_recovery_message(
+                        report,
+                        ctx.link().callback(|_| Message::Recover),
+                        ctx.link().callback(|_| Message::DismissCrashReport),
+                    )}
+                </Modal>
+            }
+        });
+
         let spinner = if loading {
             html! { <div class="cover-spin"></div> }
         } else {
@@ -202,6 +296,7 @@ impl App {
         html! {
             <main class="app">
                 {spinner}
+                <CommandPalette proof={proof.clone()} dispatch={dispatch.clone()} />
                 <Sidebar
                     dispatch={dispatch}
                     proof={proof.clone()}
@@ -217,6 +312,7 @@ impl App {
                 <Modal id="panic" header="Unexpected crash" persistent=true>
                     {info::get_panic_message()}
                 </Modal>
+                {recovery}
                 <Modal id="about" header="About">
                     {info::get_about_message()}
                 </Modal>
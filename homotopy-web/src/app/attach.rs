@@ -1,10 +1,11 @@
-use homotopy_core::signature::Signature as S;
+use homotopy_core::{signature::Signature as S, Diagram};
 use yew::prelude::*;
+use yew_macro::function_component;
 
 use crate::{
     app::tex::TexSpan,
     model::{
-        proof::{self, AttachOption, Signature},
+        proof::{self, AttachCandidate, AttachOption, Signature},
         Action,
     },
 };
@@ -87,3 +88,78 @@ impl AttachView {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct AttachCandidatesProps {
+    pub dispatch: Callback<Action>,
+    pub candidates: Vec<AttachCandidate>,
+    /// The workspace diagram, if there is one.
+    pub diagram: Option<Diagram>,
+    pub signature: Signature,
+}
+
+#[function_component(AttachCandidatesView)]
+pub fn attach_candidates_view(props: &AttachCandidatesProps) -> Html {
+    // Finding the embeddings of every candidate is expensive, so only count the attachment
+    // options again when the workspace or the candidates change.
+    let counts = use_memo(
+        |(diagram, candidates)| {
+            diagram.as_ref().map_or_else(Vec::new, |diagram| {
+                candidates
+                    .iter()
+                    .map(|candidate| candidate.options(diagram).len())
+                    .collect::<Vec<_>>()
+            })
+        },
+        (props.diagram.clone(), props.candidates.clone()),
+    );
+
+    let candidates = props
+        .candidates
+        .iter()
+        .zip(counts.iter())
+        .filter(|(_, count)| **count > 0)
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        return html! {
+            <div class="attach__empty">
+                {"Nothing can be attached to the workspace."}
+            </div>
+        };
+    }
+
+    let candidate = |candidate: &AttachCandidate, count: usize| -> Html {
+        let Some(info) = props.signature.generator_info(candidate.generator) else {
+            return Default::default();
+        };
+
+        let onclick = props.dispatch.reform({
+            let candidate = candidate.clone();
+            move |_| Action::SelectDiagram(candidate.clone())
+        });
+
+        html! {
+            <li class="attach__option" onclick={onclick}>
+                <span
+                    class="attach__option-color"
+                    style={format!("background: {}", info.color)}
+                />
+                <TexSpan
+                    class="attach__option-name"
+                    error_color="#c004"
+                    raw_tex={
+                        format!("{}{}", info.name, candidate.tag.as_ref().map_or(Default::default(), |t| format!(" ({t})")))
+                    }
+                />
+                <span class="attach__option-count">{count}</span>
+            </li>
+        }
+    };
+
+    html! {
+        <ul class="attach__options">
+            { for candidates.into_iter().map(|(c, count)| candidate(c, *count)) }
+        </ul>
+    }
+}
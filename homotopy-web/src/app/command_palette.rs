@@ -0,0 +1,373 @@
+//! An overlay that searches everything that can currently be done: actions, workspace tabs,
+//! viewpoints, shelf items, generators, folders of the signature and exports.
+
+use std::cmp::Reverse;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    app::{
+        image_export,
+        keybindings::{Chord, Command, Keymap},
+    },
+    components::delta::{CallbackIdx, Delta, State},
+    model::{
+        self,
+        proof::{self, SignatureEdit},
+        Proof,
+    },
+};
+
+/// The number of results listed at once.
+const MAX_RESULTS: usize = 50;
+
+#[derive(Default, Clone)]
+struct Opened;
+
+impl State for Opened {
+    type Action = ();
+
+    fn update(&mut self, _action: &Self::Action) -> bool {
+        true
+    }
+}
+
+thread_local! {
+    static OPENED: Delta<Opened> = Delta::default();
+}
+
+/// Show the command palette.
+pub fn open() {
+    OPENED.with(|delta| delta.emit(&()));
+}
+
+/// Score how well `query` matches `text` as a subsequence, ignoring case and whitespace in the
+/// query. Consecutive characters and characters at the start of words count for more.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let i = from + text.get(from..)?.iter().position(|&t| t == c)?;
+        score += match last {
+            Some(last) if i == last + 1 => 8,
+            _ if i == 0 || !text[i - 1].is_alphanumeric() => 6,
+            _ => 1,
+        };
+        score -= (i - from) as i32;
+        last = Some(i);
+        from = i + 1;
+    }
+    Some(score)
+}
+
+struct Entry {
+    label: String,
+    kind: &'static str,
+    shortcut: Option<String>,
+    action: model::Action,
+}
+
+fn entries(proof: &Proof) -> Vec<Entry> {
+    let keymap = Keymap::current();
+
+    let entry = |kind, label: String, action: model::Action| Entry {
+        label,
+        kind,
+        shortcut: None,
+        action,
+    };
+
+    let commands = Command::presets().into_iter().filter_map(|command| {
+        if !matches!(
+            command,
+            Command::Help | Command::Undo | Command::Redo | Command::Proof(_)
+        ) {
+            return None;
+        }
+        Some(Entry {
+            label: command.label(),
+            kind: "Action",
+            shortcut: keymap.chord(&command).map(Chord::label),
+            action: command.action()?,
+        })
+    });
+    let tabs = proof.tab_names().enumerate().flat_map(|(i, name)| {
+        [
+            entry(
+                "Workspace",
+                format!("Switch to workspace {name}"),
+                proof::Action::SwitchWorkspace(i).into(),
+            ),
+            entry(
+                "Workspace",
+                format!("Close workspace {name}"),
+                proof::Action::CloseWorkspace(i).into(),
+            ),
+        ]
+    });
+    let viewpoints = proof
+        .workspace
+        .iter()
+        .flat_map(|ws| ws.viewpoints.iter().enumerate())
+        .flat_map(|(i, viewpoint)| {
+            [
+                entry(
+                    "Viewpoint",
+                    format!("Go to viewpoint {}", viewpoint.name),
+                    proof::Action::GoToViewpoint(i).into(),
+                ),
+                entry(
+                    "Viewpoint",
+                    format!("Unpin viewpoint {}", viewpoint.name),
+                    proof::Action::UnpinViewpoint(i).into(),
+                ),
+            ]
+        });
+    let shelf = proof.shelf.iter().enumerate().map(|(i, item)| {
+        entry(
+            "Shelf",
+            format!("Paste {} from shelf", item.name),
+            proof::Action::PasteFromShelf(i).into(),
+        )
+    });
+    let generators = proof.signature.iter().map(|info| {
+        entry(
+            "Generator",
+            info.name.clone(),
+            proof::Action::SelectGenerator(info.generator).into(),
+        )
+    });
+    let folders = proof.signature.folders().map(|(node, info)| {
+        entry(
+            "Folder",
+            format!(
+                "{} folder {}",
+                if info.open { "Close" } else { "Open" },
+                info.name
+            ),
+            proof::Action::EditSignature(SignatureEdit::ToggleFolder(node)).into(),
+        )
+    });
+    let exports = image_export::exports()
+        .into_iter()
+        .chain([
+            ("Export proof", model::Action::ExportProof),
+            ("Export actions", model::Action::ExportActions),
+        ])
+        .map(|(label, action)| entry("Export", label.to_owned(), action));
+
+    commands
+        .chain(tabs)
+        .chain(viewpoints)
+        .chain(shelf)
+        .chain(generators)
+        .chain(folders)
+        .chain(exports)
+        .filter(|entry| entry.action.is_valid(proof))
+        .collect()
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub proof: Proof,
+    pub dispatch: Callback<model::Action>,
+}
+
+pub enum Msg {
+    Open,
+    Close,
+    Query(String),
+    Select(usize),
+    Move(isize),
+    Run(usize),
+    Noop,
+}
+
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+    selected: usize,
+    focus: bool,
+    input: NodeRef,
+    callback_idx: CallbackIdx,
+}
+
+impl Component for CommandPalette {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            selected: 0,
+            focus: false,
+            input: NodeRef::default(),
+            callback_idx: OPENED.with(|delta| delta.register(ctx.link().callback(|_| Msg::Open))),
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        OPENED.with(|delta| delta.unregister(self.callback_idx));
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.focus {
+            self.focus = false;
+            if let Some(input) = self.input.cast::<HtmlInputElement>() {
+                input.focus().unwrap_or_default();
+            }
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Open => {
+                self.open = true;
+                self.query.clear();
+                self.selected = 0;
+                self.focus = true;
+                true
+            }
+            Msg::Close => {
+                self.open = false;
+                true
+            }
+            Msg::Query(query) => {
+                self.query = query;
+                self.selected = 0;
+                true
+            }
+            Msg::Select(index) => {
+                let changed = self.selected != index;
+                self.selected = index;
+                changed
+            }
+            Msg::Move(offset) => {
+                let count = self.results(ctx).len();
+                if count > 0 {
+                    self.selected =
+                        (self.selected as isize + offset).rem_euclid(count as isize) as usize;
+                }
+                true
+            }
+            Msg::Run(index) => {
+                let Some(entry) = self.results(ctx).into_iter().nth(index) else { return false };
+                self.open = false;
+                ctx.props().dispatch.emit(entry.action);
+                true
+            }
+            Msg::Noop => false,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !self.open {
+            return Default::default();
+        }
+
+        let results = self.results(ctx);
+        let selected = self.selected;
+        let on_input = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::Query(input.value())
+        });
+        let on_key_down = ctx.link().callback(move |e: KeyboardEvent| {
+            let msg = match e.key().as_str() {
+                "ArrowDown" => Msg::Move(1),
+                "ArrowUp" => Msg::Move(-1),
+                "Enter" => Msg::Run(selected),
+                "Escape" => Msg::Close,
+                _ => return Msg::Noop,
+            };
+            e.prevent_default();
+            msg
+        });
+        let items = results.iter().enumerate().map(|(i, entry)| {
+            html! {
+                <li
+                    class={classes!(
+                        "command-palette__result",
+                        (i == selected).then_some("command-palette__result--selected"),
+                    )}
+                    onclick={ctx.link().callback(move |_| Msg::Run(i))}
+                    onmousemove={ctx.link().callback(move |_| Msg::Select(i))}
+                >
+                    <span class="command-palette__label">{&entry.label}</span>
+                    if let Some(shortcut) = &entry.shortcut {
+                        <kbd class="command-palette__shortcut">{shortcut}</kbd>
+                    }
+                    <span class="command-palette__kind">{entry.kind}</span>
+                </li>
+            }
+        });
+
+        html! {
+            <div class="command-palette" onclick={ctx.link().callback(|_| Msg::Close)}>
+                <div
+                    class="command-palette__dialog"
+                    onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                >
+                    <input
+                        ref={self.input.clone()}
+                        class="command-palette__input"
+                        type="text"
+                        placeholder="Search actions, generators, folders and exports"
+                        value={self.query.clone()}
+                        oninput={on_input}
+                        onkeydown={on_key_down}
+                    />
+                    <ul class="command-palette__results">
+                        {for items}
+                        if results.is_empty() {
+                            <li class="command-palette__empty">{"No matches"}</li>
+                        }
+                    </ul>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl CommandPalette {
+    /// The entries matching the query, best first.
+    fn results(&self, ctx: &Context<Self>) -> Vec<Entry> {
+        let mut scored: Vec<_> = entries(&ctx.props().proof)
+            .into_iter()
+            .filter_map(|entry| Some((fuzzy_score(&self.query, &entry.label)?, entry)))
+            .collect();
+        // The sort is stable, so equally good matches stay grouped by kind.
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+        scored
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::fuzzy_score;
+
+    #[test]
+    fn fuzzy_matches() {
+        assert_eq!(fuzzy_score("", "Undo"), Some(0));
+        assert_eq!(fuzzy_score("ns", "Next slice"), Some(8));
+        assert_eq!(fuzzy_score("N S", "next slice"), Some(8));
+        assert_eq!(fuzzy_score("ou", "Undo"), None);
+        assert_eq!(fuzzy_score("undos", "Undo"), None);
+    }
+
+    #[test]
+    fn fuzzy_ranking() {
+        // Consecutive characters beat the starts of words, which beat anything else.
+        assert!(fuzzy_score("ne", "Next slice") > fuzzy_score("ns", "Next slice"));
+        assert!(fuzzy_score("s", "Next slice") > fuzzy_score("s", "Nest"));
+        // Skipped characters count against a match.
+        assert!(fuzzy_score("v", "Invert") > fuzzy_score("v", "Increase view"));
+    }
+}
@@ -3,6 +3,7 @@ use std::{cell::RefCell, rc::Rc};
 use gloo::render::{request_animation_frame, AnimationFrame};
 use homotopy_core::Diagram;
 use homotopy_gl::GlCtx;
+use homotopy_graphics::raster::Camera;
 use ultraviolet::Vec3;
 use yew::prelude::*;
 
@@ -45,6 +46,13 @@ impl GlViewControl {
         CAMERA.with(|c| c.emit(&TouchAction::Reset));
         SCRUB.with(|s| s.emit(&ScrubAction::Scrub(0.)));
     }
+
+    /// The camera the 3D view is currently seen through.
+    pub fn camera() -> Camera {
+        let mut camera = CAMERA.with(Delta::state);
+        camera.set_ortho(AppSettings::get_orthographic_3d());
+        camera.to_camera()
+    }
 }
 
 pub enum DiagramGlMessage {
@@ -1,6 +1,7 @@
 use std::f32::consts::PI;
 
 use homotopy_gl::GlCtx;
+use homotopy_graphics::raster::Camera;
 use ultraviolet::{
     projection::rh_yup::{orthographic_gl, perspective_gl},
     Mat4, Vec2, Vec3,
@@ -80,6 +81,18 @@ impl OrbitCamera {
         self.ortho = ortho;
     }
 
+    /// The camera in the form taken by exports, which render without a GL context.
+    pub fn to_camera(&self) -> Camera {
+        Camera {
+            phi: self.phi,
+            theta: self.theta,
+            distance: self.distance,
+            target: self.target,
+            fov: self.fov,
+            ortho: self.ortho,
+        }
+    }
+
     fn offset(&self) -> Vec3 {
         let sin_phi = f32::sin(self.phi);
         let cos_phi = f32::cos(self.phi);
@@ -3,6 +3,7 @@ use homotopy_gl::{
     frame::{DepthTest, Frame},
     GlCtx, Result,
 };
+use homotopy_graphics::style::Theme;
 use ultraviolet::{Mat4, Vec4};
 
 use self::{
@@ -31,6 +32,7 @@ pub struct Renderer {
     smooth_time: bool,
     subdivision_depth: u8,
     geometry_samples: u8,
+    theme: Theme,
     // resources
     shaders: Shaders,
     scene: Scene,
@@ -48,6 +50,7 @@ impl Renderer {
         let smooth_time = AppSettings::get_smooth_time();
         let subdivision_depth = AppSettings::get_subdivision_depth() as u8;
         let samples = AppSettings::get_geometry_samples() as u8;
+        let theme = AppSettings::get_theme();
         let signature = props.signature.clone();
 
         Ok(Self {
@@ -60,6 +63,7 @@ impl Renderer {
                 smooth_time,
                 subdivision_depth,
                 samples,
+                theme,
                 &signature,
             )?,
             shaders: Shaders::new(&ctx)?,
@@ -74,6 +78,7 @@ impl Renderer {
             smooth_time,
             subdivision_depth,
             geometry_samples: samples,
+            theme,
         })
     }
 
@@ -83,6 +88,7 @@ impl Renderer {
         let smooth_time = AppSettings::get_smooth_time();
         let subdivision_depth = AppSettings::get_subdivision_depth() as u8;
         let samples = AppSettings::get_geometry_samples() as u8;
+        let theme = AppSettings::get_theme();
         let pixel_ratio = if AppSettings::get_dpr_scale() {
             web_sys::window().unwrap().device_pixel_ratio()
         } else {
@@ -96,12 +102,14 @@ impl Renderer {
             || self.smooth_time != smooth_time
             || self.subdivision_depth != subdivision_depth
             || self.geometry_samples != samples
+            || self.theme != theme
         {
             self.animated_3d = animated_3d;
             self.cubical_subdivision = cubical_subdivision;
             self.smooth_time = smooth_time;
             self.subdivision_depth = subdivision_depth;
             self.geometry_samples = samples;
+            self.theme = theme;
             self.scene.reload_meshes(
                 &self.ctx,
                 animated_3d,
@@ -109,6 +117,7 @@ impl Renderer {
                 smooth_time,
                 subdivision_depth,
                 samples,
+                theme,
                 &self.signature,
             )?;
         }
@@ -229,8 +238,9 @@ impl Renderer {
 
         // Final pass
         {
-            // Apply lighting to scene
-            let mut frame = Frame::new(&mut self.ctx).with_clear_color(Vec4::broadcast(1.));
+            // Apply lighting to scene, on the paper colour of the theme
+            let (r, g, b) = self.theme.paper().into_components::<f32>();
+            let mut frame = Frame::new(&mut self.ctx).with_clear_color(Vec4::new(r, g, b, 1.));
             frame.draw(draw! {
                 &self.shaders.lighting_pass,
                 &self.quad.array,
@@ -5,7 +5,7 @@ use homotopy_core::{Diagram, Diagram0, Generator};
 use homotopy_gl::{array::VertexArray, vertex_array, GlCtx, Result};
 use homotopy_graphics::{
     geom::{CubicalGeometry, SimplicialGeometry, VertData},
-    style::{GeneratorStyle, SignatureStyleData, VertexShape},
+    style::{GeneratorStyle, SignatureStyleData, Theme, VertexShape},
 };
 use ultraviolet::{Vec3, Vec4};
 
@@ -22,8 +22,8 @@ pub struct Scene {
     pub cylinder_components: Vec<Component<VertexArray>>,
     pub animation_curves: Vec<AnimationCurve>,
     pub animation_singularities: Vec<Component<Vec4>>,
-    pub sphere: Option<Rc<VertexArray>>,
-    pub cube: Option<Rc<VertexArray>>,
+    /// Meshes of the solids points are drawn as, by the shape they stand for.
+    pub solids: Vec<(VertexShape, Rc<VertexArray>)>,
     pub duration: f32,
 }
 
@@ -76,6 +76,7 @@ impl Scene {
         smooth_time: bool,
         subdivision_depth: u8,
         geometry_samples: u8,
+        theme: Theme,
         signature_styles: &impl SignatureStyleData,
     ) -> Result<Self> {
         let diagram = diagram.clone();
@@ -88,8 +89,7 @@ impl Scene {
             cylinder_components: vec![],
             animation_curves: vec![],
             animation_singularities: vec![],
-            sphere: None,
-            cube: None,
+            solids: vec![],
             duration: 0.,
         };
 
@@ -100,6 +100,7 @@ impl Scene {
             smooth_time,
             subdivision_depth,
             geometry_samples,
+            theme,
             signature_styles,
         )?;
         Ok(scene)
@@ -115,6 +116,7 @@ impl Scene {
         smooth_time: bool,
         subdivision_depth: u8,
         geometry_samples: u8,
+        theme: Theme,
         signature_styles: &impl SignatureStyleData,
     ) -> Result<()> {
         self.components.clear();
@@ -122,41 +124,30 @@ impl Scene {
         self.cylinder_components.clear();
         self.animation_curves.clear();
         self.animation_singularities.clear();
-        self.sphere = None;
-        self.cube = None;
+        self.solids.clear();
 
-        let mut sphere_mesh: SimplicialGeometry = Default::default();
-        let p = sphere_mesh.mk_vert(VertData {
-            position: Vec4::zero(),
-            boundary: [false; 4],
-            generator: Generator::new(0, 0).into(),
-            k: usize::MAX,
-        });
-        sphere_mesh.mk_point(p);
-        sphere_mesh.inflate_point_3d(p, geometry_samples, &VertexShape::Circle);
-        if let Some(sphere_buffers) = buffer_tris(&sphere_mesh, ctx)?.into_iter().next() {
-            self.sphere = Some(Rc::new(vertex_array!(
-                ctx,
-                &sphere_buffers.element_buffer,
-                [&sphere_buffers.vertex_buffer, &sphere_buffers.normal_buffer]
-            )?));
-        }
-
-        let mut cube_mesh: SimplicialGeometry = Default::default();
-        let p = cube_mesh.mk_vert(VertData {
-            position: Vec4::zero(),
-            boundary: [false; 4],
-            generator: Generator::new(0, 0).into(),
-            k: usize::MAX,
-        });
-        cube_mesh.mk_point(p);
-        cube_mesh.inflate_point_3d(p, geometry_samples, &VertexShape::Square);
-        if let Some(cube_buffers) = buffer_tris(&cube_mesh, ctx)?.into_iter().next() {
-            self.cube = Some(Rc::new(vertex_array!(
-                ctx,
-                &cube_buffers.element_buffer,
-                [&cube_buffers.vertex_buffer, &cube_buffers.normal_buffer]
-            )?));
+        for shape in VertexShape::ALL {
+            let solid = shape.solid();
+            if solid != shape {
+                continue;
+            }
+            let mut mesh: SimplicialGeometry = Default::default();
+            let p = mesh.mk_vert(VertData {
+                position: Vec4::zero(),
+                boundary: [false; 4],
+                generator: Generator::new(0, 0).into(),
+                k: usize::MAX,
+            });
+            mesh.mk_point(p);
+            mesh.inflate_point_3d(p, geometry_samples, &solid);
+            if let Some(buffers) = buffer_tris(&mesh, ctx)?.into_iter().next() {
+                let array = vertex_array!(
+                    ctx,
+                    &buffers.element_buffer,
+                    [&buffers.vertex_buffer, &buffers.normal_buffer]
+                )?;
+                self.solids.push((solid, Rc::new(array)));
+            }
         }
 
         let n = self.view.dimension();
@@ -185,23 +176,25 @@ impl Scene {
             let d = self.diagram.dimension();
             let n = diagram.generator.dimension;
             let c = d.saturating_sub(n + k);
-            signature_styles
+            let color = signature_styles
                 .generator_style(diagram.generator)
                 .unwrap()
-                .color()
-                .lighten(c, diagram.orientation)
+                .color();
+            theme
+                .shade(&color, c, diagram.orientation)
                 .into_linear_f32_components()
                 .into()
         };
         let shape_of = |diagram: Diagram0| -> Option<Rc<VertexArray>> {
-            match signature_styles
+            let solid = signature_styles
                 .generator_style(diagram.generator)
                 .unwrap()
                 .shape()
-            {
-                VertexShape::Circle => self.sphere.as_ref().map(Rc::clone),
-                VertexShape::Square => self.cube.as_ref().map(Rc::clone),
-            }
+                .solid();
+            self.solids
+                .iter()
+                .find(|(shape, _)| *shape == solid)
+                .map(|(_, array)| Rc::clone(array))
         };
 
         if animated {
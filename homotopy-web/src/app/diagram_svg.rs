@@ -1,12 +1,15 @@
 use std::{
+    cell::Cell,
     convert::{From, Into, TryInto},
     f32::consts::PI,
+    rc::Rc,
 };
 
 use euclid::{
     default::{Point2D, Size2D, Transform2D, Vector2D},
     Angle,
 };
+use gloo_timers::callback::Timeout;
 use homotopy_core::{
     common::Direction,
     complex::{make_complex, Simplex},
@@ -18,28 +21,109 @@ use homotopy_core::{
     Boundary, Diagram, DiagramN, Height, Orientation, SliceIndex,
 };
 use homotopy_graphics::{
-    style::VertexShape,
+    label::{place_labels, Label, LABEL_DISTANCE},
+    path_util::simplify_graphic,
+    style::{GeneratorRepresentation, VertexShape},
     svg::{
-        generator_class_from_diagram_dim,
+        generator_class_from_diagram_dim, generator_color, polygon_points,
         render::{ActionRegion, GraphicElement},
         shape::{path_to_svg, project_2d, Point, Shape},
     },
 };
+use serde::{Deserialize, Serialize};
 use web_sys::Element;
 use yew::prelude::*;
 
 use crate::{
-    components::{read_touch_list_abs, Finger},
+    app::{tex::TexSpan, AppSettings, AppSettingsKey, AppSettingsMsg},
+    components::{
+        delta::{CallbackIdx, Delta, State},
+        read_touch_list_abs, Finger,
+    },
+    gesture::{self, Gesture, LONG_PRESS},
     model::proof::{
         homotopy::{Contract, Expand, Homotopy},
         Signature,
     },
 };
 
+// The settings the drawing depends on.
+const SETTINGS: &[AppSettingsKey] = &[AppSettingsKey::show_labels, AppSettingsKey::theme];
+
+/// A direction in which the diagram under the cursor can be dragged from the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DragDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl DragDirection {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+
+    // Screen coordinates grow downwards.
+    fn angle(self) -> Angle<f32> {
+        match self {
+            Self::Up => Angle::radians(-PI / 2.0),
+            Self::Down => Angle::radians(PI / 2.0),
+            Self::Left => Angle::radians(PI),
+            Self::Right => Angle::radians(0.0),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct CursorDrag(Option<DragDirection>);
+
+impl State for CursorDrag {
+    type Action = DragDirection;
+
+    fn update(&mut self, action: &Self::Action) -> bool {
+        // Every request is a new drag, even in the same direction as the last one.
+        self.0 = Some(*action);
+        true
+    }
+}
+
+thread_local! {
+    static CURSOR_DRAG: Delta<CursorDrag> = Delta::default();
+}
+
+/// Perform the homotopy for a drag in the given direction starting from wherever the mouse cursor
+/// rests on a diagram.
+pub fn drag_at_cursor(direction: DragDirection) {
+    CURSOR_DRAG.with(|delta| delta.emit(&direction));
+}
+
 pub struct DiagramSvg<const N: usize> {
     prepared: PreparedDiagram<N>,
+    labels: Vec<Label>,
     drag_start: Option<Point2D<f32>>,
+    /// How far the pointer has moved since the drag start.
+    travel: Vector2D<f32>,
+    /// The fingers of a two-finger drag and where they went down.
+    two_finger_start: Option<[(Finger, Point2D<f32>); 2]>,
+    /// Whether the current two-finger drag was taken as a homotopy, so it should not also pan.
+    claimed: Rc<Cell<bool>>,
+    long_press: Option<Timeout>,
+    /// A contraction by a two-finger drag and where it started, waiting for a choice of bias.
+    pending_contract: Option<(Point2D<f32>, Contract)>,
+    /// The firmest pressure of the stylus during the current press, unless it is not a stylus.
+    pen_pressure: Option<f32>,
+    cursor: Option<Point2D<f32>>,
     title: String,
+    setting_callbacks: Vec<CallbackIdx>,
+    cursor_drag_callback: CallbackIdx,
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -55,18 +139,29 @@ pub struct DiagramSvgProps<const N: usize> {
     pub on_homotopy: Callback<Homotopy>,
     #[prop_or_default]
     pub highlight: Option<HighlightSvg<N>>,
+    /// Where the diagram differs from another it is compared with.
+    #[prop_or_default]
+    pub differences: Vec<HighlightSvg<N>>,
     #[prop_or_default]
     pub max_width: Option<f32>,
     #[prop_or_default]
     pub max_height: Option<f32>,
     #[prop_or_default]
     pub diagram_ref: NodeRef,
+    /// Whether to draw generator labels, if enabled in the settings.
+    #[prop_or_default]
+    pub labelled: bool,
+    /// Whether the diagram is edited by touch, so that a long press selects instead of opening
+    /// the context menu.
+    #[prop_or_default]
+    pub interactive: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum HighlightKind {
     Attach,
     Slice,
+    Difference,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -84,8 +179,15 @@ pub enum DiagramSvgMessage {
     OnMouseDown(Point2D<f32>),
     OnMouseMove(Point2D<f32>, bool),
     OnMouseUp,
+    OnMouseLeave,
     OnTouchUpdate(Vec<(Finger, Point2D<f32>)>),
     OnTouchMove(Vec<(Finger, Point2D<f32>)>),
+    OnPenDown(Option<f32>),
+    OnPenMove(f32),
+    LongPress,
+    ChooseBias(Bias),
+    Setting(AppSettingsMsg),
+    CursorDrag(Option<DragDirection>),
 }
 
 /// The computed properties of a diagram that are potentially expensive to compute but can be
@@ -176,18 +278,43 @@ impl<const N: usize> PreparedDiagram<N> {
     }
 }
 
+impl<const N: usize> PreparedDiagram<N> {
+    fn labels(&self, signature: &Signature) -> Vec<Label> {
+        place_labels(&simplify_graphic(&self.graphic), signature, LABEL_DISTANCE)
+    }
+}
+
 impl<const N: usize> Component for DiagramSvg<N> {
     type Message = DiagramSvgMessage;
     type Properties = DiagramSvgProps<N>;
 
     fn create(ctx: &Context<Self>) -> Self {
         let prepared = PreparedDiagram::new(&ctx.props().diagram, ctx.props().style);
+        let labels = prepared.labels(&ctx.props().signature);
         let drag_start = Default::default();
         let title = String::new();
+        let setting_callbacks =
+            AppSettings::subscribe(SETTINGS, ctx.link().callback(DiagramSvgMessage::Setting));
+        let cursor_drag_callback = CURSOR_DRAG.with(|delta| {
+            delta.register(
+                ctx.link()
+                    .callback(|drag: CursorDrag| DiagramSvgMessage::CursorDrag(drag.0)),
+            )
+        });
         Self {
             prepared,
+            labels,
             drag_start,
+            travel: Vector2D::zero(),
+            two_finger_start: None,
+            claimed: Rc::default(),
+            long_press: None,
+            pending_contract: None,
+            pen_pressure: None,
+            cursor: None,
             title,
+            setting_callbacks,
+            cursor_drag_callback,
         }
     }
 
@@ -195,9 +322,12 @@ impl<const N: usize> Component for DiagramSvg<N> {
         match msg {
             DiagramSvgMessage::OnMouseDown(point) => {
                 self.drag_start = Some(point);
-                false
+                self.travel = Vector2D::zero();
+                // Pressing anywhere else dismisses the choice of bias.
+                self.pending_contract.take().is_some()
             }
             DiagramSvgMessage::OnMouseMove(point, shift_key) => {
+                self.cursor = Some(point);
                 self.pointer_move(ctx, point, shift_key);
                 self.title = {
                     let point = self.transform_screen_to_image(ctx).transform_point(point);
@@ -228,27 +358,104 @@ impl<const N: usize> Component for DiagramSvg<N> {
                 self.pointer_stop(ctx);
                 false
             }
+            DiagramSvgMessage::OnMouseLeave => {
+                self.cursor = None;
+                false
+            }
             DiagramSvgMessage::OnTouchUpdate(touches) => {
-                if self.drag_start.is_none() && touches.len() == 1 {
-                    self.drag_start = Some(touches[0].1);
-                } else if touches.is_empty() {
-                    self.drag_start = None;
+                self.long_press = None;
+                match touches.as_slice() {
+                    [(_, point)]
+                        if self.drag_start.is_none() && self.two_finger_start.is_none() =>
+                    {
+                        self.drag_start = Some(*point);
+                        self.travel = Vector2D::zero();
+                        let link = ctx.link().clone();
+                        self.long_press = Some(Timeout::new(LONG_PRESS, move || {
+                            link.send_message(DiagramSvgMessage::LongPress);
+                        }));
+                        self.pending_contract.take().is_some()
+                    }
+                    [first, second] => {
+                        self.drag_start = None;
+                        self.two_finger_start = Some([*first, *second]);
+                        false
+                    }
+                    [] => {
+                        self.drag_start = None;
+                        self.two_finger_start = None;
+                        self.claimed.set(false);
+                        false
+                    }
+                    _ => {
+                        self.drag_start = None;
+                        false
+                    }
                 }
+            }
+            DiagramSvgMessage::OnTouchMove(touches) => match touches.as_slice() {
+                [(_, point)] => {
+                    self.pointer_move(ctx, *point, false);
+                    false
+                }
+                [first, second] => self.two_finger_move(ctx, [*first, *second]),
+                _ => false,
+            },
+            DiagramSvgMessage::OnPenDown(pressure) => {
+                self.pen_pressure = pressure;
                 false
             }
-            DiagramSvgMessage::OnTouchMove(touches) => {
-                if touches.len() == 1 {
-                    self.pointer_move(ctx, touches[0].1, false);
+            DiagramSvgMessage::OnPenMove(pressure) => {
+                if let Some(firmest) = &mut self.pen_pressure {
+                    *firmest = firmest.max(pressure);
+                }
+                false
+            }
+            DiagramSvgMessage::LongPress => {
+                self.long_press = None;
+                let distance = ctx.props().style.scale * 0.5;
+                if let Some(point) = self.drag_start {
+                    if gesture::press(self.travel, LONG_PRESS, distance) == Gesture::Select {
+                        self.drag_start = None;
+                        self.select_at(ctx, point);
+                    }
+                }
+                false
+            }
+            DiagramSvgMessage::ChooseBias(bias) => {
+                if let Some((_, mut contract)) = self.pending_contract.take() {
+                    contract.bias = Some(bias);
+                    ctx.props().on_homotopy.emit(Homotopy::Contract(contract));
+                }
+                true
+            }
+            DiagramSvgMessage::Setting(msg) => match msg {
+                AppSettingsMsg::show_labels(_) => ctx.props().labelled,
+                AppSettingsMsg::theme(_) => true,
+                _ => false,
+            },
+            DiagramSvgMessage::CursorDrag(direction) => {
+                if let (Some(direction), Some(point)) = (direction, self.cursor) {
+                    self.drag_from(ctx, point, direction.angle(), false);
                 }
                 false
             }
         }
     }
 
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        AppSettings::unsubscribe(SETTINGS, &self.setting_callbacks);
+        CURSOR_DRAG.with(|delta| delta.unregister(self.cursor_drag_callback));
+    }
+
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
         if old_props.diagram != ctx.props().diagram || old_props.style != ctx.props().style {
             // re-layout
             self.prepared = PreparedDiagram::new(&ctx.props().diagram, ctx.props().style);
+            self.labels = self.prepared.labels(&ctx.props().signature);
+            self.pending_contract = None;
+        } else if old_props.signature != ctx.props().signature {
+            self.labels = self.prepared.labels(&ctx.props().signature);
         }
         true
     }
@@ -295,9 +502,17 @@ impl<const N: usize> Component for DiagramSvg<N> {
             })
         };
 
+        let on_mouse_leave = ctx
+            .link()
+            .callback(|_: MouseEvent| DiagramSvgMessage::OnMouseLeave);
+
         let on_touch_move = {
             let link = ctx.link().clone();
+            let claimed = Rc::clone(&self.claimed);
             Callback::from(move |e: TouchEvent| {
+                if claimed.get() {
+                    e.stop_propagation();
+                }
                 let touches = read_touch_list_abs(&e.touches())
                     .map(|(finger, point)| (finger, point.cast()))
                     .collect();
@@ -315,6 +530,25 @@ impl<const N: usize> Component for DiagramSvg<N> {
             })
         };
 
+        let on_pointer_down = ctx.link().callback(|e: PointerEvent| {
+            DiagramSvgMessage::OnPenDown((e.pointer_type() == "pen").then(|| e.pressure()))
+        });
+
+        let on_pointer_move = {
+            let link = ctx.link().clone();
+            Callback::from(move |e: PointerEvent| {
+                if e.pointer_type() == "pen" && e.buttons() != 0 {
+                    link.send_message(DiagramSvgMessage::OnPenMove(e.pressure()));
+                }
+            })
+        };
+
+        // A long press selects instead.
+        let on_context_menu = ctx
+            .props()
+            .interactive
+            .then(|| Callback::from(|e: MouseEvent| e.prevent_default()));
+
         // TODO: Do not redraw diagram when highlight changes!
         // TODO: Do not redraw diagram for drags.
 
@@ -329,15 +563,21 @@ impl<const N: usize> Component for DiagramSvg<N> {
                 onmousedown={on_mouse_down}
                 onmouseup={on_mouse_up}
                 onmousemove={on_mouse_move}
+                onmouseleave={on_mouse_leave}
                 ontouchmove={on_touch_move}
                 ontouchstart={on_touch_update.clone()}
                 ontouchend={on_touch_update.clone()}
                 ontouchcancel={on_touch_update.clone()}
+                onpointerdown={on_pointer_down}
+                onpointermove={on_pointer_move}
+                oncontextmenu={on_context_menu}
                 ref={ctx.props().diagram_ref.clone()}
             >
                 <title>{&self.title}</title>
                 {self.prepared.graphic.iter().enumerate().map(|(i, e)| self.view_element(ctx, i, e)).collect::<Html>()}
-                {self.view_highlight(ctx)}
+                {self.view_labels(ctx)}
+                {self.view_highlights(ctx)}
+                {self.view_bias_choice(ctx)}
             </svg>
         }
     }
@@ -372,22 +612,40 @@ impl<const N: usize> DiagramSvg<N> {
             element.clone().into(),
         );
 
+        let info = ctx.props().signature.generator_info(element.generator());
+
         match element {
             GraphicElement::Surface(_, path) => {
                 let path = path_to_svg(&path.clone().transformed(&self.prepared.transform));
+                let surface = info.map(|info| info.surface).unwrap_or_default();
+                // Translucent surfaces lose the outline that hides the seams between surfaces,
+                // as it would show over the fill.
+                let style = (!surface.is_opaque())
+                    .then(|| format!("stroke: none; fill-opacity: {}", surface.alpha()));
                 html! {
-                    <path d={path} class={class} />
+                    <path d={path} class={class} style={style} />
                 }
             }
             GraphicElement::Wire(_, _, path, mask) => {
                 let path = path_to_svg(&path.clone().transformed(&self.prepared.transform));
+                let wire = info.map(|info| info.wire).unwrap_or_default();
+                let width = ctx.props().style.wire_thickness * wire.thickness_scale();
+                let dashes = wire.dash_array(width);
+                let (dash_array, line_cap) = if dashes.is_empty() {
+                    (None, None)
+                } else {
+                    let dashes: Vec<_> = dashes.iter().map(ToString::to_string).collect();
+                    (Some(dashes.join(" ")), Some("round"))
+                };
 
                 if mask.is_empty() {
                     html! {
                         <path
                             d={path}
                             class={class}
-                            stroke-width={ctx.props().style.wire_thickness.to_string()}
+                            stroke-width={width.to_string()}
+                            stroke-dasharray={dash_array}
+                            stroke-linecap={line_cap}
                         />
                     }
                 } else {
@@ -419,7 +677,9 @@ impl<const N: usize> DiagramSvg<N> {
                             <path
                                 d={path}
                                 class={class}
-                                stroke-width={ctx.props().style.wire_thickness.to_string()}
+                                stroke-width={width.to_string()}
+                                stroke-dasharray={dash_array}
+                                stroke-linecap={line_cap}
                                 mask={format!("url(#{mask_id})")}
                             />
                         </>
@@ -427,50 +687,98 @@ impl<const N: usize> DiagramSvg<N> {
                 }
             }
             GraphicElement::Point(d, point) => {
-                use VertexShape::{Circle, Square};
                 let point = self.prepared.transform.transform_point(*point);
                 let radius = ctx.props().style.point_radius;
-                let shape = if let Some(info) = ctx.props().signature.generator_info(d.generator) {
-                    info.shape.clone()
+                let shape = info.map(|info| info.shape.clone()).unwrap_or_default();
+                // The stylesheet removes the stroke of points, so hollow shapes are coloured
+                // without it.
+                let (class, style) = if shape.is_hollow() {
+                    let theme = AppSettings::get_theme();
+                    let color = generator_color(
+                        &ctx.props().signature,
+                        *d,
+                        ctx.props().diagram.dimension(),
+                        GeneratorRepresentation::Point,
+                        theme,
+                    );
+                    let style = format!(
+                        "fill: {}; stroke: {}; stroke-width: {}",
+                        theme.paper().hex(),
+                        color.hex(),
+                        VertexShape::OUTLINE * radius
+                    );
+                    (None, Some(style))
                 } else {
-                    Default::default()
+                    (Some(class), None)
                 };
-                match shape {
-                    Circle => html! {
+                match shape.polygon() {
+                    None => html! {
                         <circle
                             r={radius.to_string()}
                             cx={point.x.to_string()}
                             cy={point.y.to_string()}
-                            class={class} />
+                            class={class}
+                            style={style} />
                     },
-                    Square => html! {
-                        <rect
-                            x={(point.x - radius).to_string()}
-                            y={(point.y - radius).to_string()}
-                            width={(radius * 2.0).to_string()}
-                            height={(radius * 2.0).to_string()}
-                            class={class} />
+                    Some(corners) => html! {
+                        <polygon
+                            points={polygon_points(point, radius, &corners)}
+                            class={class}
+                            style={style} />
                     },
                 }
             }
         }
     }
 
-    fn view_highlight(&self, ctx: &Context<Self>) -> Html {
-        let Some(highlight) = ctx.props().highlight else {
-            return Default::default();
-        };
+    fn view_labels(&self, ctx: &Context<Self>) -> Html {
+        const WIDTH: f32 = 80.0;
+        const HEIGHT: f32 = 30.0;
 
-        let (Some(from), Some(to)) = (self.position(highlight.from), self.position(highlight.to)) else {
+        if !ctx.props().labelled || !AppSettings::get_show_labels() {
             return Default::default();
-        };
+        }
+
+        self.labels
+            .iter()
+            .map(|label| {
+                let position = self.prepared.transform.transform_point(label.position);
+                html! {
+                    <foreignObject
+                        class="diagram-svg__label"
+                        x={(position.x - WIDTH / 2.0).to_string()}
+                        y={(position.y - HEIGHT / 2.0).to_string()}
+                        width={WIDTH.to_string()}
+                        height={HEIGHT.to_string()}
+                    >
+                        <TexSpan class="diagram-svg__label-text" raw_tex={format!("${}$", label.text)} />
+                    </foreignObject>
+                }
+            })
+            .collect()
+    }
+
+    fn view_highlights(&self, ctx: &Context<Self>) -> Html {
+        ctx.props()
+            .differences
+            .iter()
+            .chain(&ctx.props().highlight)
+            .map(|highlight| self.view_highlight(ctx, highlight))
+            .collect()
+    }
+
+    fn view_highlight(&self, ctx: &Context<Self>, highlight: &HighlightSvg<N>) -> Html {
+        let (Some(from), Some(to)) = (self.position(highlight.from), self.position(highlight.to))
+        else { return Default::default() };
 
         let padding = match highlight.kind {
             HighlightKind::Attach => {
                 let padding = ctx.props().style.scale * 0.25;
                 Vector2D::new(padding, padding)
             }
-            HighlightKind::Slice => Vector2D::new(0.0, ctx.props().style.scale * 0.5),
+            HighlightKind::Slice | HighlightKind::Difference => {
+                Vector2D::new(0.0, ctx.props().style.scale * 0.5)
+            }
         };
 
         let from = from + padding;
@@ -491,6 +799,7 @@ impl<const N: usize> DiagramSvg<N> {
         let class = match highlight.kind {
             HighlightKind::Attach => "diagram-svg__attach-highlight",
             HighlightKind::Slice => "diagram-svg__slice-highlight",
+            HighlightKind::Difference => "diagram-svg__difference-highlight",
         };
 
         html! {
@@ -498,6 +807,48 @@ impl<const N: usize> DiagramSvg<N> {
         }
     }
 
+    /// Offers the biases for a contraction by a two-finger drag, which unlike a mouse drag has
+    /// no modifier key or angle to tell them apart.
+    fn view_bias_choice(&self, ctx: &Context<Self>) -> Html {
+        const WIDTH: f32 = 64.0;
+        const HEIGHT: f32 = 32.0;
+
+        let Some((point, _)) = &self.pending_contract else { return Default::default() };
+        let point = self.transform_screen_to_image(ctx).transform_point(*point);
+
+        let buttons: Html = [
+            (Bias::Lower, "Lower", 0.0),
+            (Bias::Same, "Same", WIDTH),
+            (Bias::Higher, "Higher", 2.0 * WIDTH),
+        ]
+        .into_iter()
+        .map(|(bias, label, x)| {
+            html! {
+                <g
+                    class="diagram-svg__bias-button"
+                    transform={format!("translate({x} 0)")}
+                    onclick={ctx.link().callback(move |_: MouseEvent| DiagramSvgMessage::ChooseBias(bias))}
+                >
+                    <rect width={WIDTH.to_string()} height={HEIGHT.to_string()} rx="4" />
+                    <text x={(WIDTH / 2.0).to_string()} y={(HEIGHT / 2.0).to_string()}>{label}</text>
+                </g>
+            }
+        })
+        .collect();
+
+        // Presses on the buttons must not start a drag or a pan of the diagram underneath.
+        html! {
+            <g
+                class="diagram-svg__bias"
+                transform={format!("translate({} {})", point.x - 1.5 * WIDTH, point.y - 1.5 * HEIGHT)}
+                onmousedown={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                ontouchstart={Callback::from(|e: TouchEvent| e.stop_propagation())}
+            >
+                {buttons}
+            </g>
+        }
+    }
+
     fn position(&self, point: [SliceIndex; N]) -> Option<Point2D<f32>> {
         let point = project_2d(*self.prepared.layout.get(&point)?).into();
         Some(self.prepared.transform.transform_point(point))
@@ -514,34 +865,92 @@ impl<const N: usize> DiagramSvg<N> {
 
     fn pointer_move(&mut self, ctx: &Context<Self>, point: Point2D<f32>, shift_key: bool) {
         if let Some(start) = self.drag_start {
-            let diff: Vector2D<f32> = point - start;
+            self.travel = point - start;
             let distance = ctx.props().style.scale * 0.5;
 
-            if diff.square_length() < distance * distance {
-                return;
-            }
+            // Long presses are timed separately, so moving only tells whether a drag started.
+            let Gesture::Drag(travel) = gesture::press(self.travel, 0, distance) else { return };
 
-            let angle = diff.angle_from_x_axis();
             self.drag_start = None;
+            self.drag_from(ctx, start, travel.angle_from_x_axis(), shift_key);
+        }
+    }
 
-            let Some(simplex) = self.simplex_at(ctx,start) else {
-                return;
-            };
+    fn drag_from(
+        &self,
+        ctx: &Context<Self>,
+        start: Point2D<f32>,
+        angle: Angle<f32>,
+        force_same: bool,
+    ) {
+        let Some(simplex) = self.simplex_at(ctx, start) else { return };
+
+        let homotopy = drag_to_homotopy(
+            angle,
+            &simplex,
+            ctx.props().diagram.clone(),
+            &self.prepared.depths,
+            force_same,
+        );
+
+        if let Some(homotopy) = homotopy {
+            tracing::info!("Homotopy: {:?}", homotopy);
+            ctx.props().on_homotopy.emit(homotopy);
+        } else {
+            tracing::info!("No homotopy");
+        }
+    }
 
-            let homotopy = drag_to_homotopy(
-                angle,
-                &simplex,
-                ctx.props().diagram.clone(),
-                &self.prepared.depths,
-                shift_key,
-            );
+    /// Two fingers moving the same way perform the homotopy for a drag from between them, while
+    /// fingers moving apart or together are left to zoom the diagram.
+    fn two_finger_move(
+        &mut self,
+        ctx: &Context<Self>,
+        touches: [(Finger, Point2D<f32>); 2],
+    ) -> bool {
+        let Some(start) = self.two_finger_start else { return false };
+
+        let travel = |(finger, from): (Finger, Point2D<f32>)| {
+            touches
+                .iter()
+                .find(|(touch, _)| *touch == finger)
+                .map(|(_, to)| *to - from)
+        };
+        let (Some(first), Some(second)) = (travel(start[0]), travel(start[1])) else { return false };
+
+        let distance = ctx.props().style.scale * 0.5;
+        let kind = gesture::two_finger([first, second], distance);
+        if kind == Gesture::Undecided {
+            return false;
+        }
 
-            if let Some(homotopy) = homotopy {
+        // Only one homotopy per gesture.
+        self.two_finger_start = None;
+        let Gesture::Drag(travel) = kind else { return false };
+
+        let point = start[0].1.lerp(start[1].1, 0.5);
+        let Some(simplex) = self.simplex_at(ctx, point) else { return false };
+        let homotopy = drag_to_homotopy(
+            travel.angle_from_x_axis(),
+            &simplex,
+            ctx.props().diagram.clone(),
+            &self.prepared.depths,
+            false,
+        );
+
+        match homotopy {
+            Some(Homotopy::Contract(contract)) if N == 2 => {
+                self.claimed.set(true);
+                self.pending_contract = Some((point, contract));
+                true
+            }
+            Some(homotopy) => {
+                self.claimed.set(true);
                 tracing::info!("Homotopy: {:?}", homotopy);
                 ctx.props().on_homotopy.emit(homotopy);
-            } else {
-                tracing::info!("No homotopy");
+                false
             }
+            None => false,
         }
     }
 
@@ -549,15 +958,20 @@ impl<const N: usize> DiagramSvg<N> {
         // If the mouse button is released without having travelled a distance great enough
         // to indicate a drag, it should be interpreted as a click.  This is preferrable to
         // a separate onclick handler since drags aren't interpreted as clicks anymore.
-        if let Some(point) = self.drag_start {
-            self.drag_start = None;
-            if let Some(simplex) = self.simplex_at(ctx, point) {
-                ctx.props()
-                    .on_select
-                    .emit(simplex.into_iter().next().unwrap().to_vec());
+        if let Some(point) = self.drag_start.take() {
+            if gesture::tap(self.pen_pressure) == Gesture::Select {
+                self.select_at(ctx, point);
             }
         }
     }
+
+    fn select_at(&self, ctx: &Context<Self>, point: Point2D<f32>) {
+        if let Some(simplex) = self.simplex_at(ctx, point) {
+            ctx.props()
+                .on_select
+                .emit(simplex.into_iter().next().unwrap().to_vec());
+        }
+    }
 }
 
 fn drag_to_homotopy<const N: usize>(
@@ -580,9 +994,7 @@ fn drag_to_homotopy<const N: usize>(
 
     match N {
         1 => {
-            let Interior(Singular(height)) = point[0] else {
-                return None;
-            };
+            let Interior(Singular(height)) = point[0] else { return None };
 
             let direction = if angle.radians <= 0.0 {
                 Direction::Forward
@@ -1,19 +1,114 @@
+use homotopy_graphics::style::Theme;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 use crate::{components::delta::CallbackIdx, declare_settings, model};
 
 declare_settings! {
     pub struct ImageExportSettings {
+        theme: Theme = Theme::Light,
         tikz_leftright_mode: bool = false,
         tikz_show_braidings: bool = true,
+        tikz_show_labels: bool = true,
+        svg_show_labels: bool = false,
+        svg_slice_duration: u32 = 1000,
+        strip_depth: u32 = 1,
+        strip_singular_slices: bool = true,
         manim_use_opengl: bool = false,
+        manim_show_labels: bool = true,
+        mesh_subdivision_depth: u32 = 3,
+        mesh_samples: u32 = 3,
     }
 }
 
+/// Every export, configured from the current export settings. Whether an export applies to the
+/// diagram in the workspace is up to [model::Action::is_valid].
+pub fn exports() -> Vec<(&'static str, model::Action)> {
+    use model::Action::{
+        ExportGltf, ExportManim, ExportManimHistory, ExportManimSlices, ExportObj, ExportPly,
+        ExportStl, ExportStripSvg, ExportStripTikz, ExportSvg, ExportSvgSlices, ExportTikz,
+        ExportTikzProjected, ExportTikzSlices,
+    };
+
+    let theme = ImageExportSettings::get_theme();
+    let leftright_mode = ImageExportSettings::get_tikz_leftright_mode();
+    let show_braidings = ImageExportSettings::get_tikz_show_braidings();
+    let tikz_labels = ImageExportSettings::get_tikz_show_labels();
+    let svg_labels = ImageExportSettings::get_svg_show_labels();
+    let slice_duration = ImageExportSettings::get_svg_slice_duration();
+    let strip_depth = ImageExportSettings::get_strip_depth().max(1) as u8;
+    let singular = ImageExportSettings::get_strip_singular_slices();
+    let use_opengl = ImageExportSettings::get_manim_use_opengl();
+    let manim_labels = ImageExportSettings::get_manim_show_labels();
+    let subdivision_depth = ImageExportSettings::get_mesh_subdivision_depth() as u8;
+    let samples = ImageExportSettings::get_mesh_samples() as u8;
+
+    vec![
+        (
+            "Export to TikZ",
+            ExportTikz(leftright_mode, show_braidings, tikz_labels, theme),
+        ),
+        (
+            "Export slices to TikZ",
+            ExportTikzSlices(leftright_mode, show_braidings, tikz_labels, theme),
+        ),
+        (
+            "Export projection to TikZ",
+            ExportTikzProjected(subdivision_depth, samples, theme),
+        ),
+        ("Export to SVG", ExportSvg(svg_labels, theme)),
+        (
+            "Animate slices in SVG",
+            ExportSvgSlices(slice_duration, svg_labels, theme),
+        ),
+        (
+            "Export slice strip to SVG",
+            ExportStripSvg(strip_depth, singular, svg_labels, theme),
+        ),
+        (
+            "Export slice strip to TikZ",
+            ExportStripTikz(
+                strip_depth,
+                singular,
+                leftright_mode,
+                show_braidings,
+                tikz_labels,
+                theme,
+            ),
+        ),
+        (
+            "Export to Manim",
+            ExportManim(use_opengl, manim_labels, theme),
+        ),
+        (
+            "Animate slices in Manim",
+            ExportManimSlices(use_opengl, manim_labels, theme),
+        ),
+        (
+            "Animate history in Manim",
+            ExportManimHistory(use_opengl, manim_labels, theme),
+        ),
+        ("Export to STL", ExportStl(subdivision_depth, samples)),
+        (
+            "Export to OBJ",
+            ExportObj(subdivision_depth, samples, theme),
+        ),
+        (
+            "Export to glTF",
+            ExportGltf(subdivision_depth, samples, theme),
+        ),
+        (
+            "Export to PLY",
+            ExportPly(subdivision_depth, samples, theme),
+        ),
+    ]
+}
+
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct Props {
     pub dispatch: Callback<model::Action>,
     pub view_dim: u8,
+    pub visible_dim: usize,
 }
 
 pub struct ImageExportView {
@@ -54,17 +149,21 @@ impl Component for ImageExportView {
                 </p>
             }
         };
+        let theme = Self::view_theme();
         let tikz = Self::view_tikz(ctx);
         let svg = Self::view_svg(ctx);
+        let strip = Self::view_strip(ctx);
         let manim = Self::view_manim(ctx);
-        let stl = Self::view_stl(ctx);
+        let mesh = Self::view_mesh(ctx);
         html! {
             <div class="settings">
                 {default_text}
+                {theme}
                 {tikz}
                 {svg}
+                {strip}
                 {manim}
-                {stl}
+                {mesh}
             </div>
 
         }
@@ -75,64 +174,227 @@ impl ImageExportView {
     fn view_tikz(ctx: &Context<Self>) -> Html {
         let show_braidings = ImageExportSettings::get_tikz_show_braidings();
         let leftright_mode = ImageExportSettings::get_tikz_leftright_mode();
-        if ctx.props().view_dim == 2 {
-            html! {
+        let show_labels = ImageExportSettings::get_tikz_show_labels();
+        let theme = ImageExportSettings::get_theme();
+        let options = html! {
+            <>
+                {
+                    Self::view_checkbox(
+                        "Left-right mode",
+                        ImageExportSettings::get_tikz_leftright_mode(),
+                        ImageExportSettings::set_tikz_leftright_mode,
+                    )
+                }
+                {
+                    Self::view_checkbox(
+                        "Show braidings",
+                        ImageExportSettings::get_tikz_show_braidings(),
+                        ImageExportSettings::set_tikz_show_braidings,
+                    )
+                }
+                {
+                    Self::view_checkbox(
+                        "Show labels",
+                        ImageExportSettings::get_tikz_show_labels(),
+                        ImageExportSettings::set_tikz_show_labels,
+                    )
+                }
+            </>
+        };
+        match ctx.props().view_dim {
+            2 => html! {
                 <>
                     <h3>{"Export to TikZ"}</h3>
                     <div class="settings__segment">
-                        {
-                            Self::view_checkbox(
-                                "Left-right mode",
-                                ImageExportSettings::get_tikz_leftright_mode(),
-                                ImageExportSettings::set_tikz_leftright_mode,
-                            )
-                        }
-                        {
-                            Self::view_checkbox(
-                                "Show braidings",
-                                ImageExportSettings::get_tikz_show_braidings(),
-                                ImageExportSettings::set_tikz_show_braidings,
-                            )
-                        }
-                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportTikz(leftright_mode,show_braidings))}>{"Export"}</button>
+                        {options}
+                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportTikz(leftright_mode,show_braidings,show_labels,theme))}>{"Export"}</button>
                     </div>
                 </>
+            },
+            3 => {
+                // The projection is meshed with the settings of the mesh export below.
+                let subdivision_depth = ImageExportSettings::get_mesh_subdivision_depth() as u8;
+                let samples = ImageExportSettings::get_mesh_samples() as u8;
+                html! {
+                    <>
+                        <h3>{"Export to TikZ"}</h3>
+                        <div class="settings__segment">
+                            {options}
+                            <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportTikzSlices(leftright_mode,show_braidings,show_labels,theme))}>{"Export slices"}</button>
+                            <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportTikzProjected(subdivision_depth, samples, theme))}>{"Export projection"}</button>
+                        </div>
+                    </>
+                }
             }
-        } else {
-            Default::default()
+            _ => Default::default(),
         }
     }
 
     fn view_svg(ctx: &Context<Self>) -> Html {
-        if ctx.props().view_dim == 2 {
-            html! {
-                <>
-                    <h3>{"Export to SVG"}</h3>
-                    <div class="settings__segment">
-                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportSvg)}>{"Export"}</button>
-                    </div>
-                </>
-            }
-        } else {
-            Default::default()
+        let show_labels = ImageExportSettings::get_svg_show_labels();
+        let slice_duration = ImageExportSettings::get_svg_slice_duration();
+        let theme = ImageExportSettings::get_theme();
+        let dispatch = &ctx.props().dispatch;
+        let export = html! {
+            <button onclick={dispatch.reform(move |_| model::Action::ExportSvg(show_labels, theme))}>{"Export"}</button>
+        };
+        let slices = html! {
+            <>
+                {
+                    Self::view_slider(
+                        "Slice duration (ms)",
+                        slice_duration,
+                        ImageExportSettings::set_svg_slice_duration,
+                        100,
+                        5000,
+                    )
+                }
+                <button onclick={dispatch.reform(move |_| model::Action::ExportSvgSlices(slice_duration, show_labels, theme))}>{"Animate slices"}</button>
+            </>
+        };
+        let buttons = match (ctx.props().view_dim, ctx.props().visible_dim) {
+            (2, 3) => html! { <>{export}{slices}</> },
+            (2, _) => export,
+            (_, 3) => slices,
+            _ => return Default::default(),
+        };
+        html! {
+            <>
+                <h3>{"Export to SVG"}</h3>
+                <div class="settings__segment">
+                    {
+                        Self::view_checkbox(
+                            "Show labels",
+                            ImageExportSettings::get_svg_show_labels(),
+                            ImageExportSettings::set_svg_show_labels,
+                        )
+                    }
+                    {buttons}
+                </div>
+            </>
+        }
+    }
+
+    fn view_strip(ctx: &Context<Self>) -> Html {
+        if ctx.props().visible_dim < 3 {
+            return Default::default();
+        }
+
+        // Slices are drawn in two dimensions, so there is no point slicing any further.
+        let max_depth = ctx.props().visible_dim as u32 - 2;
+        let depth = ImageExportSettings::get_strip_depth().clamp(1, max_depth) as u8;
+        let singular = ImageExportSettings::get_strip_singular_slices();
+        let leftright_mode = ImageExportSettings::get_tikz_leftright_mode();
+        let show_braidings = ImageExportSettings::get_tikz_show_braidings();
+        let show_labels = ImageExportSettings::get_svg_show_labels();
+        let show_tikz_labels = ImageExportSettings::get_tikz_show_labels();
+        let theme = ImageExportSettings::get_theme();
+        html! {
+            <>
+                <h3>{"Export slices"}</h3>
+                <div class="settings__segment">
+                    {
+                        Self::view_slider(
+                            "Slicing depth",
+                            u32::from(depth),
+                            ImageExportSettings::set_strip_depth,
+                            1,
+                            max_depth,
+                        )
+                    }
+                    {
+                        Self::view_checkbox(
+                            "Singular slices",
+                            singular,
+                            ImageExportSettings::set_strip_singular_slices,
+                        )
+                    }
+                    <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportStripSvg(depth, singular, show_labels, theme))}>{"SVG"}</button>
+                    <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportStripTikz(depth, singular, leftright_mode, show_braidings, show_tikz_labels, theme))}>{"TikZ"}</button>
+                </div>
+            </>
         }
     }
 
     fn view_manim(ctx: &Context<Self>) -> Html {
         let use_opengl = ImageExportSettings::get_manim_use_opengl();
-        if ctx.props().view_dim == 2 {
+        let show_labels = ImageExportSettings::get_manim_show_labels();
+        let theme = ImageExportSettings::get_theme();
+        let dispatch = &ctx.props().dispatch;
+        let export = html! {
+            <button onclick={dispatch.reform(move |_| model::Action::ExportManim(use_opengl,show_labels,theme))}>{"Export"}</button>
+        };
+        let slices = html! {
+            <button onclick={dispatch.reform(move |_| model::Action::ExportManimSlices(use_opengl,show_labels,theme))}>{"Animate slices"}</button>
+        };
+        let buttons = match (ctx.props().view_dim, ctx.props().visible_dim) {
+            (2, 2) => export,
+            (2, _) => html! { <>{export}{slices}</> },
+            (_, visible_dim) if visible_dim >= 3 => slices,
+            _ => return Default::default(),
+        };
+        html! {
+            <>
+                <h3>{"Export to Manim"}</h3>
+                <div class="settings__segment">
+                    {
+                        Self::view_checkbox(
+                            "OpenGL renderer",
+                            ImageExportSettings::get_manim_use_opengl(),
+                            ImageExportSettings::set_manim_use_opengl,
+                        )
+                    }
+                    {
+                        Self::view_checkbox(
+                            "Show labels",
+                            ImageExportSettings::get_manim_show_labels(),
+                            ImageExportSettings::set_manim_show_labels,
+                        )
+                    }
+                    {buttons}
+                    <button onclick={dispatch.reform(move |_| model::Action::ExportManimHistory(use_opengl,show_labels,theme))}>{"Animate history"}</button>
+                </div>
+            </>
+        }
+    }
+
+    fn view_mesh(ctx: &Context<Self>) -> Html {
+        let subdivision_depth = ImageExportSettings::get_mesh_subdivision_depth() as u8;
+        let samples = ImageExportSettings::get_mesh_samples() as u8;
+        let theme = ImageExportSettings::get_theme();
+        if ctx.props().view_dim == 3 {
+            let export = |name: &str, action: fn(u8, u8, Theme) -> model::Action| {
+                html! {
+                    <button onclick={ctx.props().dispatch.reform(move |_| action(subdivision_depth, samples, theme))}>{name}</button>
+                }
+            };
             html! {
                 <>
-                    <h3>{"Export to Manim"}</h3>
+                    <h3>{"Export to 3D mesh"}</h3>
                     <div class="settings__segment">
                         {
-                            Self::view_checkbox(
-                                "OpenGL renderer",
-                                ImageExportSettings::get_manim_use_opengl(),
-                                ImageExportSettings::set_manim_use_opengl,
+                            Self::view_slider(
+                                "Subdivision depth",
+                                ImageExportSettings::get_mesh_subdivision_depth(),
+                                ImageExportSettings::set_mesh_subdivision_depth,
+                                0,
+                                6,
                             )
                         }
-                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportManim(use_opengl))}>{"Export"}</button>
+                        {
+                            Self::view_slider(
+                                "Geometry samples",
+                                ImageExportSettings::get_mesh_samples(),
+                                ImageExportSettings::set_mesh_samples,
+                                3,
+                                20,
+                            )
+                        }
+                        // STL files carry no colours, so they do not depend on the theme.
+                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportStl(subdivision_depth, samples))}>{"STL"}</button>
+                        {export("OBJ", model::Action::ExportObj)}
+                        {export("glTF", model::Action::ExportGltf)}
+                        {export("PLY", model::Action::ExportPly)}
                     </div>
                 </>
             }
@@ -141,18 +403,59 @@ impl ImageExportView {
         }
     }
 
-    fn view_stl(ctx: &Context<Self>) -> Html {
-        if ctx.props().view_dim == 3 {
+    fn view_theme() -> Html {
+        html! {
+            <div class="settings__segment">
+                {
+                    Self::view_select(
+                        "Theme",
+                        &Theme::ALL,
+                        ImageExportSettings::get_theme(),
+                        Theme::name,
+                        ImageExportSettings::set_theme,
+                    )
+                }
+            </div>
+        }
+    }
+
+    fn view_select<T, S>(
+        name: &str,
+        choices: &'static [T],
+        current: T,
+        label: fn(T) -> &'static str,
+        setter: S,
+    ) -> Html
+    where
+        T: Copy + PartialEq + 'static,
+        S: Fn(T) + 'static,
+    {
+        let options = choices.iter().enumerate().map(|(i, &choice)| {
             html! {
-                <>
-                    <h3>{"Export to STL"}</h3>
-                    <div class="settings__segment">
-                        <button onclick={ctx.props().dispatch.reform(move |_| model::Action::ExportStl)}>{"Export"}</button>
-                    </div>
-                </>
+                <option value={i.to_string()} selected={choice == current}>
+                    {label(choice)}
+                </option>
             }
-        } else {
-            Default::default()
+        });
+        html! {
+            <div class="settings__select-setting">
+                {name}
+                <select
+                    onchange={Callback::from(move |e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        if let Some(&choice) = select
+                            .value()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| choices.get(i))
+                        {
+                            setter(choice);
+                        }
+                    })}
+                >
+                    {for options}
+                </select>
+            </div>
         }
     }
 
@@ -171,4 +474,26 @@ impl ImageExportView {
             </div>
         }
     }
+
+    fn view_slider<S>(name: &str, current: u32, setter: S, min: u32, max: u32) -> Html
+    where
+        S: Fn(u32) + 'static,
+    {
+        html! {
+            <div class="settings__slider-setting">
+                {name}
+                <input
+                    type="range"
+                    min={min.to_string()}
+                    max={max.to_string()}
+                    value={current.to_string()}
+                    onchange={Callback::from(move |e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        let updated = input.value().parse::<u32>().unwrap_or(min);
+                        setter(updated);
+                    })}
+                />
+            </div>
+        }
+    }
 }
@@ -1,8 +1,29 @@
+use homotopy_core::Boundary;
 use yew::prelude::*;
 
-use crate::components::icon::{Icon, IconSize};
+use crate::{
+    app::keybindings::{Chord, Command, Keymap},
+    components::{
+        icon::{Icon, IconSize},
+        toast::{toast, Toast},
+    },
+    model::proof,
+    panic::CrashReport,
+};
+
+fn view_chord(chord: &Chord) -> Html {
+    html! {
+        {for chord.strokes().iter().enumerate().map(|(i, stroke)| html! {
+            <>
+                if i > 0 {{" "}}
+                <kbd class="kbc-button">{stroke.label()}</kbd>
+            </>
+        })}
+    }
+}
 
 pub fn get_help_message() -> Html {
+    let keymap = Keymap::current();
     html! {
         <div class="help">
             <p>
@@ -21,78 +42,12 @@ pub fn get_help_message() -> Html {
                     <td class="help-action">{"Hold  "}<kbd class="kbc-button">{"SHIFT"}</kbd></td>
                     <td class="help-description">{"Cancel inverses"}</td>
                 </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"U"}</kbd></td>
-                    <td class="help-description">{"Undo"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"Y"}</kbd></td>
-                    <td class="help-description">{"Redo"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"A"}</kbd></td>
-                    <td class="help-description">{"Add 0-cell"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action">
-                        <kbd class="kbc-button">{"S"}</kbd>
-                        {" "}
-                        <kbd class="kbc-button">{"T"}</kbd>
-                    </td>
-                    <td class="help-description">{"Set source/target"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"I"}</kbd></td>
-                    <td class="help-description">{"Take identity"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"C"}</kbd></td>
-                    <td class="help-description">{"Clear workspace"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action">
-                        <kbd class="kbc-button">{"↑"}</kbd>
-                        {" "}
-                        <kbd class="kbc-button">{"↓"}</kbd>
-                    </td>
-                    <td class="help-description">{"Switch slice"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"←"}</kbd></td>
-                    <td class="help-description">{"Ascend slice"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"→"}</kbd></td>
-                    <td class="help-description">{"Descend slice"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"D"}</kbd></td>
-                    <td class="help-description">{"Behead"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"F"}</kbd></td>
-                    <td class="help-description">{"Befoot"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"V"}</kbd></td>
-                    <td class="help-description">{"Invert"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"R"}</kbd></td>
-                    <td class="help-description">{"Restrict"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action"><kbd class="kbc-button">{"H"}</kbd></td>
-                    <td class="help-description">{"Theorem"}</td>
-                </tr>
-                <tr>
-                    <td class="help-action">
-                        <kbd class="kbc-button">{"1"}</kbd>
-                        {" - "}
-                        <kbd class="kbc-button">{"9"}</kbd>
-                    </td>
-                    <td class="help-description">{"Select generator or attachment"}</td>
-                </tr>
+                {for keymap.bindings.iter().map(|binding| html! {
+                    <tr>
+                        <td class="help-action">{view_chord(&binding.keys)}</td>
+                        <td class="help-description">{binding.command.label()}</td>
+                    </tr>
+                })}
             </table>
         </div>
     }
@@ -151,12 +106,96 @@ pub fn get_panic_message() -> Html {
             <p>
                 {"We'll fix the problem in no time!"}
             </p>
+            <p>
+                {"Your work up to the crash has been saved in this browser: reload the page to recover it."}
+            </p>
             <button onclick={move |_| {crate::panic::export_dump(false).unwrap();}}>{"Download action logs"}</button>
         </div>
     }
 }
 
+pub fn get_recovery_message(
+    report: &CrashReport,
+    on_recover: Callback<MouseEvent>,
+    on_dismiss: Callback<MouseEvent>,
+) -> Html {
+    let time = js_sys::Date::new(&report.time.into())
+        .to_locale_string("default", &wasm_bindgen::JsValue::UNDEFINED);
+    let message = report.message.clone();
+    let recoverable = report.can_recover();
+    let report = report.clone();
+    let on_report = move |_| {
+        if let Err(error) = crate::panic::export_crash_report(&report) {
+            toast(Toast::error(format!(
+                "Failed to export bug report: {error}"
+            )));
+        }
+    };
+
+    html! {
+        <div class="recovery">
+            <p>
+                {format!("The app crashed on {}. ", String::from(time))}
+                if recoverable {
+                    {"The proof as it was just before the crash was saved, and can be reopened now."}
+                } else {
+                    {"There was no proof to save."}
+                }
+            </p>
+            <p>
+                {"Please help us fix the problem by opening a new issue in our "}
+                <a href="https://github.com/homotopy-io/homotopy-rs/issues">{"GitHub tracker"}</a>
+                {" with the bug report attached, alongside a brief description of what you were doing."}
+            </p>
+            <pre class="recovery__message">{message}</pre>
+            <div class="recovery__actions">
+                if recoverable {
+                    <button onclick={on_recover}>{"Recover proof"}</button>
+                }
+                <button onclick={on_report}>{"Download bug report"}</button>
+                <button onclick={on_dismiss}>{"Discard"}</button>
+            </div>
+        </div>
+    }
+}
+
 pub fn get_onboarding_message() -> Html {
+    use proof::Action::{ClearWorkspace, CreateGeneratorZero, SetBoundary, TakeIdentityDiagram};
+
+    let keymap = Keymap::current();
+    let rows = [
+        ("Add 0-cell", vec![Command::Proof(CreateGeneratorZero)]),
+        (
+            "Set source/target",
+            vec![
+                Command::Proof(SetBoundary(Boundary::Source)),
+                Command::Proof(SetBoundary(Boundary::Target)),
+            ],
+        ),
+        ("Take identity", vec![Command::Proof(TakeIdentityDiagram)]),
+        ("Clear workspace", vec![Command::Proof(ClearWorkspace)]),
+        ("Help", vec![Command::Help]),
+    ]
+    .into_iter()
+    .filter_map(|(description, commands)| {
+        let chords: Vec<_> = commands.iter().filter_map(|c| keymap.chord(c)).collect();
+        (!chords.is_empty()).then(|| {
+            html! {
+                <tr>
+                    <td class="workspace__empty-keydesc">{description}</td>
+                    <td class="workspace__empty-keyicon">
+                        {for chords.into_iter().enumerate().map(|(i, chord)| html! {
+                            <>
+                                if i > 0 {{" "}}
+                                {view_chord(chord)}
+                            </>
+                        })}
+                    </td>
+                </tr>
+            }
+        })
+    });
+
     html! {
         <div class="workspace__empty-diagram">
             <div class="workspace__empty-diagram-content">
@@ -165,30 +204,7 @@ pub fn get_onboarding_message() -> Html {
                     <h3>{"Don't Panic!"}</h3>
                 </header>
                 <table>
-                    <tr>
-                        <td class="workspace__empty-keydesc">{"Add 0-cell"}</td>
-                        <td class="workspace__empty-keyicon"><kbd class="kbc-button">{"A"}</kbd></td>
-                    </tr>
-                    <tr>
-                        <td class="workspace__empty-keydesc">{"Set source/target"}</td>
-                        <td class="workspace__empty-keyicon">
-                            <kbd class="kbc-button">{"S"}</kbd>
-                            {" "}
-                            <kbd class="kbc-button">{"T"}</kbd>
-                        </td>
-                    </tr>
-                    <tr>
-                        <td class="workspace__empty-keydesc">{"Take identity"}</td>
-                        <td class="workspace__empty-keyicon"><kbd class="kbc-button">{"I"}</kbd></td>
-                    </tr>
-                    <tr>
-                        <td class="workspace__empty-keydesc">{"Clear workspace"}</td>
-                        <td class="workspace__empty-keyicon"><kbd class="kbc-button">{"C"}</kbd></td>
-                    </tr>
-                    <tr>
-                        <td class="workspace__empty-keydesc">{"Help"}</td>
-                        <td class="workspace__empty-keyicon"><kbd class="kbc-button">{"?"}</kbd></td>
-                    </tr>
+                    {for rows}
                 </table>
             </div>
         </div>
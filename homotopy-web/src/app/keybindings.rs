@@ -1,58 +1,685 @@
+//! Keyboard shortcuts.
+//!
+//! Shortcuts live in a [Keymap] which users can edit in the settings drawer. The keymap is one of
+//! the [AppSettings], which also keeps it in local storage so that it survives reloads, and can be
+//! exported to and imported from JSON.
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use gloo::storage::{LocalStorage, Storage};
 use homotopy_core::{Boundary, Direction, SliceIndex};
-use paste::paste;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+use yew::callback::Callback;
 
-use crate::model::{history, proof, Action};
+use crate::{
+    app::{
+        command_palette,
+        diagram_svg::{drag_at_cursor, DragDirection},
+        AppSettings,
+    },
+    components::delta::State,
+    model::{history, proof, Action},
+};
 
-macro_rules! declare_keybindings {
-    ($($key:expr => $action:expr)*) => {
-        paste! {
-            pub struct Keybindings;
+/// The key under which the keymap is saved in local storage.
+const STORAGE_KEY: &str = "homotopy-io:keymap";
 
-            impl Keybindings {
-                pub fn get_action(key: &str) -> Option<Action> {
-                    match key {
-                        $($key => Some($action)),*,
-                        _ => None
-                    }
-                }
+/// Keys which only modify other keys and never make up a stroke by themselves.
+const MODIFIER_KEYS: &[&str] = &[
+    "alt", "altgraph", "capslock", "control", "fn", "meta", "os", "shift",
+];
+
+#[derive(Debug, Error)]
+pub enum KeymapError {
+    #[error("a key binding needs at least one key")]
+    Empty,
+    #[error("unknown modifier \"{0}\"")]
+    UnknownModifier(String),
+    #[error("invalid keymap: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Whether a key event is typing into a form field, in which case it is not a shortcut.
+pub fn is_typing(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .map_or(false, |element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "SELECT" | "TEXTAREA")
+                || element.is_content_editable()
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Meta,
+}
+
+impl Modifier {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Ctrl => "ctrl",
+            Self::Alt => "alt",
+            Self::Shift => "shift",
+            Self::Meta => "meta",
+        }
+    }
+}
+
+impl FromStr for Modifier {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ctrl" | "control" => Ok(Self::Ctrl),
+            "alt" | "option" => Ok(Self::Alt),
+            "shift" => Ok(Self::Shift),
+            "meta" | "cmd" | "super" => Ok(Self::Meta),
+            _ => Err(KeymapError::UnknownModifier(s.to_owned())),
+        }
+    }
+}
+
+/// A single key press together with the modifiers held down, written like `ctrl+shift+z`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyStroke {
+    key: String,
+    modifiers: BTreeSet<Modifier>,
+}
+
+impl KeyStroke {
+    pub fn new<I>(key: &str, modifiers: I) -> Self
+    where
+        I: IntoIterator<Item = Modifier>,
+    {
+        let key = if key == " " {
+            "space".to_owned()
+        } else {
+            key.to_lowercase()
+        };
+        // Printable keys already tell whether shift was held (`?` rather than `/`), so shift is
+        // only kept for named keys such as `shift+arrowup`.
+        let printable = key.chars().count() == 1;
+        let modifiers = modifiers
+            .into_iter()
+            .filter(|&m| !(printable && m == Modifier::Shift))
+            .collect();
+        Self { key, modifiers }
+    }
+
+    pub fn plain(key: &str) -> Self {
+        Self::new(key, [])
+    }
+
+    /// The stroke made by a key event, or `None` if only a modifier key was pressed.
+    pub fn from_event(event: &KeyboardEvent) -> Option<Self> {
+        let key = event.key();
+        if MODIFIER_KEYS.contains(&key.to_lowercase().as_str()) {
+            return None;
+        }
+        let held = [
+            (event.ctrl_key(), Modifier::Ctrl),
+            (event.alt_key(), Modifier::Alt),
+            (event.shift_key(), Modifier::Shift),
+            (event.meta_key(), Modifier::Meta),
+        ];
+        Some(Self::new(
+            &key,
+            held.into_iter()
+                .filter_map(|(held, modifier)| held.then_some(modifier)),
+        ))
+    }
+
+    /// How the stroke is shown on key caps in the UI.
+    pub fn label(&self) -> String {
+        let key = match self.key.as_str() {
+            "arrowup" => "↑".to_owned(),
+            "arrowdown" => "↓".to_owned(),
+            "arrowleft" => "←".to_owned(),
+            "arrowright" => "→".to_owned(),
+            key => key.to_uppercase(),
+        };
+        self.modifiers
+            .iter()
+            .map(|m| m.name().to_uppercase())
+            .chain(std::iter::once(key))
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+}
+
+impl fmt::Display for KeyStroke {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier.name())?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for KeyStroke {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A trailing `+` is the plus key itself, as in `ctrl++`.
+        let (modifiers, key) = match s.trim().rsplit_once('+') {
+            Some((modifiers, "")) => (modifiers.strip_suffix('+').unwrap_or(modifiers), "+"),
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", s.trim()),
+        };
+        if key.is_empty() {
+            return Err(KeymapError::Empty);
+        }
+        let modifiers = modifiers
+            .split('+')
+            .filter(|m| !m.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+/// A sequence of strokes pressed one after the other, written like `g shift+arrowup`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Chord(Vec<KeyStroke>);
+
+impl Chord {
+    pub fn new(strokes: Vec<KeyStroke>) -> Result<Self, KeymapError> {
+        if strokes.is_empty() {
+            Err(KeymapError::Empty)
+        } else {
+            Ok(Self(strokes))
+        }
+    }
+
+    pub fn strokes(&self) -> &[KeyStroke] {
+        &self.0
+    }
+
+    pub fn label(&self) -> String {
+        self.0
+            .iter()
+            .map(KeyStroke::label)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.0.starts_with(&other.0) || other.0.starts_with(&self.0)
+    }
+}
+
+impl From<KeyStroke> for Chord {
+    fn from(stroke: KeyStroke) -> Self {
+        Self(vec![stroke])
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, stroke) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{stroke}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Chord {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(
+            s.split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        )
+    }
+}
+
+impl TryFrom<String> for Chord {
+    type Error = KeymapError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Chord> for String {
+    fn from(chord: Chord) -> Self {
+        chord.to_string()
+    }
+}
+
+/// Something that can be bound to a key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+    Help,
+    Palette,
+    Undo,
+    Redo,
+    /// Pick the n-th generator or attachment option shown.
+    Select(usize),
+    Proof(proof::Action),
+    /// Perform the homotopy obtained by dragging from the point under the mouse cursor.
+    HomotopyAtCursor(DragDirection),
+}
+
+impl Command {
+    /// The commands offered when adding a binding in the settings.
+    pub fn presets() -> Vec<Self> {
+        use proof::Action::{
+            AscendSlice, Befoot, Behead, ClearBoundary, ClearWorkspace, CopyToShelf,
+            CreateGeneratorZero, CreateWorkspace, DecreaseView, DescendSlice, FlipBoundary,
+            IncreaseView, Invert, RecoverBoundary, Restrict, SetBoundary, SwitchSlice,
+            TakeIdentityDiagram, Theorem,
+        };
+
+        let mut presets = vec![Self::Help, Self::Palette, Self::Undo, Self::Redo];
+        presets.extend(
+            [
+                CreateGeneratorZero,
+                SetBoundary(Boundary::Source),
+                SetBoundary(Boundary::Target),
+                FlipBoundary,
+                RecoverBoundary,
+                ClearBoundary,
+                TakeIdentityDiagram,
+                ClearWorkspace,
+                Behead,
+                Befoot,
+                Invert,
+                Restrict,
+                Theorem,
+                SwitchSlice(Direction::Forward),
+                SwitchSlice(Direction::Backward),
+                AscendSlice(1),
+                DescendSlice(SliceIndex::Boundary(Boundary::Source)),
+                IncreaseView(1),
+                DecreaseView(1),
+                CreateWorkspace,
+                CopyToShelf,
+            ]
+            .into_iter()
+            .map(Self::Proof),
+        );
+        presets.extend(DragDirection::ALL.into_iter().map(Self::HomotopyAtCursor));
+        presets.extend((0..9).map(Self::Select));
+        presets
+    }
+
+    /// The model action performed by the command, if it is not handled by the UI itself.
+    pub fn action(&self) -> Option<Action> {
+        use history::Direction::Linear;
+
+        match self {
+            Self::Help => Some(Action::Help),
+            Self::Undo => Some(Action::History(history::Action::Move(Linear(
+                Direction::Backward,
+            )))),
+            Self::Redo => Some(Action::History(history::Action::Move(Linear(
+                Direction::Forward,
+            )))),
+            Self::Select(index) => Some(Action::Select(*index)),
+            Self::Proof(action) => Some(Action::Proof(action.clone())),
+            Self::Palette | Self::HomotopyAtCursor(_) => None,
+        }
+    }
 
-                pub fn get_shortcut(action: Action) -> Option<&'static str> {
-                    match action {
-                        $($action => Some($key)),*,
-                        _ => None
-                    }
+    /// Perform the command, passing any model action on to `dispatch`.
+    pub fn run(&self, dispatch: &Callback<Action>) {
+        match self {
+            Self::Palette => command_palette::open(),
+            Self::HomotopyAtCursor(direction) => drag_at_cursor(*direction),
+            _ => {
+                if let Some(action) = self.action() {
+                    dispatch.emit(action);
                 }
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Help => "Help".to_owned(),
+            Self::Palette => "Command palette".to_owned(),
+            Self::Undo => "Undo".to_owned(),
+            Self::Redo => "Redo".to_owned(),
+            Self::Select(index) => format!("Select item {}", index + 1),
+            Self::Proof(action) => proof_label(action),
+            Self::HomotopyAtCursor(direction) => format!("Drag {} at cursor", direction.name()),
+        }
+    }
+}
+
+fn proof_label(action: &proof::Action) -> String {
+    use proof::Action::{
+        AscendSlice, Befoot, Behead, ClearBoundary, ClearWorkspace, CopyToShelf,
+        CreateGeneratorZero, CreateWorkspace, DecreaseView, DescendSlice, FlipBoundary,
+        IncreaseView, Invert, RecoverBoundary, Restrict, SetBoundary, SwitchSlice,
+        TakeIdentityDiagram, Theorem,
+    };
+
+    match action {
+        CreateGeneratorZero => "Add 0-cell".to_owned(),
+        SetBoundary(Boundary::Source) => "Set source".to_owned(),
+        SetBoundary(Boundary::Target) => "Set target".to_owned(),
+        FlipBoundary => "Flip boundary".to_owned(),
+        RecoverBoundary => "Recover boundary".to_owned(),
+        ClearBoundary => "Clear boundary".to_owned(),
+        TakeIdentityDiagram => "Take identity".to_owned(),
+        ClearWorkspace => "Clear workspace".to_owned(),
+        Behead => "Behead".to_owned(),
+        Befoot => "Befoot".to_owned(),
+        Invert => "Invert".to_owned(),
+        Restrict => "Restrict".to_owned(),
+        Theorem => "Theorem".to_owned(),
+        SwitchSlice(Direction::Forward) => "Next slice".to_owned(),
+        SwitchSlice(Direction::Backward) => "Previous slice".to_owned(),
+        AscendSlice(1) => "Ascend slice".to_owned(),
+        AscendSlice(count) => format!("Ascend {count} slices"),
+        DescendSlice(SliceIndex::Boundary(Boundary::Source)) => "Descend slice".to_owned(),
+        DescendSlice(slice) => format!("Descend into slice {slice:?}"),
+        IncreaseView(count) => format!("Increase view by {count}"),
+        DecreaseView(count) => format!("Decrease view by {count}"),
+        CreateWorkspace => "New workspace".to_owned(),
+        CopyToShelf => "Copy to shelf".to_owned(),
+        action => format!("{action:?}"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub keys: Chord,
+    pub command: Command,
+}
+
+impl Binding {
+    fn key(key: &str, command: Command) -> Self {
+        Self {
+            keys: KeyStroke::plain(key).into(),
+            command,
+        }
+    }
+}
+
+/// Two bindings which cannot both be used: their keys are equal, or the keys of one start with
+/// the keys of the other, so that the longer chord can never be completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict(pub usize, pub usize);
+
+/// The result of looking up the strokes pressed so far.
+pub enum Lookup<'a> {
+    Bound(&'a Command),
+    /// The strokes are the start of a longer chord.
+    Pending,
+    Unbound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use proof::Action::{
+            AscendSlice, Befoot, Behead, ClearWorkspace, CreateGeneratorZero, DescendSlice, Invert,
+            Restrict, SetBoundary, SwitchSlice, TakeIdentityDiagram, Theorem,
+        };
+
+        let mut bindings = vec![
+            Binding::key("?", Command::Help),
+            Binding {
+                keys: KeyStroke::new("k", [Modifier::Ctrl]).into(),
+                command: Command::Palette,
+            },
+            Binding {
+                keys: KeyStroke::new("k", [Modifier::Meta]).into(),
+                command: Command::Palette,
+            },
+            Binding::key("y", Command::Redo),
+            Binding::key("u", Command::Undo),
+            Binding::key("d", Command::Proof(Behead)),
+            Binding::key("f", Command::Proof(Befoot)),
+            Binding::key("v", Command::Proof(Invert)),
+            Binding::key("r", Command::Proof(Restrict)),
+            Binding::key("h", Command::Proof(Theorem)),
+            Binding::key("a", Command::Proof(CreateGeneratorZero)),
+            Binding::key("s", Command::Proof(SetBoundary(Boundary::Source))),
+            Binding::key("t", Command::Proof(SetBoundary(Boundary::Target))),
+            Binding::key("i", Command::Proof(TakeIdentityDiagram)),
+            Binding::key("c", Command::Proof(ClearWorkspace)),
+        ];
+        bindings.extend((0..9).map(|i| Binding::key(&(i + 1).to_string(), Command::Select(i))));
+        bindings.extend([
+            Binding::key("arrowup", Command::Proof(SwitchSlice(Direction::Forward))),
+            Binding::key(
+                "arrowdown",
+                Command::Proof(SwitchSlice(Direction::Backward)),
+            ),
+            Binding::key("arrowleft", Command::Proof(AscendSlice(1))),
+            Binding::key(
+                "arrowright",
+                Command::Proof(DescendSlice(SliceIndex::Boundary(Boundary::Source))),
+            ),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+impl State for Keymap {
+    type Action = Self;
+
+    fn update(&mut self, action: &Self::Action) -> bool {
+        let ret = self != action;
+        *self = action.clone();
+        ret
+    }
+}
+
+impl Keymap {
+    /// The keymap currently in use.
+    pub fn current() -> Self {
+        AppSettings::get_keymap()
+    }
+
+    /// Replace the keymap in use and save it for later sessions.
+    pub fn set(keymap: Self) {
+        if let Err(error) = LocalStorage::set(STORAGE_KEY, &keymap) {
+            tracing::warn!("Failed to save keymap: {error}");
+        }
+        AppSettings::set_keymap(keymap);
+    }
+
+    /// The keymap saved by an earlier session, or the default one.
+    pub fn load() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeymapError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, KeymapError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
 
+    pub fn lookup(&self, strokes: &[KeyStroke]) -> Lookup<'_> {
+        if let Some(binding) = self.bindings.iter().find(|b| b.keys.0 == strokes) {
+            Lookup::Bound(&binding.command)
+        } else if self.bindings.iter().any(|b| b.keys.0.starts_with(strokes)) {
+            Lookup::Pending
+        } else {
+            Lookup::Unbound
+        }
+    }
+
+    /// The first chord bound to a command.
+    pub fn chord(&self, command: &Command) -> Option<&Chord> {
+        self.bindings
+            .iter()
+            .find(|b| &b.command == command)
+            .map(|b| &b.keys)
+    }
+
+    /// The first chord bound to a command performing the given action.
+    pub fn shortcut(&self, action: &Action) -> Option<&Chord> {
+        self.bindings
+            .iter()
+            .find(|b| b.command.action().as_ref() == Some(action))
+            .map(|b| &b.keys)
+    }
+
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (i, a) in self.bindings.iter().enumerate() {
+            for (j, b) in self.bindings.iter().enumerate().skip(i + 1) {
+                if a.keys.overlaps(&b.keys) {
+                    conflicts.push(Conflict(i, j));
+                }
             }
         }
+        conflicts
+    }
+
+    pub fn describe(&self, conflict: Conflict) -> String {
+        let a = &self.bindings[conflict.0];
+        let b = &self.bindings[conflict.1];
+        if a.keys == b.keys {
+            format!(
+                "{} is bound to both \"{}\" and \"{}\"",
+                a.keys,
+                a.command.label(),
+                b.command.label(),
+            )
+        } else {
+            let (short, long) = if a.keys.0.len() < b.keys.0.len() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            format!(
+                "{} (\"{}\") hides {} (\"{}\")",
+                short.keys,
+                short.command.label(),
+                long.keys,
+                long.command.label(),
+            )
+        }
     }
 }
 
-declare_keybindings! {
-    "?" => Action::Help
-    "y" => Action::History(history::Action::Move(history::Direction::Linear(Direction::Forward)))
-    "u" => Action::History(history::Action::Move(history::Direction::Linear(Direction::Backward)))
-    "d" => Action::Proof(proof::Action::Behead)
-    "f" => Action::Proof(proof::Action::Befoot)
-    "v" => Action::Proof(proof::Action::Invert)
-    "r" => Action::Proof(proof::Action::Restrict)
-    "h" => Action::Proof(proof::Action::Theorem)
-    "a" => Action::Proof(proof::Action::CreateGeneratorZero)
-    "s" => Action::Proof(proof::Action::SetBoundary(Boundary::Source))
-    "t" => Action::Proof(proof::Action::SetBoundary(Boundary::Target))
-    "i" => Action::Proof(proof::Action::TakeIdentityDiagram)
-    "c" => Action::Proof(proof::Action::ClearWorkspace)
-    "1" => Action::Select(0)
-    "2" => Action::Select(1)
-    "3" => Action::Select(2)
-    "4" => Action::Select(3)
-    "5" => Action::Select(4)
-    "6" => Action::Select(5)
-    "7" => Action::Select(6)
-    "8" => Action::Select(7)
-    "9" => Action::Select(8)
-    "arrowup" => Action::Proof(proof::Action::SwitchSlice(Direction::Forward))
-    "arrowdown" => Action::Proof(proof::Action::SwitchSlice(Direction::Backward))
-    "arrowleft" => Action::Proof(proof::Action::AscendSlice(1))
-    "arrowright" => Action::Proof(proof::Action::DescendSlice(SliceIndex::Boundary(Boundary::Source)))
+#[cfg(test)]
+mod test {
+    use super::{Binding, Chord, Command, Conflict, KeyStroke, Keymap, Lookup, Modifier};
+
+    fn stroke(s: &str) -> KeyStroke {
+        s.parse().unwrap()
+    }
+
+    fn chord(s: &str) -> Chord {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_key_strokes() {
+        assert_eq!(stroke("z"), KeyStroke::plain("z"));
+        assert_eq!(
+            stroke("Ctrl+Shift+ArrowUp"),
+            KeyStroke::new("arrowup", [Modifier::Ctrl, Modifier::Shift])
+        );
+        assert_eq!(stroke("cmd+k"), KeyStroke::new("k", [Modifier::Meta]));
+        assert_eq!(stroke("ctrl++"), KeyStroke::new("+", [Modifier::Ctrl]));
+        assert_eq!(stroke("+"), KeyStroke::plain("+"));
+        // Shift is implied by printable keys.
+        assert_eq!(stroke("shift+?"), KeyStroke::plain("?"));
+        assert!(" ".parse::<KeyStroke>().is_err());
+        assert!("hyper+x".parse::<KeyStroke>().is_err());
+    }
+
+    #[test]
+    fn key_strokes_round_trip() {
+        for s in ["ctrl++", "alt+shift+arrowleft", "space", "meta+k"] {
+            assert_eq!(stroke(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_chords() {
+        assert_eq!(
+            chord("g  shift+arrowup").strokes(),
+            [
+                KeyStroke::plain("g"),
+                KeyStroke::new("arrowup", [Modifier::Shift])
+            ]
+        );
+        assert_eq!(chord("g ctrl++").to_string(), "g ctrl++");
+        assert!("".parse::<Chord>().is_err());
+        assert!(serde_json::from_str::<Chord>("\"\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<Chord>("\"ctrl+k\"").unwrap(),
+            chord("ctrl+k")
+        );
+    }
+
+    #[test]
+    fn lookup_chords() {
+        let keymap = Keymap {
+            bindings: vec![
+                Binding {
+                    keys: chord("g u"),
+                    command: Command::Undo,
+                },
+                Binding {
+                    keys: chord("ctrl+k"),
+                    command: Command::Palette,
+                },
+            ],
+        };
+        assert!(matches!(keymap.lookup(&[stroke("g")]), Lookup::Pending));
+        assert!(matches!(
+            keymap.lookup(&[stroke("g"), stroke("u")]),
+            Lookup::Bound(Command::Undo)
+        ));
+        assert!(matches!(
+            keymap.lookup(&[stroke("ctrl+k")]),
+            Lookup::Bound(Command::Palette)
+        ));
+        assert!(matches!(keymap.lookup(&[stroke("k")]), Lookup::Unbound));
+        assert!(matches!(
+            keymap.lookup(&[stroke("g"), stroke("y")]),
+            Lookup::Unbound
+        ));
+    }
+
+    #[test]
+    fn conflicting_bindings() {
+        assert!(Keymap::default().conflicts().is_empty());
+
+        let binding = |keys: &str, command| Binding {
+            keys: chord(keys),
+            command,
+        };
+        let keymap = Keymap {
+            bindings: vec![
+                binding("g", Command::Help),
+                binding("u", Command::Undo),
+                binding("g u", Command::Redo),
+                binding("u", Command::Palette),
+            ],
+        };
+        assert_eq!(keymap.conflicts(), [Conflict(0, 2), Conflict(1, 3)]);
+    }
 }
@@ -13,6 +13,7 @@ use crate::{
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Msg {
     ImportProof(File),
+    ImportLibrary(File),
     EditMetadata(MetadataEdit),
     Noop,
 }
@@ -47,6 +48,13 @@ impl Component for ProjectView {
                 Msg::Noop
             }
         });
+        let import_library = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            match input.files().and_then(|files| files.get(0)) {
+                Some(file) => Msg::ImportLibrary(file),
+                None => Msg::Noop,
+            }
+        });
 
         html! {
             <>
@@ -55,6 +63,14 @@ impl Component for ProjectView {
                     {"Import"}
                 </label>
                 <input type="file" accept="application/msgpack,application/octet-stream,.hom,.json" class="visually-hidden" id="import" onchange={import}/>
+                <label
+                    for="import-library"
+                    class="button"
+                    title="Add the generators of another proof to the signature as a new folder"
+                >
+                    {"Import library"}
+                </label>
+                <input type="file" accept="application/msgpack,application/octet-stream,.hom,.json" class="visually-hidden" id="import-library" onchange={import_library}/>
                 <div class="metadata__details">
                     <TexSpan
                         class="metadata__title"
@@ -113,6 +129,22 @@ impl Component for ProjectView {
                 self.reader = Some(task);
                 false
             }
+            Msg::ImportLibrary(file) => {
+                // Name the new folder after the file it came from.
+                let name = file.name();
+                let name = name
+                    .rsplit_once('.')
+                    .map_or(name.as_str(), |(stem, _)| stem)
+                    .to_owned();
+                let task = gloo::file::callbacks::read_as_bytes(
+                    &file.into(),
+                    closure!(move name, clone dispatch, |res| {
+                        dispatch.emit(model::Action::Proof(model::proof::Action::ImportLibrary(name, res.expect("failed to read file").into())));
+                    }),
+                );
+                self.reader = Some(task);
+                false
+            }
             Msg::EditMetadata(edit) => {
                 // In order to avoid generating multiple history events for a single rename, we
                 // don't dispatch renames until the user is done editing.
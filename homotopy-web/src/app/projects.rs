@@ -0,0 +1,295 @@
+//! Projects saved in the browser. The current proof and the actions which led to it are autosaved
+//! here every so often, so that work and its undo history survive closing the tab.
+
+use std::cell::RefCell;
+
+use homotopy_graphics::svg::{self, SvgOptions};
+use store::{Project, ProjectSummary, StoreError};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    components::{
+        delta::{CallbackIdx, Delta, State},
+        toast::{toast, Toast},
+    },
+    model::{self, history::History, proof, serialize, Proof},
+};
+
+mod store;
+
+/// How often the current proof is autosaved, in milliseconds.
+pub const AUTOSAVE_INTERVAL: u32 = 30_000;
+
+#[derive(Default, Clone)]
+struct Saved;
+
+impl State for Saved {
+    type Action = ();
+
+    fn update(&mut self, _action: &Self::Action) -> bool {
+        true
+    }
+}
+
+thread_local! {
+    /// The id the current proof is saved under.
+    static CURRENT: RefCell<String> = RefCell::new(new_id());
+    /// The current history, if it changed since it was last saved.
+    static PENDING: RefCell<Option<History>> = RefCell::new(None);
+    /// The last proof marked as changed, whether or not it was saved since.
+    static LATEST: RefCell<Option<Proof>> = RefCell::new(None);
+    static SAVED: Delta<Saved> = Delta::default();
+}
+
+fn new_id() -> String {
+    format!(
+        "{:x}-{:x}",
+        js_sys::Date::now() as u64,
+        (js_sys::Math::random() * f64::from(u32::MAX)) as u32
+    )
+}
+
+/// Note that the history changed, so that it is saved at the next autosave.
+pub fn mark_changed(history: &History) {
+    PENDING.with(|pending| *pending.borrow_mut() = Some(history.clone()));
+    LATEST.with(|latest| *latest.borrow_mut() = Some(history.proof().clone()));
+}
+
+/// The last proof marked as changed, serialized.
+pub fn latest() -> Option<Vec<u8>> {
+    // This runs from the panic hook, which may interrupt a borrow.
+    let proof = LATEST.with(|latest| latest.try_borrow().ok()?.clone())?;
+    Some(serialize_proof(&proof))
+}
+
+/// Save the current proof now if it changed since it was last saved.
+pub fn flush() {
+    let Some(history) = PENDING.with(|pending| pending.borrow_mut().take()) else { return };
+    let id = CURRENT.with(|current| current.borrow().clone());
+    store::save(&snapshot(id, &history), |result| match result {
+        Ok(()) => SAVED.with(|saved| saved.emit(&())),
+        Err(error) => tracing::error!("Autosave failed: {}", error),
+    });
+}
+
+fn serialize_proof(proof: &Proof) -> Vec<u8> {
+    serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    )
+}
+
+fn snapshot(id: String, history: &History) -> Project {
+    let proof = history.proof();
+    let data = serialize_proof(proof);
+    // The same format as exported actions, so that they can be replayed on an empty proof.
+    let actions = serde_json::to_vec(&(true, history.get_last_import_segment())).ok();
+    let thumbnail = proof
+        .workspace
+        .as_ref()
+        .map(proof::Workspace::visible_diagram)
+        .filter(|diagram| diagram.dimension() <= 2)
+        .and_then(|diagram| svg::export(&diagram, &proof.signature, &SvgOptions::default()).ok());
+
+    Project {
+        summary: ProjectSummary {
+            id,
+            title: proof.metadata.title.clone().unwrap_or_default(),
+            author: proof.metadata.author.clone().unwrap_or_default(),
+            saved: js_sys::Date::now(),
+            thumbnail,
+        },
+        data,
+        actions,
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub dispatch: Callback<model::Action>,
+}
+
+pub enum Msg {
+    Refresh,
+    Listed(Result<Vec<ProjectSummary>, StoreError>),
+    Query(String),
+    Open(String),
+    Opened(Result<Project, StoreError>),
+    Delete(String),
+}
+
+/// Browse, search, open and delete the projects saved in the browser.
+pub struct ProjectsView {
+    projects: Option<Result<Vec<ProjectSummary>, StoreError>>,
+    query: String,
+    callback_idx: CallbackIdx,
+}
+
+impl Component for ProjectsView {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Refresh);
+        Self {
+            projects: None,
+            query: String::new(),
+            callback_idx: SAVED.with(|saved| saved.register(ctx.link().callback(|_| Msg::Refresh))),
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        SAVED.with(|saved| saved.unregister(self.callback_idx));
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Refresh => {
+                let link = ctx.link().clone();
+                store::list(move |projects| link.send_message(Msg::Listed(projects)));
+                false
+            }
+            Msg::Listed(projects) => {
+                self.projects = Some(projects);
+                true
+            }
+            Msg::Query(query) => {
+                self.query = query;
+                true
+            }
+            Msg::Open(id) => {
+                let link = ctx.link().clone();
+                store::load(&id, move |project| link.send_message(Msg::Opened(project)));
+                false
+            }
+            Msg::Opened(Ok(project)) => {
+                // Keep the changes to the project being left before switching.
+                flush();
+                CURRENT.with(|current| *current.borrow_mut() = project.summary.id);
+                ctx.props().dispatch.emit(model::Action::OpenProof(
+                    project.data.into(),
+                    project.actions.map(Into::into),
+                ));
+                false
+            }
+            Msg::Opened(Err(error)) => {
+                toast(Toast::error(format!("Failed to open project: {error}")));
+                false
+            }
+            Msg::Delete(id) => {
+                let link = ctx.link().clone();
+                store::delete(&id, move |result| {
+                    if let Err(error) = result {
+                        toast(Toast::error(format!("Failed to delete project: {error}")));
+                    }
+                    link.send_message(Msg::Refresh);
+                });
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_input = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::Query(input.value())
+        });
+
+        let body = match &self.projects {
+            None => html! {},
+            Some(Err(error)) => html! {
+                <p class="projects__message">{error.to_string()}</p>
+            },
+            Some(Ok(projects)) => {
+                let current = CURRENT.with(|current| current.borrow().clone());
+                let query = self.query.to_lowercase();
+                let items: Html = projects
+                    .iter()
+                    .filter(|project| {
+                        project.title.to_lowercase().contains(&query)
+                            || project.author.to_lowercase().contains(&query)
+                    })
+                    .map(|project| Self::view_project(ctx, project, project.id == current))
+                    .collect();
+                if projects.is_empty() {
+                    html! {
+                        <p class="projects__message">
+                            {"Projects are saved here automatically as you work."}
+                        </p>
+                    }
+                } else {
+                    html! {
+                        <ul class="projects__list">{items}</ul>
+                    }
+                }
+            }
+        };
+
+        html! {
+            <div class="projects">
+                <input
+                    class="projects__search"
+                    type="search"
+                    placeholder="Search by title or author"
+                    value={self.query.clone()}
+                    oninput={on_input}
+                />
+                {body}
+            </div>
+        }
+    }
+}
+
+impl ProjectsView {
+    fn view_project(ctx: &Context<Self>, project: &ProjectSummary, current: bool) -> Html {
+        let open = {
+            let id = project.id.clone();
+            ctx.link().callback(move |_| Msg::Open(id.clone()))
+        };
+        let delete = {
+            let id = project.id.clone();
+            ctx.link().callback(move |_| Msg::Delete(id.clone()))
+        };
+        let saved = js_sys::Date::new(&project.saved.into())
+            .to_locale_string("default", &wasm_bindgen::JsValue::UNDEFINED);
+        let thumbnail = project.thumbnail.as_ref().map(|thumbnail| {
+            format!(
+                "data:image/svg+xml;charset=utf-8,{}",
+                js_sys::encode_uri_component(thumbnail)
+            )
+        });
+
+        html! {
+            <li class={classes!("projects__item", current.then_some("projects__item--current"))}>
+                <div class="projects__thumbnail">
+                    if let Some(thumbnail) = thumbnail {
+                        <img src={thumbnail} alt="" />
+                    }
+                </div>
+                <div class="projects__details">
+                    <span class="projects__title">
+                        {if project.title.is_empty() { "Untitled" } else { project.title.as_str() }}
+                    </span>
+                    if !project.author.is_empty() {
+                        <span class="projects__author">{&project.author}</span>
+                    }
+                    <span class="projects__saved">
+                        {String::from(saved)}
+                        if current {
+                            {" · current"}
+                        }
+                    </span>
+                    <div class="projects__actions">
+                        if !current {
+                            <button onclick={open}>{"Open"}</button>
+                            <button onclick={delete}>{"Delete"}</button>
+                        }
+                    </div>
+                </div>
+            </li>
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Saved projects, kept in an IndexedDB object store of the browser.
+//!
+//! IndexedDB is asynchronous, so every operation takes a callback that receives its outcome.
+
+use std::{cell::RefCell, rc::Rc};
+
+use js_sys::{Object, Reflect, Uint8Array};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransactionMode};
+
+const DATABASE: &str = "homotopy-io";
+const VERSION: u32 = 1;
+const PROJECTS: &str = "projects";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StoreError {
+    #[error("browser storage is unavailable")]
+    Unavailable,
+    #[error("browser storage request failed")]
+    Request,
+    #[error("saved project is malformed")]
+    Malformed,
+}
+
+/// Everything listed about a saved project, without the proof itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSummary {
+    pub id: String,
+    pub title: String,
+    pub author: String,
+    /// When the project was last saved, in milliseconds since the epoch.
+    pub saved: f64,
+    /// An SVG picture of the workspace when the project was saved.
+    pub thumbnail: Option<String>,
+}
+
+/// A saved project: its summary and the proof in the format of [crate::model::serialize].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    pub summary: ProjectSummary,
+    pub data: Vec<u8>,
+    /// The actions which led to the proof, in the format of [crate::model::Action::ImportActions].
+    /// Projects saved by earlier versions have none.
+    pub actions: Option<Vec<u8>>,
+}
+
+impl Project {
+    fn to_js(&self) -> Result<JsValue, JsValue> {
+        let object = Object::new();
+        let summary = &self.summary;
+        Reflect::set(&object, &"id".into(), &summary.id.as_str().into())?;
+        Reflect::set(&object, &"title".into(), &summary.title.as_str().into())?;
+        Reflect::set(&object, &"author".into(), &summary.author.as_str().into())?;
+        Reflect::set(&object, &"saved".into(), &summary.saved.into())?;
+        if let Some(thumbnail) = &summary.thumbnail {
+            Reflect::set(&object, &"thumbnail".into(), &thumbnail.as_str().into())?;
+        }
+        let data: Uint8Array = self.data.as_slice().into();
+        Reflect::set(&object, &"data".into(), &data)?;
+        if let Some(actions) = &self.actions {
+            let actions: Uint8Array = actions.as_slice().into();
+            Reflect::set(&object, &"actions".into(), &actions)?;
+        }
+        Ok(object.into())
+    }
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        let string = |key: &str| Reflect::get(value, &key.into()).ok()?.as_string();
+        let summary = ProjectSummary {
+            id: string("id")?,
+            title: string("title").unwrap_or_default(),
+            author: string("author").unwrap_or_default(),
+            saved: Reflect::get(value, &"saved".into()).ok()?.as_f64()?,
+            thumbnail: string("thumbnail"),
+        };
+        let bytes = |key: &str| {
+            Some(
+                Reflect::get(value, &key.into())
+                    .ok()?
+                    .dyn_into::<Uint8Array>()
+                    .ok()?
+                    .to_vec(),
+            )
+        };
+        Some(Self {
+            summary,
+            data: bytes("data")?,
+            actions: bytes("actions"),
+        })
+    }
+}
+
+/// Save a project, replacing any project with the same id.
+pub fn save(project: &Project, on_done: impl FnOnce(Result<(), StoreError>) + 'static) {
+    let Ok(value) = project.to_js() else {
+        on_done(Err(StoreError::Malformed));
+        return;
+    };
+    request(
+        IdbTransactionMode::Readwrite,
+        move |store| store.put(&value),
+        move |result| on_done(result.map(|_| ())),
+    );
+}
+
+/// List the saved projects, most recently saved first.
+pub fn list(on_done: impl FnOnce(Result<Vec<ProjectSummary>, StoreError>) + 'static) {
+    request(
+        IdbTransactionMode::Readonly,
+        IdbObjectStore::get_all,
+        move |result| {
+            on_done(result.map(|value| {
+                let mut projects: Vec<_> = js_sys::Array::from(&value)
+                    .iter()
+                    .filter_map(|value| Project::from_js(&value))
+                    .map(|project| project.summary)
+                    .collect();
+                projects.sort_by(|a, b| b.saved.total_cmp(&a.saved));
+                projects
+            }));
+        },
+    );
+}
+
+pub fn load(id: &str, on_done: impl FnOnce(Result<Project, StoreError>) + 'static) {
+    let key = JsValue::from_str(id);
+    request(
+        IdbTransactionMode::Readonly,
+        move |store| store.get(&key),
+        move |result| {
+            on_done(result.and_then(|value| Project::from_js(&value).ok_or(StoreError::Malformed)));
+        },
+    );
+}
+
+pub fn delete(id: &str, on_done: impl FnOnce(Result<(), StoreError>) + 'static) {
+    let key = JsValue::from_str(id);
+    request(
+        IdbTransactionMode::Readwrite,
+        move |store| store.delete(&key),
+        move |result| on_done(result.map(|_| ())),
+    );
+}
+
+/// Open the database, creating it on first use, and make a request against the project store.
+fn request<R, F>(mode: IdbTransactionMode, make_request: R, on_done: F)
+where
+    R: FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue> + 'static,
+    F: FnOnce(Result<JsValue, StoreError>) + 'static,
+{
+    // Exactly one of the success and error handlers of each request runs, so they share the
+    // callback.
+    let on_done = Rc::new(RefCell::new(Some(on_done)));
+    let finish = move |result| {
+        if let Some(on_done) = on_done.borrow_mut().take() {
+            on_done(result);
+        }
+    };
+    let finish = Rc::new(finish);
+
+    let open = web_sys::window()
+        .and_then(|window| window.indexed_db().ok().flatten())
+        .and_then(|factory| factory.open_with_u32(DATABASE, VERSION).ok());
+    let Some(open) = open else {
+        finish(Err(StoreError::Unavailable));
+        return;
+    };
+
+    let on_upgrade = Closure::once_into_js(|event: web_sys::Event| {
+        let database = event
+            .target()
+            .and_then(|target| target.dyn_into::<IdbOpenDbRequest>().ok())
+            .and_then(|open| open.result().ok())
+            .and_then(|database| database.dyn_into::<IdbDatabase>().ok());
+        if let Some(database) = database {
+            let mut parameters = web_sys::IdbObjectStoreParameters::new();
+            parameters.key_path(Some(&"id".into()));
+            if database
+                .create_object_store_with_optional_parameters(PROJECTS, &parameters)
+                .is_err()
+            {
+                tracing::error!("Failed to create the project store");
+            }
+        }
+    });
+    open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    let on_open_error = {
+        let finish = finish.clone();
+        Closure::once_into_js(move || finish(Err(StoreError::Unavailable)))
+    };
+    open.set_onerror(Some(on_open_error.unchecked_ref()));
+
+    let on_open = {
+        let open = open.clone();
+        Closure::once_into_js(move || {
+            let request = open
+                .result()
+                .ok()
+                .and_then(|database| database.dyn_into::<IdbDatabase>().ok())
+                .and_then(|database| database.transaction_with_str_and_mode(PROJECTS, mode).ok())
+                .and_then(|transaction| transaction.object_store(PROJECTS).ok())
+                .and_then(|store| make_request(&store).ok());
+            let Some(request) = request else {
+                finish(Err(StoreError::Request));
+                return;
+            };
+
+            let on_success = {
+                let finish = finish.clone();
+                let request = request.clone();
+                Closure::once_into_js(move || {
+                    finish(request.result().map_err(|_err| StoreError::Request));
+                })
+            };
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            let on_error = Closure::once_into_js(move || finish(Err(StoreError::Request)));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        })
+    };
+    open.set_onsuccess(Some(on_open.unchecked_ref()));
+}
@@ -1,13 +1,20 @@
-use web_sys::HtmlInputElement;
+use homotopy_graphics::style::Theme;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
-use crate::{components::delta::CallbackIdx, declare_settings};
+use crate::{app::keybindings::Keymap, components::delta::CallbackIdx, declare_settings};
+use keymap::KeymapView;
+
+mod keymap;
 
 declare_settings! {
     pub struct AppSettings {
+        theme: Theme = Theme::Light,
         show_previews: bool = true,
         weak_units: bool = false,
         animated_3d: bool = false,
+        show_labels: bool = true,
+        keymap: Keymap = Keymap::load(),
 
         cubical_subdivision: bool = true,
         dpr_scale: bool = true,
@@ -61,6 +68,15 @@ impl Component for SettingsView {
             <div class="settings">
                 <h3>{"General"}</h3>
                 <div class="settings__segment">
+                    {
+                        Self::view_select(
+                            "Theme",
+                            &Theme::ALL,
+                            AppSettings::get_theme(),
+                            Theme::name,
+                            AppSettings::set_theme,
+                        )
+                    }
                     {
                         Self::view_checkbox(
                             "Show previews in signature",
@@ -75,6 +91,13 @@ impl Component for SettingsView {
                             AppSettings::set_weak_units,
                         )
                     }
+                    {
+                        Self::view_checkbox(
+                            "Show generator labels",
+                            AppSettings::get_show_labels(),
+                            AppSettings::set_show_labels,
+                        )
+                    }
                     {
                         Self::view_checkbox(
                             "Render 3D diagrams as movies",
@@ -83,6 +106,10 @@ impl Component for SettingsView {
                         )
                     }
                 </div>
+                <h3>{"Keyboard shortcuts"}</h3>
+                <div class="settings__segment">
+                    <KeymapView />
+                </div>
                 <h3>{"3D renderer"}</h3>
                 <div class="settings__segment">
                     <h4>{"Quality"}</h4>
@@ -239,6 +266,46 @@ impl SettingsView {
         }
     }
 
+    fn view_select<T, S>(
+        name: &str,
+        choices: &'static [T],
+        current: T,
+        label: fn(T) -> &'static str,
+        setter: S,
+    ) -> Html
+    where
+        T: Copy + PartialEq + 'static,
+        S: Fn(T) + 'static,
+    {
+        let options = choices.iter().enumerate().map(|(i, &choice)| {
+            html! {
+                <option value={i.to_string()} selected={choice == current}>
+                    {label(choice)}
+                </option>
+            }
+        });
+        html! {
+            <div class="settings__select-setting">
+                {name}
+                <select
+                    onchange={Callback::from(move |e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        if let Some(&choice) = select
+                            .value()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| choices.get(i))
+                        {
+                            setter(choice);
+                        }
+                    })}
+                >
+                    {for options}
+                </select>
+            </div>
+        }
+    }
+
     fn view_slider<S>(name: &str, current: u32, setter: S, min: u32, max: u32) -> Html
     where
         S: Fn(u32) + 'static,
@@ -0,0 +1,320 @@
+use web_sys::{File, HtmlElement, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    app::{
+        keybindings::{Binding, Chord, Command, KeyStroke, Keymap},
+        AppSettings, AppSettingsKey, AppSettingsMsg,
+    },
+    components::{
+        delta::CallbackIdx,
+        toast::{toast, Toast},
+    },
+    model::generate_download,
+};
+
+pub enum Msg {
+    Changed(Keymap),
+    Record(usize),
+    Add(Command),
+    Stroke(KeyStroke),
+    Finish,
+    Cancel,
+    Remove(usize),
+    Reset,
+    Export,
+    Import(File),
+    Noop,
+}
+
+/// The chord being typed in for a binding, which replaces the binding at `index` or is added as
+/// a new binding.
+struct Recording {
+    index: Option<usize>,
+    command: Command,
+    strokes: Vec<KeyStroke>,
+}
+
+/// Edit the keyboard shortcuts: rebind, add or remove bindings, and move keymaps in and out of
+/// files.
+pub struct KeymapView {
+    keymap: Keymap,
+    recording: Option<Recording>,
+    reader: Option<gloo::file::callbacks::FileReader>,
+    recorder: NodeRef,
+    callback_idx: CallbackIdx,
+}
+
+impl Component for KeymapView {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            keymap: Keymap::current(),
+            recording: None,
+            reader: None,
+            recorder: NodeRef::default(),
+            callback_idx: AppSettings::subscribe(
+                &[AppSettingsKey::keymap],
+                ctx.link().callback(|msg| match msg {
+                    AppSettingsMsg::keymap(keymap) => Msg::Changed(keymap),
+                    _ => Msg::Noop,
+                }),
+            )[0],
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        AppSettings::unsubscribe(&[AppSettingsKey::keymap], &[self.callback_idx]);
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(recorder) = self.recorder.cast::<HtmlElement>() {
+            recorder.focus().unwrap_or_default();
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Changed(keymap) => {
+                self.keymap = keymap;
+                true
+            }
+            Msg::Record(index) => {
+                self.recording = self.keymap.bindings.get(index).map(|b| Recording {
+                    index: Some(index),
+                    command: b.command.clone(),
+                    strokes: vec![],
+                });
+                true
+            }
+            Msg::Add(command) => {
+                self.recording = Some(Recording {
+                    index: None,
+                    command,
+                    strokes: vec![],
+                });
+                true
+            }
+            Msg::Stroke(stroke) => {
+                if let Some(recording) = &mut self.recording {
+                    recording.strokes.push(stroke);
+                }
+                true
+            }
+            Msg::Finish => {
+                let Some(recording) = self.recording.take() else { return false };
+                // Leaving a binding without pressing anything keeps its old keys.
+                let Ok(keys) = Chord::new(recording.strokes) else { return true };
+                let mut keymap = self.keymap.clone();
+                let binding = Binding {
+                    keys,
+                    command: recording.command,
+                };
+                match recording.index {
+                    Some(index) => keymap.bindings[index] = binding,
+                    None => keymap.bindings.push(binding),
+                }
+                Keymap::set(keymap);
+                true
+            }
+            Msg::Cancel => {
+                self.recording = None;
+                true
+            }
+            Msg::Remove(index) => {
+                let mut keymap = self.keymap.clone();
+                keymap.bindings.remove(index);
+                Keymap::set(keymap);
+                false
+            }
+            Msg::Reset => {
+                self.recording = None;
+                Keymap::set(Keymap::default());
+                false
+            }
+            Msg::Export => {
+                let exported = self
+                    .keymap
+                    .to_json()
+                    .map(|json| generate_download("homotopy_io_keymap", "json", json.as_bytes()));
+                if !matches!(exported, Ok(Ok(()))) {
+                    toast(Toast::error("Failed to export keymap"));
+                }
+                false
+            }
+            Msg::Import(file) => {
+                let task =
+                    gloo::file::callbacks::read_as_text(&file.into(), |res: Result<String, _>| {
+                        match res.map(|json| Keymap::from_json(&json)) {
+                            Ok(Ok(keymap)) => {
+                                let conflicts = keymap.conflicts().len();
+                                if conflicts > 0 {
+                                    toast(Toast::warn(format!(
+                                        "Imported keymap has {conflicts} conflicting shortcuts"
+                                    )));
+                                } else {
+                                    toast(Toast::success("Imported keymap"));
+                                }
+                                Keymap::set(keymap);
+                            }
+                            Ok(Err(error)) => toast(Toast::error(error.to_string())),
+                            Err(_) => toast(Toast::error("Failed to read keymap file")),
+                        }
+                    });
+                self.reader = Some(task);
+                false
+            }
+            Msg::Noop => false,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let conflicts = self.keymap.conflicts();
+        let rows = self.keymap.bindings.iter().enumerate().map(|(i, binding)| {
+            let conflict = conflicts.iter().any(|c| c.0 == i || c.1 == i);
+            html! {
+                <tr class={classes!(conflict.then_some("settings__binding--conflict"))}>
+                    <td>{binding.command.label()}</td>
+                    <td>{self.view_keys(ctx, i, &binding.keys)}</td>
+                    <td>
+                        <button
+                            class="settings__binding-remove"
+                            title="Remove shortcut"
+                            onclick={ctx.link().callback(move |_| Msg::Remove(i))}
+                        >
+                            {"×"}
+                        </button>
+                    </td>
+                </tr>
+            }
+        });
+        let added = self
+            .recording
+            .as_ref()
+            .filter(|recording| recording.index.is_none())
+            .map(|recording| {
+                html! {
+                    <tr>
+                        <td>{recording.command.label()}</td>
+                        <td>{self.view_recorder(ctx, recording)}</td>
+                        <td />
+                    </tr>
+                }
+            });
+        let warnings = conflicts.iter().map(|&conflict| {
+            html! {
+                <li>{self.keymap.describe(conflict)}</li>
+            }
+        });
+
+        let presets = Command::presets();
+        let options: Html = presets
+            .iter()
+            .enumerate()
+            .map(|(i, command)| {
+                html! {
+                    <option value={i.to_string()}>{command.label()}</option>
+                }
+            })
+            .collect();
+        let add = ctx.link().callback(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let command = select
+                .value()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| presets.get(i).cloned());
+            select.set_value("");
+            command.map_or(Msg::Noop, Msg::Add)
+        });
+        let import = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            match input.files().and_then(|files| files.get(0)) {
+                Some(file) => Msg::Import(file),
+                None => Msg::Noop,
+            }
+        });
+
+        html! {
+            <div class="settings__keymap">
+                <table>
+                    {for rows}
+                    {for added}
+                </table>
+                if !conflicts.is_empty() {
+                    <ul class="settings__keymap-conflicts">{for warnings}</ul>
+                }
+                <select class="settings__keymap-add" onchange={add}>
+                    <option value="" selected={true}>{"Add shortcut…"}</option>
+                    {options}
+                </select>
+                <div class="settings__keymap-actions">
+                    <button onclick={ctx.link().callback(|_| Msg::Reset)}>{"Reset"}</button>
+                    <button onclick={ctx.link().callback(|_| Msg::Export)}>{"Export"}</button>
+                    <label for="import-keymap" class="button">{"Import"}</label>
+                    <input
+                        type="file"
+                        accept=".json,application/json"
+                        class="visually-hidden"
+                        id="import-keymap"
+                        onchange={import}
+                    />
+                </div>
+            </div>
+        }
+    }
+}
+
+impl KeymapView {
+    fn view_keys(&self, ctx: &Context<Self>, index: usize, keys: &Chord) -> Html {
+        match &self.recording {
+            Some(recording) if recording.index == Some(index) => self.view_recorder(ctx, recording),
+            _ => {
+                let record = ctx.link().callback(move |_| Msg::Record(index));
+                html! {
+                    <button class="settings__chord" title="Change shortcut" onclick={record}>
+                        {keys.label()}
+                    </button>
+                }
+            }
+        }
+    }
+
+    fn view_recorder(&self, ctx: &Context<Self>, recording: &Recording) -> Html {
+        // The window listener for shortcuts must not see the keys being recorded.
+        let on_key_down = ctx.link().callback(|e: KeyboardEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            match e.key().as_str() {
+                "Enter" => Msg::Finish,
+                "Escape" => Msg::Cancel,
+                _ => KeyStroke::from_event(&e).map_or(Msg::Noop, Msg::Stroke),
+            }
+        });
+        let on_key_up = Callback::from(|e: KeyboardEvent| e.stop_propagation());
+        let prompt = if recording.strokes.is_empty() {
+            "Press keys, then Enter".to_owned()
+        } else {
+            recording
+                .strokes
+                .iter()
+                .map(KeyStroke::label)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        html! {
+            <button
+                class="settings__chord settings__chord--recording"
+                ref={self.recorder.clone()}
+                onkeydown={on_key_down}
+                onkeyup={on_key_up}
+                onblur={ctx.link().callback(|_| Msg::Finish)}
+            >
+                {prompt}
+            </button>
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use homotopy_core::Diagram;
+use yew::prelude::*;
+use yew_macro::function_component;
+
+use crate::{
+    app::{diagram_svg::DiagramSvg, Icon, IconSize},
+    model::proof::{
+        shelf::{Shelf, ShelfEdit},
+        Action, Signature,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ShelfProps {
+    pub shelf: Shelf,
+    pub signature: Signature,
+    pub dispatch: Callback<Action>,
+}
+
+#[function_component(ShelfView)]
+pub fn shelf_view(props: &ShelfProps) -> Html {
+    if props.shelf.is_empty() {
+        return html! {
+            <div class="shelf__empty">
+                {"Copy the workspace to keep a diagram around for later."}
+            </div>
+        };
+    }
+
+    let item = |index: usize, name: &String, diagram: &Diagram| -> Html {
+        let id = format!("shelf__preview-{index}");
+        let preview = match diagram.dimension() {
+            0 => view_diagram_svg::<0>(diagram, id, &props.signature),
+            1 => view_diagram_svg::<1>(diagram, id, &props.signature),
+            _ => view_diagram_svg::<2>(diagram, id, &props.signature),
+        };
+
+        let paste = props
+            .dispatch
+            .reform(move |_| Action::PasteFromShelf(index));
+        let rename = {
+            let dispatch = props.dispatch.clone();
+            let name = name.clone();
+            Callback::from(move |_| {
+                if let Some(name) = gloo::dialogs::prompt("Rename diagram", Some(&name)) {
+                    dispatch.emit(Action::EditShelf(ShelfEdit::Rename(index, name)));
+                }
+            })
+        };
+        let remove = props
+            .dispatch
+            .reform(move |_| Action::EditShelf(ShelfEdit::Remove(index)));
+
+        html! {
+            <li class="shelf__item">
+                <div class="shelf__item-header">
+                    <span class="shelf__item-name" ondblclick={rename}>{name}</span>
+                    <span class="shelf__item-button" onclick={remove}>
+                        <Icon name="delete" size={IconSize::Icon18} />
+                    </span>
+                </div>
+                <div class="shelf__item-preview" onclick={paste}>
+                    {preview}
+                </div>
+            </li>
+        }
+    };
+
+    let items: Html = props
+        .shelf
+        .iter()
+        .enumerate()
+        .map(|(index, shelf_item)| item(index, &shelf_item.name, &shelf_item.diagram))
+        .collect();
+
+    html! {
+        <ul class="shelf">
+            {items}
+        </ul>
+    }
+}
+
+fn view_diagram_svg<const N: usize>(diagram: &Diagram, id: String, signature: &Signature) -> Html {
+    html! {
+        <DiagramSvg<N>
+                diagram={diagram.clone()}
+                id={id}
+                signature={signature.clone()}
+                max_width={Some(160.0)}
+                max_height={Some(160.0)}
+        />
+    }
+}
@@ -5,8 +5,13 @@ use yew::prelude::*;
 use yew_macro::function_component;
 
 use crate::{
-    app::{attach::AttachView, keybindings::Keybindings},
+    app::{
+        attach::AttachView,
+        keybindings::{is_typing, KeyStroke, Keymap, Lookup},
+        AppSettings, AppSettingsKey,
+    },
     components::{
+        delta::CallbackIdx,
         icon::{Icon, IconSize},
         Visibility,
     },
@@ -22,7 +27,7 @@ pub struct SidebarButtonProps {
     pub icon: &'static str,
     pub action: SidebarMsg,
     #[prop_or_default]
-    pub shortcut: Option<&'static str>,
+    pub shortcut: Option<String>,
     pub dispatch: Callback<SidebarMsg>,
     #[prop_or(Visibility::Visible)]
     pub visibility: Visibility,
@@ -37,8 +42,8 @@ pub fn sidebar_button(props: &SidebarButtonProps) -> Html {
             class="sidebar__button tooltip tooltip--right"
             onclick={props.dispatch.reform(move |_| action.clone())}
             data-tooltip={
-                if let Some(shortcut) = props.shortcut {
-                    format!("{} ({})", props.label, shortcut.to_uppercase())
+                if let Some(shortcut) = &props.shortcut {
+                    format!("{} ({shortcut})", props.label)
                 } else {
                     props.label.to_owned()
                 }
@@ -272,6 +277,7 @@ pub enum SidebarMsg {
     SaveDrawerWidth(i32),
     ResizeDrawerView(DrawerViewSize),
     Toggle(Option<drawers::NavDrawer>),
+    KeymapChanged,
 }
 
 pub struct Sidebar {
@@ -280,6 +286,7 @@ pub struct Sidebar {
     open: Option<drawers::NavDrawer>,
     // Hold onto bindings so that they are dropped when the app is destroyed
     keybindings: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    keymap_callback: Option<CallbackIdx>,
 }
 
 impl Default for Sidebar {
@@ -289,6 +296,7 @@ impl Default for Sidebar {
             drawer_view_size: DrawerViewSize::from(SidebarDrawer::DEFAULT_WIDTH),
             open: Default::default(),
             keybindings: Default::default(),
+            keymap_callback: Default::default(),
         }
     }
 }
@@ -300,9 +308,22 @@ impl Component for Sidebar {
     fn create(ctx: &Context<Self>) -> Self {
         let mut sidebar = Sidebar::default();
         sidebar.install_keyboard_shortcuts(ctx);
+        // Tooltips show the current shortcuts.
+        sidebar.keymap_callback = Some(
+            AppSettings::subscribe(
+                &[AppSettingsKey::keymap],
+                ctx.link().callback(|_| SidebarMsg::KeymapChanged),
+            )[0],
+        );
         sidebar
     }
 
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(idx) = self.keymap_callback.take() {
+            AppSettings::unsubscribe(&[AppSettingsKey::keymap], &[idx]);
+        }
+    }
+
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             SidebarMsg::SaveDrawerWidth(width) => {
@@ -338,6 +359,7 @@ impl Component for Sidebar {
                 ctx.props().dispatch.emit(action);
                 false
             }
+            SidebarMsg::KeymapChanged => true,
         }
     }
 
@@ -398,17 +420,44 @@ impl Sidebar {
     }
 
     fn install_keyboard_shortcuts(&mut self, ctx: &Context<Self>) {
+        // Strokes further apart than this do not make up a chord.
+        const CHORD_TIMEOUT: f64 = 1000.0; // ms
+
         let dispatch = ctx.link().callback(SidebarMsg::Dispatch);
+        let mut pending: Vec<KeyStroke> = Vec::new();
+        let mut last_stroke = 0.0;
         let keybindings = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
-            let key = event.key().to_ascii_lowercase();
-            if let Some(action) = Keybindings::get_action(&key) {
-                dispatch.emit(action);
+            if event.repeat() || is_typing(&event) {
+                return;
+            }
+            let Some(stroke) = KeyStroke::from_event(&event) else { return };
+            if event.time_stamp() - last_stroke > CHORD_TIMEOUT {
+                pending.clear();
+            }
+            last_stroke = event.time_stamp();
+            pending.push(stroke);
+
+            let keymap = Keymap::current();
+            let mut lookup = keymap.lookup(&pending);
+            if matches!(lookup, Lookup::Unbound) && pending.len() > 1 {
+                // The stroke that broke the chord may still start another one.
+                pending.drain(..pending.len() - 1);
+                lookup = keymap.lookup(&pending);
+            }
+            match lookup {
+                Lookup::Bound(command) => {
+                    event.prevent_default();
+                    command.run(&dispatch);
+                    pending.clear();
+                }
+                Lookup::Pending => event.prevent_default(),
+                Lookup::Unbound => pending.clear(),
             }
         }) as Box<dyn FnMut(_)>);
 
         web_sys::window()
             .unwrap()
-            .add_event_listener_with_callback("keyup", keybindings.as_ref().unchecked_ref())
+            .add_event_listener_with_callback("keydown", keybindings.as_ref().unchecked_ref())
             .unwrap();
 
         self.keybindings = Some(keybindings);
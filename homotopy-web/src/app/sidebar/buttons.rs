@@ -6,7 +6,12 @@ use paste::paste;
 use yew::prelude::*;
 
 use super::{Sidebar, SidebarButton, SidebarMsg};
-use crate::{app::keybindings::Keybindings, components::Visibility, model, model::history};
+use crate::{
+    app::keybindings::{Chord, Keymap},
+    components::Visibility,
+    model,
+    model::history,
+};
 
 macro_rules! declare_sidebar_tools {
     ($($name:ident {
@@ -19,13 +24,14 @@ macro_rules! declare_sidebar_tools {
                 pub(super) fn tools(&self, ctx: &Context<Self>) -> Html {
                     let dispatch = &ctx.link().callback(|x| x);
                     let proof = &ctx.props().proof;
+                    let keymap = Keymap::current();
                     html! {
                         <nav class="sidebar__tools">
                             $(<SidebarButton
                                 label={$label}
                                 icon={$icon}
                                 action={SidebarMsg::Dispatch($action)}
-                                shortcut={Keybindings::get_shortcut($action)}
+                                shortcut={keymap.shortcut(&$action).map(Chord::label)}
                                 dispatch={dispatch}
                                 visibility={Visibility::from($action.is_valid(proof))}
                             />)*
@@ -5,8 +5,8 @@ use super::{DrawerViewSize, Sidebar, SidebarButton, SidebarDrawer, SidebarMsg};
 use crate::app::debug::DebugView;
 use crate::{
     app::{
-        image_export::ImageExportView, project::ProjectView, settings::SettingsView,
-        signature::SignatureView,
+        attach::AttachCandidatesView, image_export::ImageExportView, project::ProjectView,
+        projects::ProjectsView, settings::SettingsView, shelf::ShelfView, signature::SignatureView,
     },
     components::Visible,
     model::{
@@ -130,6 +130,16 @@ declare_sidebar_drawers! {
         min_width: 250,
     }
 
+    DRAWER_PROJECTS {
+        "Projects",
+        "projects",
+        "folder_open",
+        |dispatch, _, _| html! {
+            <ProjectsView dispatch={dispatch} />
+        },
+        min_width: 250,
+    }
+
     DRAWER_SIGNATURE {
         "Signature",
         "signature",
@@ -139,6 +149,7 @@ declare_sidebar_drawers! {
                 signature={proof.signature.clone()}
                 dispatch={dispatch.reform(model::Action::Proof)}
                 drawer_view_size={drawer_view_size}
+                on_export={dispatch.reform(model::Action::ExportLibrary)}
             />
         },
         min_width: 250,
@@ -146,6 +157,44 @@ declare_sidebar_drawers! {
         top_icon_action: |proof: &Proof| model::Action::Proof(Action::EditSignature(SignatureEdit::NewFolder(proof.signature.as_tree().root()))),
     }
 
+    DRAWER_ATTACH {
+        "Attach",
+        "attach",
+        "add_link",
+        |dispatch: &Callback<model::Action>, proof: &Proof, _| {
+            // Candidates for the interior of the workspace diagram and for each of its boundaries.
+            let dimension = proof.workspace.as_ref().map_or(0, |ws| ws.diagram.dimension() + 1);
+            let candidates = (1..=dimension)
+                .flat_map(|dimension| proof.attach_candidates(dimension, false))
+                .collect::<Vec<_>>();
+            html! {
+                <AttachCandidatesView
+                    dispatch={dispatch}
+                    candidates={candidates}
+                    diagram={proof.workspace.as_ref().map(|ws| ws.diagram.clone())}
+                    signature={proof.signature.clone()}
+                />
+            }
+        },
+        min_width: 250,
+    }
+
+    DRAWER_SHELF {
+        "Shelf",
+        "shelf",
+        "inventory_2",
+        |dispatch: &Callback<model::Action>, proof: &Proof, _| html! {
+            <ShelfView
+                shelf={proof.shelf.clone()}
+                signature={proof.signature.clone()}
+                dispatch={dispatch.reform(model::Action::Proof)}
+            />
+        },
+        min_width: 250,
+        top_icon: "add_to_photos",
+        top_icon_action: |_: &Proof| model::Action::Proof(Action::CopyToShelf),
+    }
+
     DRAWER_IMAGE_EXPORT {
         "Image export",
         "ImageExport",
@@ -154,6 +203,7 @@ declare_sidebar_drawers! {
             <ImageExportView
                 dispatch={dispatch}
                 view_dim={proof.workspace.as_ref().map_or(0, |ws| ws.view.dimension())}
+                visible_dim={proof.workspace.as_ref().map_or(0, |ws| ws.visible_dimension())}
             />
         },
         min_width: 250,
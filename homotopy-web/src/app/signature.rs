@@ -1,31 +1,78 @@
 use folder::FolderView;
+use homotopy_common::tree::Node;
+use palette::PaletteView;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_macro::function_component;
 
 use crate::{
     app::sidebar::DrawerViewSize,
-    model::proof::{Action, Signature},
+    model::proof::{generators::GeneratorFilter, Action, Signature},
 };
 
 mod folder;
 mod item;
+mod palette;
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     pub signature: Signature,
     pub dispatch: Callback<Action>,
     pub drawer_view_size: DrawerViewSize,
+    /// Export part of the signature as a library.
+    pub on_export: Callback<Vec<Node>>,
 }
 
 #[function_component(SignatureView)]
 pub fn signature_view(props: &Props) -> Html {
-    // TODO: Add search
     // TODO: On mobile, drag to the side to delete
+    let query = use_state(String::new);
+    let filter = GeneratorFilter::parse(&query);
+    let search = (!filter.is_empty()).then(|| props.signature.search(&filter));
+    let on_input = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+    let empty = search
+        .as_ref()
+        .map_or(false, |search| search.matches.is_empty());
+    let export_matches = search.as_ref().filter(|_| !empty).map(|search| {
+        let matches: Vec<_> = search.matches.iter().copied().collect();
+        props.on_export.reform(move |_: MouseEvent| matches.clone())
+    });
+
     html! {
-        <FolderView
-            dispatch={props.dispatch.clone()}
-            signature={props.signature.clone()}
-            drawer_view_size={props.drawer_view_size}
-        />
+        <>
+            <PaletteView
+                palette={props.signature.palette().clone()}
+                dispatch={props.dispatch.clone()}
+            />
+            <input
+                class="signature__search"
+                type="search"
+                placeholder="Search, e.g. unit dim:2 is:invertible #axiom"
+                title="Filter by name or tag, dim:n, is: or not: invertible or oriented, and #tag"
+                value={(*query).clone()}
+                oninput={on_input}
+            />
+            if empty {
+                <div class="signature__search-empty">{"No matching generators"}</div>
+            }
+            if let Some(export_matches) = export_matches {
+                <button class="signature__search-export" onclick={export_matches}>
+                    {"Export matches as library"}
+                </button>
+            }
+            <FolderView
+                dispatch={props.dispatch.clone()}
+                signature={props.signature.clone()}
+                drawer_view_size={props.drawer_view_size}
+                search={search}
+                on_export={props.on_export.clone()}
+            />
+        </>
     }
 }
@@ -5,7 +5,7 @@ use super::item::ItemView;
 use crate::{
     app::sidebar::DrawerViewSize,
     components::{add_class, remove_class},
-    model::proof::{Action, Signature, SignatureEdit, SignatureItem},
+    model::proof::{Action, Signature, SignatureEdit, SignatureItem, SignatureSearch},
 };
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -19,6 +19,10 @@ pub struct Props {
     pub dispatch: Callback<Action>,
     pub signature: Signature,
     pub drawer_view_size: DrawerViewSize,
+    /// While searching, only the visible part of the tree is shown, with every folder on it open.
+    #[prop_or_default]
+    pub search: Option<SignatureSearch>,
+    pub on_export: Callback<Vec<Node>>,
 }
 
 fn on_valid_callback<F>(props: &Props, node: Node, f: F) -> Callback<DragEvent>
@@ -110,6 +114,11 @@ impl Component for FolderView {
 }
 
 fn render_drop_zone(props: &Props, node: Node, position: DropPosition) -> Html {
+    // Items hidden by a search would make the drop position ambiguous.
+    if props.search.is_some() {
+        return Html::default();
+    }
+
     let drop_zone_ref = NodeRef::default();
     let on_drag_enter = {
         let drop_zone_ref = drop_zone_ref.clone();
@@ -164,6 +173,7 @@ fn render_item(props: &Props, node: Node) -> Html {
                         on_drag_enter={on_drag_enter(props, node)}
                         on_drop={on_drop(props, node, DropPosition::After)}
                         on_drag_start={on_drag_start(node)}
+                        on_export={props.on_export.reform(move |_: MouseEvent| vec![node])}
                     />
                 }
             }
@@ -188,8 +198,16 @@ fn render_children(props: &Props, node: Node) -> Html {
     let contents = props.signature.as_tree();
     contents
         .with(node, move |n| match n.inner() {
-            SignatureItem::Folder(info) if info.open => {
-                let children = n.children().map(|child| render_tree(props, child));
+            SignatureItem::Folder(info) if info.open || props.search.is_some() => {
+                let children = n
+                    .children()
+                    .filter(|child| {
+                        props
+                            .search
+                            .as_ref()
+                            .map_or(true, |search| search.visible.contains(child))
+                    })
+                    .map(|child| render_tree(props, child));
                 let class = format!(
                     "signature__branch {}",
                     if n.is_empty() {
@@ -212,10 +230,15 @@ fn render_children(props: &Props, node: Node) -> Html {
 }
 
 fn render_tree(props: &Props, node: Node) -> Html {
+    let matched = props
+        .search
+        .as_ref()
+        .map_or(false, |search| search.matches.contains(&node));
+
     html! {
         <>
             {render_drop_zone(props, node, DropPosition::Before)}
-            <li>
+            <li class={classes!(matched.then_some("signature__match"))}>
                 {render_item(props, node)}
                 {render_children(props, node)}
             </li>
@@ -2,9 +2,9 @@ use std::str::FromStr;
 
 use homotopy_common::tree::Node;
 use homotopy_core::Diagram;
-use homotopy_graphics::style::{Color, VertexShape};
+use homotopy_graphics::style::{Color, DashPattern, SurfaceStyle, VertexShape, WireStyle};
 use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlInputElement};
+use web_sys::{Element, HtmlInputElement, HtmlTextAreaElement};
 use yew::prelude::*;
 use yew_macro::function_component;
 
@@ -19,7 +19,7 @@ use crate::{
     },
     model::proof::{
         generators::GeneratorInfo, Action, Signature, SignatureEdit, SignatureItem,
-        SignatureItemEdit, COLORS, VERTEX_SHAPES,
+        SignatureItemEdit,
     },
 };
 
@@ -121,6 +121,9 @@ pub struct ItemViewProps {
     pub on_drop: Callback<DragEvent>,
     #[prop_or_default]
     pub on_drag_start: Callback<DragEvent>,
+    /// Export a folder as a library.
+    #[prop_or_default]
+    pub on_export: Callback<MouseEvent>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -191,11 +194,14 @@ fn custom_recolor_button(props: &CustomRecolorButtonProps) -> Html {
     }
 }
 
+const ITEM_SUBSCRIPTIONS: &[AppSettingsKey] =
+    &[AppSettingsKey::show_previews, AppSettingsKey::theme];
+
 pub struct ItemView {
     mode: ItemViewMode,
     name: String,
     preview_cache: Option<Preview>,
-    callback_idx: CallbackIdx,
+    callback_idxs: Vec<CallbackIdx>,
 }
 
 impl Component for ItemView {
@@ -203,12 +209,10 @@ impl Component for ItemView {
     type Properties = ItemViewProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        const ITEM_SUBSCRIPTIONS: &[AppSettingsKey] = &[AppSettingsKey::show_previews];
-
-        let callback_idx = AppSettings::subscribe(
+        let callback_idxs = AppSettings::subscribe(
             ITEM_SUBSCRIPTIONS,
             ctx.link().callback(ItemViewMessage::Setting),
-        )[0];
+        );
 
         let name = match &ctx.props().item {
             SignatureItem::Item(info) => info.name.clone(),
@@ -218,7 +222,7 @@ impl Component for ItemView {
             mode: Default::default(),
             name,
             preview_cache: Default::default(),
-            callback_idx,
+            callback_idxs,
         }
     }
 
@@ -239,7 +243,9 @@ impl Component for ItemView {
                 self.cache_preview(ctx, show_single_preview, &diagram);
                 return true;
             }
-            ItemViewMessage::Setting(AppSettingsMsg::show_previews(_)) => {
+            ItemViewMessage::Setting(
+                AppSettingsMsg::show_previews(_) | AppSettingsMsg::theme(_),
+            ) => {
                 return true;
             }
             ItemViewMessage::Setting(_) | ItemViewMessage::Noop => {}
@@ -249,8 +255,7 @@ impl Component for ItemView {
     }
 
     fn destroy(&mut self, _ctx: &Context<Self>) {
-        const ITEM_SUBSCRIPTIONS: &[AppSettingsKey] = &[AppSettingsKey::show_previews];
-        AppSettings::unsubscribe(ITEM_SUBSCRIPTIONS, &[self.callback_idx]);
+        AppSettings::unsubscribe(ITEM_SUBSCRIPTIONS, &self.callback_idxs);
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -279,6 +284,7 @@ impl Component for ItemView {
                 <>
                     {self.view_preferences(ctx, info)}
                     {self.view_picker(ctx, info)}
+                    {self.view_details(ctx, info)}
                 </>
             }
         } else {
@@ -400,7 +406,7 @@ impl ItemView {
             let style = format!("background-color: {}", info.color.hex());
             let class = format!(
                 "signature__generator-color-sliver {}",
-                if info.color.is_light() {
+                if AppSettings::get_theme().is_faint(&info.color) {
                     "signature__generator-color-sliver-light"
                 } else {
                     ""
@@ -496,15 +502,17 @@ impl ItemView {
         }
 
         let selected_color = info.color.clone();
-        let color_preset_buttons = COLORS.iter().map(|color| {
+        let color_preset_buttons = ctx.props().signature.palette().colors.iter().map(|color| {
+            let hex = color.hex();
+            let color = color.clone();
             let recolor = ctx.link().callback(move |_| {
-                ItemViewMessage::Edit(SignatureItemEdit::Recolor(Color::from_str(color).unwrap()))
+                ItemViewMessage::Edit(SignatureItemEdit::Recolor(color.clone()))
             });
 
             html! {
                 <div
                     class="signature__generator-picker-preset"
-                    style={format!("background: {color}")}
+                    style={format!("background: {hex}")}
                     onclick={recolor}
                 />
             }
@@ -524,20 +532,26 @@ impl ItemView {
         });
 
         let selected_shape = info.shape.clone();
-        let shape_preset_buttons = VERTEX_SHAPES.iter().map(|shape| {
-            let reshape = ctx.link().callback(move |_| {
-                ItemViewMessage::Edit(SignatureItemEdit::Reshape(shape.clone()))
-            });
+        let shape_preset_buttons = VertexShape::ALL.into_iter().map(|shape| {
             let icon_name = match shape {
                 VertexShape::Circle => "circle",
                 VertexShape::Square => "square",
+                VertexShape::Diamond => "diamond",
+                VertexShape::Triangle => "change_history",
+                VertexShape::Star => "star",
+                VertexShape::Hollow => "radio_button_unchecked",
+                VertexShape::LabelledBox => "crop_din",
             };
-            let icon_class = if *shape == selected_shape {
+            let icon_class = if shape == selected_shape {
                 ""
             } else {
                 "md-inactive"
             };
 
+            let reshape = ctx.link().callback(move |_| {
+                ItemViewMessage::Edit(SignatureItemEdit::Reshape(shape.clone()))
+            });
+
             html! {
                 <div class="signature__generator-picker-preset" onclick={reshape}>
                     <Icon name={icon_name} size={IconSize::Icon18} class={icon_class} />
@@ -545,11 +559,87 @@ impl ItemView {
             }
         });
 
+        let wire = info.wire;
+        let dash_preset_buttons = DashPattern::ALL.into_iter().map(|dash| {
+            let restyle = ctx.link().callback(move |_| {
+                ItemViewMessage::Edit(SignatureItemEdit::SetWireStyle(WireStyle { dash, ..wire }))
+            });
+            // Draw a sample of the pattern, as there are no icons for it.
+            let dashes: Vec<_> = WireStyle { dash, ..wire }
+                .dash_array(3.0)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            let class = if dash == wire.dash { "" } else { "md-inactive" };
+
+            html! {
+                <div class="signature__generator-picker-preset" onclick={restyle}>
+                    <svg width="18" height="18" class={class}>
+                        <line
+                            x1="3" y1="9" x2="15" y2="9"
+                            stroke="currentColor"
+                            stroke-width="3"
+                            stroke-linecap="round"
+                            stroke-dasharray={(!dashes.is_empty()).then(|| dashes.join(" "))}
+                        />
+                    </svg>
+                </div>
+            }
+        });
+        let rethicken = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input
+                .value()
+                .parse()
+                .map_or(ItemViewMessage::Noop, |thickness| {
+                    ItemViewMessage::Edit(SignatureItemEdit::SetWireStyle(WireStyle {
+                        thickness,
+                        ..wire
+                    }))
+                })
+        });
+        let reopacify = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input
+                .value()
+                .parse()
+                .map_or(ItemViewMessage::Noop, |opacity| {
+                    ItemViewMessage::Edit(SignatureItemEdit::SetSurfaceStyle(SurfaceStyle {
+                        opacity,
+                    }))
+                })
+        });
+
         html! {
             <>
                 <div class="signature__generator-picker signature__generator-picker-shape">
                     {for shape_preset_buttons}
                 </div>
+                <div class="signature__generator-picker signature__generator-picker-style">
+                    {for dash_preset_buttons}
+                    <label title="Wire thickness">
+                        <Icon name="line_weight" size={IconSize::Icon18} />
+                        <input
+                            type="range"
+                            min="25"
+                            max="300"
+                            step="25"
+                            value={wire.thickness.to_string()}
+                            onchange={rethicken}
+                        />
+                    </label>
+                    <label title="Surface opacity">
+                        <Icon name="opacity" size={IconSize::Icon18} />
+                        <input
+                            type="range"
+                            min="10"
+                            max="100"
+                            step="10"
+                            value={info.surface.opacity.to_string()}
+                            onchange={reopacify}
+                        />
+                    </label>
+                </div>
                 <div class="signature__generator-picker signature__generator-picker-color">
                     {for color_preset_buttons}
                     <CustomRecolorButton
@@ -569,6 +659,102 @@ impl ItemView {
         }
     }
 
+    fn view_details(&self, ctx: &Context<Self>, info: &GeneratorInfo) -> Html {
+        if self.mode != ItemViewMode::Editing {
+            return html! {};
+        }
+
+        // Keyboard shortcuts should not fire while typing, and pressing enter in a single line
+        // input commits its value through `onchange`.
+        let on_key_up = ctx.link().callback(|e: KeyboardEvent| {
+            e.stop_propagation();
+            ItemViewMessage::Noop
+        });
+        let on_add = |edit: fn(String) -> SignatureItemEdit| {
+            ctx.link().callback(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                let value = input.value().trim().to_owned();
+                input.set_value("");
+                if value.is_empty() {
+                    ItemViewMessage::Noop
+                } else {
+                    ItemViewMessage::Edit(edit(value))
+                }
+            })
+        };
+
+        let tags = info.tags.iter().map(|tag| {
+            let remove = ctx.link().callback({
+                let tag = tag.clone();
+                move |_| ItemViewMessage::Edit(SignatureItemEdit::RemoveTag(tag.clone()))
+            });
+            html! {
+                <span class="signature__generator-tag">
+                    {tag}
+                    <span class="signature__generator-tag-remove" onclick={remove}>
+                        <Icon name="close" size={IconSize::Icon18} />
+                    </span>
+                </span>
+            }
+        });
+
+        let citations = info.citations.iter().enumerate().map(|(index, citation)| {
+            let remove = ctx
+                .link()
+                .callback(move |_| ItemViewMessage::Edit(SignatureItemEdit::RemoveCitation(index)));
+            html! {
+                <li class="signature__generator-citation">
+                    <TexSpan raw_tex={citation.clone()} />
+                    <span class="signature__generator-tag-remove" onclick={remove}>
+                        <Icon name="close" size={IconSize::Icon18} />
+                    </span>
+                </li>
+            }
+        });
+
+        html! {
+            <div class="signature__generator-details">
+                <input
+                    type="text"
+                    placeholder="Label (LaTeX)"
+                    value={info.label.clone().unwrap_or_default()}
+                    onchange={ctx.link().callback(|e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        ItemViewMessage::Edit(SignatureItemEdit::Relabel(input.value()))
+                    })}
+                    onkeyup={on_key_up.clone()}
+                />
+                <textarea
+                    placeholder="Description"
+                    value={info.description.clone().unwrap_or_default()}
+                    onchange={ctx.link().callback(|e: Event| {
+                        let input: HtmlTextAreaElement = e.target_unchecked_into();
+                        ItemViewMessage::Edit(SignatureItemEdit::Describe(input.value()))
+                    })}
+                    onkeyup={on_key_up.clone()}
+                />
+                <div class="signature__generator-tags">
+                    {for tags}
+                    <input
+                        type="text"
+                        placeholder="Add tag"
+                        onchange={on_add(SignatureItemEdit::AddTag)}
+                        onkeyup={on_key_up.clone()}
+                    />
+                </div>
+                <ul class="signature__generator-citations">
+                    {for citations}
+                </ul>
+                <input
+                    type="text"
+                    placeholder="Add citation"
+                    onchange={on_add(SignatureItemEdit::AddCitation)}
+                    onkeyup={on_key_up}
+                />
+            </div>
+        }
+    }
+
     fn view_info(&self, ctx: &Context<Self>) -> Html {
         match &ctx.props().item {
             SignatureItem::Item(_info) => self.view_name(ctx),
@@ -703,6 +889,10 @@ impl ItemView {
                 }
                 ItemViewMode::Editing => html! {
                     <>
+                        <ItemViewButton
+                            icon={"file_download"}
+                            on_click={ctx.props().on_export.clone()}
+                        />
                         <ItemViewButton icon={"delete"} on_click={
                             ctx.props().dispatch.reform(
                                 move |_| Action::EditSignature(SignatureEdit::Remove(node))
@@ -802,7 +992,7 @@ impl ItemView {
         let toggle_invertible = ctx.link().callback(toggle_or_noop!(MakeInvertible));
         let toggle_framed = ctx.link().callback(toggle_or_noop!(MakeOriented));
 
-        let color = if info.color.is_light() {
+        let color = if AppSettings::get_theme().is_faint(&info.color) {
             "var(--drawer-foreground)".to_owned()
         } else {
             info.color.hex()
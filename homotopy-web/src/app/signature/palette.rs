@@ -0,0 +1,142 @@
+use closure::closure;
+use homotopy_graphics::style::Palette;
+use web_sys::{File, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    components::toast::{toast, Toast},
+    model::proof::{Action, SignatureEdit},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Msg {
+    Select(usize),
+    Import(File),
+    Noop,
+}
+
+#[derive(Debug, Properties, Clone, PartialEq)]
+pub struct Props {
+    pub palette: Palette,
+    pub dispatch: Callback<Action>,
+}
+
+/// Choose the palette new generators are coloured from, either one of the built-in palettes or
+/// one read from a file.
+#[derive(Debug, Default)]
+pub struct PaletteView {
+    reader: Option<gloo::file::callbacks::FileReader>,
+}
+
+impl Component for PaletteView {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let palette = &ctx.props().palette;
+        let builtin = Palette::builtin();
+
+        let select = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            select
+                .value()
+                .parse()
+                .map_or(Msg::Noop, |index: usize| Msg::Select(index))
+        });
+        let import = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            match input.files().and_then(|files| files.get(0)) {
+                Some(file) => Msg::Import(file),
+                None => Msg::Noop,
+            }
+        });
+        let recolor_all = ctx
+            .props()
+            .dispatch
+            .reform(|_| Action::EditSignature(SignatureEdit::RecolorAll));
+
+        // An imported palette is listed alongside the built-in ones while it is in use.
+        let custom = (!builtin.contains(palette)).then(|| {
+            html! {
+                <option selected={true} disabled={true}>{&palette.name}</option>
+            }
+        });
+        let options = builtin.iter().enumerate().map(|(i, builtin)| {
+            html! {
+                <option value={i.to_string()} selected={builtin == palette}>
+                    {&builtin.name}
+                </option>
+            }
+        });
+        let swatches = palette.colors.iter().map(|color| {
+            html! {
+                <div
+                    class="signature__palette-swatch"
+                    style={format!("background: {}", color.hex())}
+                />
+            }
+        });
+
+        html! {
+            <div class="signature__palette">
+                <select class="signature__palette-select" onchange={select}>
+                    {for custom}
+                    {for options}
+                </select>
+                <label for="import-palette" class="button" title="Import palette">
+                    {"Import"}
+                </label>
+                <input
+                    type="file"
+                    accept=".json,.txt,text/plain,application/json"
+                    class="visually-hidden"
+                    id="import-palette"
+                    onchange={import}
+                />
+                <button onclick={recolor_all} title="Recolour all generators from the palette">
+                    {"Recolour all"}
+                </button>
+                <div class="signature__palette-swatches">
+                    {for swatches}
+                </div>
+            </div>
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let dispatch = &ctx.props().dispatch;
+        match msg {
+            Msg::Select(index) => {
+                if let Some(palette) = Palette::builtin().into_iter().nth(index) {
+                    dispatch.emit(Action::EditSignature(SignatureEdit::SetPalette(palette)));
+                }
+                false
+            }
+            Msg::Import(file) => {
+                let name = file
+                    .name()
+                    .rsplit_once('.')
+                    .map_or_else(|| file.name(), |(stem, _)| stem.to_owned());
+                let task = gloo::file::callbacks::read_as_text(
+                    &file.into(),
+                    closure!(clone dispatch, |res: Result<String, _>| {
+                        match res.map(|text| Palette::parse(&name, &text)) {
+                            Ok(Ok(palette)) => {
+                                dispatch.emit(Action::EditSignature(SignatureEdit::SetPalette(palette)));
+                            }
+                            Ok(Err(error)) => toast(Toast::error(error.to_string())),
+                            Err(_) => toast(Toast::error("Failed to read palette file")),
+                        }
+                    }),
+                );
+                self.reader = Some(task);
+                false
+            }
+            Msg::Noop => false,
+        }
+    }
+}
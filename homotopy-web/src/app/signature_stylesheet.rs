@@ -1,4 +1,4 @@
-use homotopy_graphics::svg;
+use homotopy_graphics::{style::Theme, svg};
 use wasm_bindgen::JsCast;
 use web_sys::{Element, Node};
 
@@ -6,13 +6,14 @@ use crate::{components::document, model::proof::Signature};
 
 // It would be nice if we could remove the `SignatureStylesheet` entirely but we still need to
 // maintain a handle to the html node for mounting/unmounting. This also means we can avoid
-// regenerating the stylesheet unless the signature has changed [`update`].
+// regenerating the stylesheet unless the signature or the theme has changed [`update`].
 
 // TODO: Check if there is a performance problem with the current approach.. If so, then use the
 // stylesheet API to change the colors more granularly.
 
 pub struct SignatureStylesheet {
     signature: Signature,
+    theme: Theme,
     element: Element,
 }
 
@@ -22,6 +23,7 @@ impl SignatureStylesheet {
         element.set_id("signature__stylesheet");
         Self {
             signature: Default::default(),
+            theme: Default::default(),
             element,
         }
     }
@@ -49,8 +51,19 @@ impl SignatureStylesheet {
     pub fn update(&mut self, signature: Signature) {
         if signature != self.signature {
             self.signature = signature;
-            self.element
-                .set_inner_html(&svg::stylesheet(&self.signature));
+            self.regenerate();
         }
     }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        if theme != self.theme {
+            self.theme = theme;
+            self.regenerate();
+        }
+    }
+
+    fn regenerate(&self) {
+        self.element
+            .set_inner_html(&svg::stylesheet(&self.signature, self.theme));
+    }
 }
@@ -1,13 +1,16 @@
 use std::convert::{Into, TryInto};
 
+use compare_control::CompareControl;
 use homotopy_core::{
     common::{Boundary, Height, SliceIndex},
-    DiagramN,
+    Diagram, DiagramN,
 };
 use im::Vector;
 use path_control::PathControl;
 use slice_control::SliceControl;
+use tab_control::TabControl;
 use view_control::ViewControl;
+use viewpoint_control::ViewpointControl;
 use yew::prelude::*;
 
 use crate::{
@@ -21,25 +24,33 @@ use crate::{
     components::panzoom::PanZoomComponent,
     model::{
         proof::{self, homotopy::Homotopy, AttachOption, Metadata, Signature, Workspace},
-        Action,
+        Action, Comparison,
     },
 };
 
+mod compare_control;
 mod path_control;
 mod slice_control;
+mod tab_control;
 mod view_control;
+mod viewpoint_control;
 
 // TODO: Workspace rerendering when panzoom is changed needs to be smoother.
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     pub workspace: Option<Workspace>,
+    pub tabs: Vec<String>,
+    pub active_tab: usize,
     pub dispatch: Callback<Action>,
     pub signature: Signature,
     pub metadata: Metadata,
     pub attach: Option<Vector<AttachOption>>,
     pub attachment_highlight: Option<AttachOption>,
     pub slice_highlight: Option<SliceIndex>,
+    pub comparison: Option<Comparison>,
+    /// How many steps back the history goes.
+    pub history_len: usize,
 }
 
 pub struct WorkspaceView {
@@ -70,12 +81,15 @@ impl Component for WorkspaceView {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let workspace = &ctx.props().workspace;
-        let diagram_view = if workspace.is_some() {
-            self.view_diagram(ctx)
-        } else {
-            // Show onboarding info if workspace and signature is empty
-            get_onboarding_message()
-        };
+        let diagram_view =
+            if let (Some(ws), Some(comparison)) = (workspace, &ctx.props().comparison) {
+                self.view_comparison(ctx, ws, comparison)
+            } else if workspace.is_some() {
+                self.view_diagram(ctx, &[])
+            } else {
+                // Show onboarding info if workspace and signature is empty
+                get_onboarding_message()
+            };
         let project_title = html! {
             <TexSpan
                 class="workspace__project-title"
@@ -97,19 +111,42 @@ impl Component for WorkspaceView {
             }
             _ => Default::default(),
         };
+        let tabs = html! {
+            <div class="workspace__toolbar">
+                <TabControl
+                    tabs={ctx.props().tabs.clone()}
+                    active={ctx.props().active_tab}
+                    create={ctx.props().dispatch.reform(|()| Action::Proof(proof::Action::CreateWorkspace))}
+                    switch={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::SwitchWorkspace)}
+                    close={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::CloseWorkspace)}
+                    rename={ctx.props().dispatch.reform(|(index, name): (usize, String)| Action::Proof(proof::Action::RenameWorkspace(index, name)))}
+                />
+            </div>
+        };
         let toolbar = workspace.as_ref().map_or_else(
             Default::default,
             |ws| {
                 html! {
                     <div class="workspace__toolbar">
                         <PathControl
-                            path={ws.path.clone()}
-                            view={ws.view}
+                            workspace={ws.clone()}
+                            signature={ctx.props().signature.clone()}
                             ascend_slice={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::AscendSlice)}
                             increase_view={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::IncreaseView)}
                             decrease_view={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::DecreaseView)}
                             dimension={ws.diagram.dimension()}
                         />
+                        <ViewpointControl
+                            viewpoints={ws.viewpoints.clone()}
+                            pin={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::PinViewpoint)}
+                            go_to={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::GoToViewpoint)}
+                            unpin={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::UnpinViewpoint)}
+                        />
+                        <CompareControl
+                            steps={ctx.props().comparison.as_ref().map(|comparison| comparison.steps)}
+                            max_steps={ctx.props().history_len}
+                            compare={ctx.props().dispatch.reform(Action::Compare)}
+                        />
                         <ViewControl />
                     </div>
                 }
@@ -122,6 +159,7 @@ impl Component for WorkspaceView {
                 {slice_buttons}
                 <div class="workspace__overlay-top">
                     {project_title}
+                    {tabs}
                     {toolbar}
                 </div>
             </div>
@@ -130,12 +168,85 @@ impl Component for WorkspaceView {
 }
 
 impl WorkspaceView {
-    fn view_diagram(&self, ctx: &Context<Self>) -> Html {
+    /// The earlier workspace on the left and the current one on the right, with the singular
+    /// heights at which they differ highlighted in both.
+    fn view_comparison(
+        &self,
+        ctx: &Context<Self>,
+        ws: &Workspace,
+        comparison: &Comparison,
+    ) -> Html {
+        // Heights only correspond if both workspaces show the same slice.
+        let differences = match &comparison.workspace {
+            Some(earlier) if earlier.path == ws.path => {
+                match (ws.visible_diagram(), earlier.visible_diagram()) {
+                    (Diagram::DiagramN(current), Diagram::DiagramN(earlier)) => {
+                        current.differing_heights(&earlier)
+                    }
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        };
+
+        let signature = &ctx.props().signature;
+        let earlier = match &comparison.workspace {
+            None => html! {
+                <div class="workspace__compare-message">{"The workspace was empty."}</div>
+            },
+            // Generators removed since cannot be drawn.
+            Some(earlier)
+                if !earlier
+                    .diagram
+                    .generators()
+                    .into_iter()
+                    .all(|g| signature.generator_info(g).is_some()) =>
+            {
+                html! {
+                    <div class="workspace__compare-message">
+                        {"The workspace used generators which have been removed since."}
+                    </div>
+                }
+            }
+            Some(earlier) => match earlier.view.dimension() {
+                0 => Self::view_earlier_svg::<0>(ctx, earlier, &differences),
+                1 => Self::view_earlier_svg::<1>(ctx, earlier, &differences),
+                2 => Self::view_earlier_svg::<2>(ctx, earlier, &differences),
+                _ => html! {
+                    <DiagramGl
+                        diagram={earlier.visible_diagram()}
+                        signature={signature.clone()}
+                        view={earlier.view}
+                    />
+                },
+            },
+        };
+        let steps = if comparison.steps == 1 {
+            "1 step ago".to_owned()
+        } else {
+            format!("{} steps ago", comparison.steps)
+        };
+
+        html! {
+            <div class="workspace__compare">
+                <div class="workspace__compare-pane">
+                    <span class="workspace__compare-label">{steps}</span>
+                    {earlier}
+                </div>
+                <div class="workspace__compare-pane">
+                    <span class="workspace__compare-label">{"Now"}</span>
+                    {self.view_diagram(ctx, &differences)}
+                </div>
+            </div>
+        }
+    }
+
+    fn view_diagram(&self, ctx: &Context<Self>, differences: &[usize]) -> Html {
         if let Some(ref ws) = ctx.props().workspace {
             match ws.view.dimension() {
-                0 => Self::view_diagram_svg::<0>(self, ctx),
-                1 => Self::view_diagram_svg::<1>(self, ctx),
-                2 => Self::view_diagram_svg::<2>(self, ctx),
+                0 => Self::view_diagram_svg::<0>(self, ctx, differences),
+                1 => Self::view_diagram_svg::<1>(self, ctx, differences),
+                2 => Self::view_diagram_svg::<2>(self, ctx, differences),
                 _ => {
                     html! {
                         <DiagramGl
@@ -151,7 +262,7 @@ impl WorkspaceView {
         }
     }
 
-    fn view_diagram_svg<const N: usize>(&self, ctx: &Context<Self>) -> Html {
+    fn view_diagram_svg<const N: usize>(&self, ctx: &Context<Self>, differences: &[usize]) -> Html {
         if let Some(ref ws) = ctx.props().workspace {
             let attachment_highlight = match N {
                 0 => None,
@@ -161,7 +272,10 @@ impl WorkspaceView {
                     .as_ref()
                     .map(|option| highlight_attachment::<N>(ws, option)),
             };
-            let slice_highlight = ctx.props().slice_highlight.map(highlight_slice::<N>);
+            let slice_highlight = ctx
+                .props()
+                .slice_highlight
+                .map(|slice| highlight_slice::<N>(slice, HighlightKind::Slice));
             let highlight = attachment_highlight.or(slice_highlight);
             html! {
                 <PanZoomComponent on_scroll={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::SwitchSlice)}>
@@ -172,7 +286,10 @@ impl WorkspaceView {
                         on_select={self.on_select.clone()}
                         on_homotopy={self.on_homotopy.clone()}
                         highlight={highlight}
+                        differences={highlight_differences::<N>(differences)}
                         diagram_ref={self.diagram_ref.clone()}
+                        labelled={true}
+                        interactive={true}
                     />
                 </PanZoomComponent>
             }
@@ -180,6 +297,29 @@ impl WorkspaceView {
             Default::default()
         }
     }
+
+    /// The earlier diagram of a comparison, which pans, zooms and switches slices along with
+    /// the current one but cannot be edited.
+    fn view_earlier_svg<const N: usize>(
+        ctx: &Context<Self>,
+        ws: &Workspace,
+        differences: &[usize],
+    ) -> Html {
+        html! {
+            <PanZoomComponent
+                index={1}
+                on_scroll={ctx.props().dispatch.reform(Action::Proof).reform(proof::Action::SwitchSlice)}
+            >
+                <DiagramSvg<N>
+                    diagram={ws.visible_diagram()}
+                    id="workspace__compare-diagram"
+                    signature={ctx.props().signature.clone()}
+                    differences={highlight_differences::<N>(differences)}
+                    labelled={true}
+                />
+            </PanZoomComponent>
+        }
+    }
 }
 
 // TODO: highlighting needs better documentation and maybe a refactor
@@ -268,15 +408,24 @@ fn highlight_attachment<const N: usize>(
     }
 }
 
-fn highlight_slice<const N: usize>(slice: SliceIndex) -> HighlightSvg<N> {
+fn highlight_slice<const N: usize>(slice: SliceIndex, kind: HighlightKind) -> HighlightSvg<N> {
     let mut from = [Boundary::Source.into(); N];
     from[0] = slice;
     let mut to = [Boundary::Target.into(); N];
     to[0] = slice;
 
-    HighlightSvg {
-        from,
-        to,
-        kind: HighlightKind::Slice,
+    HighlightSvg { from, to, kind }
+}
+
+fn highlight_differences<const N: usize>(heights: &[usize]) -> Vec<HighlightSvg<N>> {
+    if N == 0 {
+        return vec![];
     }
+
+    heights
+        .iter()
+        .map(|&height| {
+            highlight_slice::<N>(Height::Singular(height).into(), HighlightKind::Difference)
+        })
+        .collect()
 }
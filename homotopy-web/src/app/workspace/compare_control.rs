@@ -0,0 +1,71 @@
+use yew::prelude::*;
+use yew_macro::function_component;
+
+use crate::app::{Icon, IconSize};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CompareControlProps {
+    /// How many steps back the workspace is compared with, if at all.
+    pub steps: Option<usize>,
+    pub max_steps: usize,
+    pub compare: Callback<Option<usize>>,
+}
+
+#[function_component(CompareControl)]
+pub fn compare_control(props: &CompareControlProps) -> Html {
+    let Some(steps) = props.steps else {
+        if props.max_steps == 0 {
+            return Default::default();
+        }
+
+        return html! {
+            <div class="workspace__toolbar__segment">
+                <span
+                    class="workspace__toolbar__button"
+                    title="Compare with the previous step"
+                    onclick={props.compare.reform(|_| Some(1))}
+                >
+                    <Icon name="compare" size={IconSize::Icon24} />
+                </span>
+            </div>
+        };
+    };
+
+    let back = (steps < props.max_steps).then(|| {
+        html! {
+            <span
+                class="workspace__toolbar__button"
+                title="Compare with an earlier step"
+                onclick={props.compare.reform(move |_| Some(steps + 1))}
+            >
+                <Icon name="chevron_left" size={IconSize::Icon24} />
+            </span>
+        }
+    });
+    let forward = (steps > 1).then(|| {
+        html! {
+            <span
+                class="workspace__toolbar__button"
+                title="Compare with a later step"
+                onclick={props.compare.reform(move |_| Some(steps - 1))}
+            >
+                <Icon name="chevron_right" size={IconSize::Icon24} />
+            </span>
+        }
+    });
+
+    html! {
+        <div class="workspace__toolbar__segment">
+            {back}
+            <span class="workspace__compare-steps">{format!("−{steps}")}</span>
+            {forward}
+            <span
+                class="workspace__toolbar__button"
+                title="Stop comparing"
+                onclick={props.compare.reform(|_| None)}
+            >
+                <Icon name="close" size={IconSize::Icon24} />
+            </span>
+        </div>
+    }
+}
@@ -1,19 +1,18 @@
 use std::cmp;
 
-use homotopy_core::{Boundary, Height, SliceIndex};
-use im::Vector;
+use homotopy_core::{Boundary, Diagram, Height, SliceIndex};
 use yew::prelude::*;
 use yew_macro::function_component;
 
 use crate::{
-    app::{Icon, IconSize},
-    model::proof::View,
+    app::{diagram_svg::DiagramSvg, Icon, IconSize},
+    model::proof::{Signature, Workspace},
 };
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct PathControlProps {
-    pub path: Vector<SliceIndex>,
-    pub view: View,
+    pub workspace: Workspace,
+    pub signature: Signature,
     pub dimension: usize,
     pub ascend_slice: Callback<usize>,
     pub increase_view: Callback<u8>,
@@ -29,7 +28,31 @@ enum Step {
 
 #[function_component(PathControl)]
 pub fn path_control(props: &PathControlProps) -> Html {
-    let path_len = props.path.len();
+    let path_len = props.workspace.path.len();
+    // The depth of the ancestor diagram whose preview is shown, if any.
+    let hovered = use_state(|| None::<usize>);
+
+    let preview = |depth: usize| -> Html {
+        if *hovered != Some(depth) {
+            return Default::default();
+        }
+
+        let Some(diagram) = props.workspace.try_ancestor_diagram(depth) else { return Default::default() };
+        let preview = match diagram.dimension() {
+            0 => view_diagram_svg::<0>(diagram, &props.signature),
+            1 => view_diagram_svg::<1>(diagram, &props.signature),
+            _ => view_diagram_svg::<2>(diagram, &props.signature),
+        };
+
+        html! {
+            <div class="workspace__path-preview">{preview}</div>
+        }
+    };
+
+    let hover = |depth: Option<usize>| {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set(depth))
+    };
 
     let step_button = |index: usize, step: Step| -> Html {
         let label = match step {
@@ -61,23 +84,35 @@ pub fn path_control(props: &PathControlProps) -> Html {
             }
         });
 
+        // Slice steps lead to the diagram after descending into the slice.
+        let depth = (index < path_len).then_some(index + 1);
+
         html! {
             <span
-                class="workspace__toolbar__button workspace__path-segment"
+                class="workspace__toolbar__button workspace__path-crumb workspace__path-segment"
                 onclick={onclick}
+                onmouseenter={hover(depth)}
+                onmouseleave={hover(None)}
             >
                 {label}
+                {depth.map(preview).unwrap_or_default()}
             </span>
         }
     };
 
     let path = {
         let mut path = Vec::with_capacity(props.dimension);
-        path.extend(props.path.iter().map(|slice| Step::SliceIndex(*slice)));
+        path.extend(
+            props
+                .workspace
+                .path
+                .iter()
+                .map(|slice| Step::SliceIndex(*slice)),
+        );
 
         let viewing_range = path.len()
             ..cmp::min(
-                path.len() + props.view.dimension() as usize,
+                path.len() + props.workspace.view.dimension() as usize,
                 props.dimension,
             );
 
@@ -95,12 +130,27 @@ pub fn path_control(props: &PathControlProps) -> Html {
     html! {
         <div class="workspace__toolbar__segment">
             <span
-                class="workspace__toolbar__button"
+                class="workspace__toolbar__button workspace__path-crumb"
                 onclick={props.ascend_slice.reform(move |_| path_len)}
+                onmouseenter={hover(Some(0))}
+                onmouseleave={hover(None)}
             >
                 <Icon name="star" size={IconSize::Icon24} />
+                {preview(0)}
             </span>
             {step_buttons}
         </div>
     }
 }
+
+fn view_diagram_svg<const N: usize>(diagram: Diagram, signature: &Signature) -> Html {
+    html! {
+        <DiagramSvg<N>
+            diagram={diagram}
+            id="workspace__path-preview"
+            signature={signature.clone()}
+            max_width={Some(120.0)}
+            max_height={Some(120.0)}
+        />
+    }
+}
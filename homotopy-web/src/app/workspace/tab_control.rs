@@ -0,0 +1,78 @@
+use yew::prelude::*;
+use yew_macro::function_component;
+
+use crate::app::{Icon, IconSize};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct TabControlProps {
+    pub tabs: Vec<String>,
+    pub active: usize,
+    pub create: Callback<()>,
+    pub switch: Callback<usize>,
+    pub close: Callback<usize>,
+    pub rename: Callback<(usize, String)>,
+}
+
+#[function_component(TabControl)]
+pub fn tab_control(props: &TabControlProps) -> Html {
+    let closable = props.tabs.len() > 1;
+
+    let tab = |index: usize, name: &String| -> Html {
+        let class = if index == props.active {
+            "workspace__toolbar__button workspace__tab workspace__tab--active"
+        } else {
+            "workspace__toolbar__button workspace__tab"
+        };
+
+        let onclick = props.switch.reform(move |_| index);
+        let ondblclick = {
+            let rename = props.rename.clone();
+            let name = name.clone();
+            Callback::from(move |_| {
+                if let Some(name) = gloo::dialogs::prompt("Rename workspace", Some(&name)) {
+                    rename.emit((index, name));
+                }
+            })
+        };
+        let close = if closable {
+            let onclick = props.close.reform(move |e: MouseEvent| {
+                // Do not also switch to the tab being closed.
+                e.stop_propagation();
+                index
+            });
+            html! {
+                <span class="workspace__tab__close" onclick={onclick}>
+                    <Icon name="close" size={IconSize::Icon18} />
+                </span>
+            }
+        } else {
+            Default::default()
+        };
+
+        html! {
+            <span class={class} onclick={onclick} ondblclick={ondblclick}>
+                {name}
+                {close}
+            </span>
+        }
+    };
+
+    let tabs: Html = props
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(index, name)| tab(index, name))
+        .collect();
+
+    html! {
+        <div class="workspace__toolbar__segment workspace__tabs">
+            {tabs}
+            <span
+                class="workspace__toolbar__button"
+                onclick={props.create.reform(|_| ())}
+            >
+                <Icon name="add" size={IconSize::Icon24} />
+            </span>
+        </div>
+    }
+}
@@ -0,0 +1,71 @@
+use im::Vector;
+use yew::prelude::*;
+use yew_macro::function_component;
+
+use crate::{
+    app::{Icon, IconSize},
+    model::proof::Viewpoint,
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ViewpointControlProps {
+    pub viewpoints: Vector<Viewpoint>,
+    pub pin: Callback<String>,
+    pub go_to: Callback<usize>,
+    pub unpin: Callback<usize>,
+}
+
+#[function_component(ViewpointControl)]
+pub fn viewpoint_control(props: &ViewpointControlProps) -> Html {
+    let viewpoint = |index: usize, viewpoint: &Viewpoint| -> Html {
+        let onclick = props.go_to.reform(move |_| index);
+        let unpin = props.unpin.reform(move |e: MouseEvent| {
+            // Do not also go to the viewpoint being unpinned.
+            e.stop_propagation();
+            index
+        });
+
+        html! {
+            <span
+                class="workspace__toolbar__button workspace__tab"
+                title={format!("Go to {}", viewpoint.name)}
+                onclick={onclick}
+            >
+                {&viewpoint.name}
+                <span class="workspace__tab__close" onclick={unpin}>
+                    <Icon name="close" size={IconSize::Icon18} />
+                </span>
+            </span>
+        }
+    };
+
+    let viewpoints: Html = props
+        .viewpoints
+        .iter()
+        .enumerate()
+        .map(|(index, v)| viewpoint(index, v))
+        .collect();
+
+    let pin = {
+        let pin = props.pin.clone();
+        let default = format!("Viewpoint {}", props.viewpoints.len() + 1);
+        Callback::from(move |_| {
+            if let Some(name) = gloo::dialogs::prompt("Pin viewpoint", Some(&default)) {
+                pin.emit(name);
+            }
+        })
+    };
+
+    html! {
+        <div class="workspace__toolbar__segment workspace__tabs">
+            {viewpoints}
+            <span
+                class="workspace__toolbar__button"
+                title="Pin the current slice as a viewpoint"
+                onclick={pin}
+            >
+                <Icon name="push_pin" size={IconSize::Icon24} />
+            </span>
+        </div>
+    }
+}
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 
 use homotopy_common::idx::{Idx, IdxVec};
+use homotopy_graphics::style::Theme;
 use yew::callback::Callback;
 
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
@@ -42,6 +43,16 @@ impl State for u32 {
     }
 }
 
+impl State for Theme {
+    type Action = Theme;
+
+    fn update(&mut self, action: &Self::Action) -> bool {
+        let ret = self != action;
+        *self = *action;
+        ret
+    }
+}
+
 #[derive(Default)]
 pub struct Delta<T>(RefCell<DeltaInner<T>>)
 where
@@ -102,6 +102,9 @@ impl TouchInterface for PanZoomState {
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct PanZoomProps {
+    /// Distinguishes the ids of several panes shown at once.
+    #[prop_or_default]
+    pub index: usize,
     #[prop_or_default]
     pub on_scroll: Callback<Direction>,
     #[prop_or_default]
@@ -207,7 +210,7 @@ impl Component for PanZoomComponent {
                 ref={self.node_ref.clone()}
             >
                 <div
-                    id="panzoom__inner__0"
+                    id={format!("panzoom__inner__{}", ctx.props().index)}
                     class="panzoom__inner"
                     style={style}
                 >
@@ -0,0 +1,66 @@
+//! What touches, styluses and mouse presses on a diagram amount to, apart from the events that
+//! report them.
+
+use euclid::default::Vector2D;
+
+/// How long in milliseconds a finger has to rest on the diagram to select the point under it.
+pub const LONG_PRESS: u32 = 500;
+
+/// How firmly a stylus has to press for a tap to select, on the scale of
+/// `PointerEvent::pressure`. Styluses that do not sense pressure report a half while pressed.
+pub const SELECT_PRESSURE: f32 = 0.35;
+
+/// The least cosine of the angle between the paths of the fingers in a two-finger drag. Fingers
+/// moving further apart than this are pinching to zoom instead.
+pub const PARALLEL: f32 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// Too little has happened yet to tell.
+    Undecided,
+    /// Select the point where the gesture started.
+    Select,
+    /// Drag from where the gesture started, by the given vector.
+    Drag(Vector2D<f32>),
+    /// Fingers moving apart or together, which zoom the diagram.
+    Pinch,
+}
+
+/// A single pointer which has travelled by `travel` while held down for `held` milliseconds. It
+/// starts a drag once it travels at least `min_travel`, and selects if it rests long enough
+/// before that.
+pub fn press(travel: Vector2D<f32>, held: u32, min_travel: f32) -> Gesture {
+    if travel.square_length() >= min_travel * min_travel {
+        Gesture::Drag(travel)
+    } else if held >= LONG_PRESS {
+        Gesture::Select
+    } else {
+        Gesture::Undecided
+    }
+}
+
+/// A pointer released before it started a drag, pressed with the given stylus pressure or `None`
+/// if it is not a stylus. A stylus brushing against the screen should not select, only a firm
+/// tap.
+pub fn tap(pressure: Option<f32>) -> Gesture {
+    if pressure.map_or(true, |pressure| pressure >= SELECT_PRESSURE) {
+        Gesture::Select
+    } else {
+        Gesture::Undecided
+    }
+}
+
+/// Two fingers which have travelled by the given vectors. Once both have travelled at least
+/// `min_travel`, fingers moving the same way drag by their combined travel, and others pinch.
+pub fn two_finger(travel: [Vector2D<f32>; 2], min_travel: f32) -> Gesture {
+    let [first, second] = travel;
+    if first.square_length() < min_travel * min_travel
+        || second.square_length() < min_travel * min_travel
+    {
+        Gesture::Undecided
+    } else if first.normalize().dot(second.normalize()) < PARALLEL {
+        Gesture::Pinch
+    } else {
+        Gesture::Drag(first + second)
+    }
+}
@@ -13,7 +13,8 @@ use wasm_bindgen::prelude::*;
 mod app;
 mod components;
 mod panic;
-// Model has to be public for tests to work
+// Model and gestures have to be public for tests to work
+pub mod gesture;
 pub mod model;
 
 fn tracing_filter(meta: &Metadata<'_>) -> bool {
@@ -1,34 +1,66 @@
 pub use history::Proof;
 use history::{History, UndoState};
-use homotopy_core::{
-    common::{BoundaryPath, RegularHeight},
-    Boundary, Diagram, DiagramN, Height, SliceIndex,
+use homotopy_common::tree::Node;
+use homotopy_core::{Diagram, SliceIndex};
+use homotopy_graphics::{
+    figure::FigureOptions,
+    gltf,
+    manim::{self, StepOptions},
+    mesh::MeshOptions,
+    obj, ply, stl,
+    strip::StripOptions,
+    style::{SignatureStyleData, Theme},
+    svg, tikz,
 };
-use homotopy_graphics::{manim, stl, svg, tikz};
-use homotopy_model::proof::AttachOption;
+use homotopy_model::proof::{AttachCandidate, AttachOption};
 pub use homotopy_model::{history, migration, proof, serialize};
 use im::Vector;
 use serde::Serialize;
 use thiserror::Error;
 use wasm_bindgen::JsCast;
 
+use crate::app::diagram_gl::GlViewControl;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum Action {
     Proof(proof::Action),
     History(history::Action),
     ImportActions(proof::SerializedData),
+    /// Replace the history with a fresh one holding a saved proof. The actions which led to the
+    /// proof, in the format of [Action::ImportActions], are replayed so that they can be undone;
+    /// without them, or if they fail, the proof is imported as the only step.
+    OpenProof(proof::SerializedData, Option<proof::SerializedData>),
     ExportProof,
+    /// Export the given signature nodes, with everything they depend on, as a proof without
+    /// workspaces or shelf.
+    ExportLibrary(Vec<Node>),
     ExportActions,
-    ExportTikz(bool, bool),
-    ExportSvg,
-    ExportManim(bool),
-    ExportStl,
+    ExportTikz(bool, bool, bool, Theme),
+    ExportTikzSlices(bool, bool, bool, Theme),
+    ExportTikzProjected(u8, u8, Theme),
+    ExportSvg(bool, Theme),
+    /// Export the slices of a 3-diagram as an SVG animation, showing each slice for the given
+    /// number of milliseconds.
+    ExportSvgSlices(u32, bool, Theme),
+    ExportStripSvg(u8, bool, bool, Theme),
+    ExportStripTikz(u8, bool, bool, bool, bool, Theme),
+    ExportManim(bool, bool, Theme),
+    ExportManimSlices(bool, bool, Theme),
+    ExportManimHistory(bool, bool, Theme),
+    ExportStl(u8, u8),
+    ExportObj(u8, u8, Theme),
+    ExportGltf(u8, u8, Theme),
+    ExportPly(u8, u8, Theme),
     Select(usize),
 
     ClearAttach,
     SelectPoint(Vec<SliceIndex>, bool),
+    SelectDiagram(AttachCandidate),
     HighlightAttachment(Option<AttachOption>),
     HighlightSlice(Option<SliceIndex>),
+    /// Show the workspace from the given number of steps back in the history beside the current
+    /// one, or stop comparing.
+    Compare(Option<usize>),
 
     Help,
 }
@@ -39,15 +71,35 @@ impl Action {
         match self {
             Self::Proof(action) => action.is_valid(proof),
             Self::History(history::Action::Move(dir)) => proof.can_move(dir),
-            Self::ExportTikz(_, _) | Self::ExportSvg | Self::ExportManim(_) => proof
+            Self::ExportTikz(_, _, _, _) | Self::ExportSvg(_, _) | Self::ExportManim(_, _, _) => {
+                proof
+                    .workspace
+                    .as_ref()
+                    .map_or(false, |ws| ws.view.dimension() == 2)
+            }
+            Self::ExportStripSvg(_, _, _, _)
+            | Self::ExportStripTikz(_, _, _, _, _, _)
+            | Self::ExportManimSlices(_, _, _) => proof
                 .workspace
                 .as_ref()
-                .map_or(false, |ws| ws.view.dimension() == 2),
-            Self::ExportStl => proof
+                .map_or(false, |ws| ws.visible_dimension() >= 3),
+            Self::ExportSvgSlices(_, _, _) => proof
+                .workspace
+                .as_ref()
+                .map_or(false, |ws| ws.visible_dimension() == 3),
+            Self::ExportManimHistory(_, _, _) => proof.workspace.is_some(),
+            Self::ExportTikzSlices(_, _, _, _)
+            | Self::ExportTikzProjected(_, _, _)
+            | Self::ExportStl(_, _)
+            | Self::ExportObj(_, _, _)
+            | Self::ExportGltf(_, _, _)
+            | Self::ExportPly(_, _, _) => proof
                 .workspace
                 .as_ref()
                 .map_or(false, |ws| ws.view.dimension() == 3),
-            Self::SelectPoint(_, _) => proof.workspace.is_some(),
+            Self::SelectPoint(_, _) | Self::SelectDiagram(_) => proof.workspace.is_some(),
+            Self::ExportLibrary(nodes) => !nodes.is_empty(),
+            Self::Compare(steps) => steps.map_or(true, |steps| steps > 0),
             _ => true,
         }
     }
@@ -71,6 +123,16 @@ pub struct State {
     pub attach: Option<Vector<AttachOption>>,
     pub attachment_highlight: Option<AttachOption>,
     pub slice_highlight: Option<SliceIndex>,
+    /// How many steps back in the history the workspace is compared with, if at all.
+    pub compare: Option<usize>,
+}
+
+/// An earlier state of the workspace, shown beside the current one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub steps: usize,
+    /// The earlier workspace, sliced and viewed like the current one as far as its diagram allows.
+    pub workspace: Option<proof::Workspace>,
 }
 
 impl State {
@@ -79,6 +141,16 @@ impl State {
         self.history.proof()
     }
 
+    pub fn comparison(&self) -> Option<Comparison> {
+        let steps = self.compare?;
+        let mut workspace = self.history.earlier(steps)?.workspace.clone();
+        if let (Some(workspace), Some(current)) = (&mut workspace, &self.proof().workspace) {
+            // Otherwise the earlier workspace keeps its own slice.
+            workspace.move_to(current.path.clone(), current.view);
+        }
+        Some(Comparison { steps, workspace })
+    }
+
     /// Update the state in response to an [Action].
     pub fn update(&mut self, action: Action) -> Result<bool, ModelError> {
         match action {
@@ -123,75 +195,251 @@ impl State {
                 self.clear_attach();
             }
 
-            Action::ExportTikz(leftright, with_braid) => {
+            Action::ExportTikz(leftright, with_braid, with_labels, theme) => {
                 let signature = &self.proof().signature;
                 let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
-                let data = tikz::render(&diagram, signature, leftright, with_braid).unwrap();
+                let data = tikz::render(
+                    &diagram,
+                    signature,
+                    leftright,
+                    with_braid,
+                    with_labels,
+                    theme,
+                )
+                .unwrap();
                 generate_download("homotopy_io_export", "tikz", data.as_bytes())
                     .map_err(ModelError::Export)?;
             }
 
-            Action::ExportSvg => {
+            Action::ExportTikzSlices(leftright, with_braid, with_labels, theme) => {
                 let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let data = tikz::render_slices(
+                    &diagram,
+                    signature,
+                    leftright,
+                    with_braid,
+                    with_labels,
+                    None,
+                    theme,
+                )
+                .unwrap();
+                generate_download("homotopy_io_export", "tikz", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
 
-                // First we locate the element containing the SVG rendered the SVG rendering
-                // pipeline. We *could* do this by using a lookup by class name, which would not
-                // require any chances to components/panzoom.rs, but get_elements_by_class_name
-                // has a return type that requires a feature of web-sys that is not
-                // currently activated. Thus this solution avoids increases in build times.
-                let svg_element = web_sys::window()
-                    .expect("no window")
-                    .document()
-                    .expect("no document")
-                    .get_element_by_id("panzoom__inner__0")
-                    .expect("no SVG in document");
-                let svg = svg_element.inner_html();
-
-                // We must now pull all the relevant stylesheets that are needed in the SVG.
-                // Failure to do so gives a fully-black SVG. We can generate the stylesheet in the
-                // same way the `SignatureStylesheet` struct does.
-                // We also strip the styles of whitespace since it is unneeded.
-                let stylesheet = {
-                    let mut inner_stylesheet = svg::stylesheet(signature);
-                    inner_stylesheet.retain(|c| !c.is_whitespace());
-                    format!("<style>{inner_stylesheet}</style>")
+            Action::ExportTikzProjected(subdivision_depth, samples, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = MeshOptions {
+                    subdivision_depth,
+                    samples,
+                    theme,
                 };
+                let camera = GlViewControl::camera();
+                let data =
+                    tikz::render_projected(&diagram, signature, options, &camera, 10.).unwrap();
+                generate_download("homotopy_io_export", "tikz", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
 
-                // So we now have the SVG and its stylesheet in separate strings.
-                // It is not enough to just concatenate them, the stylesheets need to be inside the
-                // root element. Since we know that this will have form
-                // <svg.*><.*
-                // We just look for the first >< and use some very light indexing maths to insert
-                // the stylesheet in the appropriate place.
-                // Again, the function would be half as long and twice as clean if the SVG and the SVG's
-                // stylesheet were in the same place in the DOM.
-                let content_start = svg.find("><").unwrap() + 1;
-                let mut data = String::new();
-                data.push_str(&svg[..content_start]);
-                data.push_str(&stylesheet);
-                data.push_str(&svg[content_start..]);
+            Action::ExportSvg(show_labels, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = svg::SvgOptions {
+                    figure: FigureOptions {
+                        theme,
+                        ..Default::default()
+                    },
+                    show_labels,
+                    ..Default::default()
+                };
+                let data = svg::export(&diagram, signature, &options).unwrap();
+                generate_download("homotopy_io_export", "svg", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
 
+            Action::ExportSvgSlices(slice_duration, show_labels, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = svg::SvgOptions {
+                    figure: FigureOptions {
+                        theme,
+                        ..Default::default()
+                    },
+                    show_labels,
+                    slice_duration: Some(slice_duration as f32 / 1000.),
+                    ..Default::default()
+                };
+                let data = svg::export(&diagram, signature, &options).unwrap();
                 generate_download("homotopy_io_export", "svg", data.as_bytes())
                     .map_err(ModelError::Export)?;
             }
 
-            Action::ExportManim(use_opengl) => {
+            Action::ExportStripSvg(depth, singular_slices, show_labels, theme) => {
                 let signature = &self.proof().signature;
                 let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
-                let stylesheet = manim::stylesheet(signature);
-                let data = manim::render(&diagram, signature, &stylesheet, use_opengl).unwrap();
+                let strip = StripOptions {
+                    depth: depth.into(),
+                    singular_slices,
+                    ..Default::default()
+                };
+                let options = svg::SvgOptions {
+                    figure: FigureOptions {
+                        theme,
+                        ..Default::default()
+                    },
+                    show_labels,
+                    ..Default::default()
+                };
+                let data = svg::export_strip(&diagram, signature, &strip, &options).unwrap();
+                generate_download("homotopy_io_export", "svg", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportStripTikz(
+                depth,
+                singular_slices,
+                leftright,
+                with_braid,
+                with_labels,
+                theme,
+            ) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let strip = StripOptions {
+                    depth: depth.into(),
+                    singular_slices,
+                    ..Default::default()
+                };
+                let data = tikz::render_strip(
+                    &diagram,
+                    signature,
+                    &strip,
+                    leftright,
+                    with_braid,
+                    with_labels,
+                    theme,
+                )
+                .unwrap();
+                generate_download("homotopy_io_export", "tikz", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportManim(use_opengl, with_labels, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let stylesheet = manim::stylesheet(signature, theme);
+                let data = manim::render(&diagram, signature, &stylesheet, use_opengl, with_labels)
+                    .unwrap();
+                generate_download("homotopy_io_export", "py", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportManimSlices(use_opengl, with_labels, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let stylesheet = manim::stylesheet(signature, theme);
+                let data = manim::render_slices(
+                    &diagram,
+                    signature,
+                    &stylesheet,
+                    use_opengl,
+                    with_labels,
+                    StepOptions::default(),
+                )
+                .unwrap();
+                generate_download("homotopy_io_export", "py", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportManimHistory(use_opengl, with_labels, theme) => {
+                let signature = &self.proof().signature;
+                // Generators removed since are not styled any more, so earlier steps using them
+                // are left out along with views that cannot be drawn in two dimensions.
+                let mut diagrams: Vec<Diagram> = Vec::new();
+                for proof in self.history.proofs() {
+                    let diagram = match &proof.workspace {
+                        Some(ws) if ws.visible_dimension() >= 2 => ws.visible_diagram(),
+                        _ => continue,
+                    };
+                    let styled = diagram
+                        .generators()
+                        .into_iter()
+                        .all(|g| signature.generator_style(g).is_some());
+                    if styled && diagrams.last() != Some(&diagram) {
+                        diagrams.push(diagram);
+                    }
+                }
+                let stylesheet = manim::stylesheet(signature, theme);
+                let data = manim::render_steps(
+                    &diagrams,
+                    signature,
+                    &stylesheet,
+                    use_opengl,
+                    with_labels,
+                    StepOptions::default(),
+                )
+                .unwrap();
                 generate_download("homotopy_io_export", "py", data.as_bytes())
                     .map_err(ModelError::Export)?;
             }
 
-            Action::ExportStl => {
+            Action::ExportStl(subdivision_depth, samples) => {
                 let signature = &self.proof().signature;
                 let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
-                let data = stl::render(&diagram, signature).unwrap();
+                let options = MeshOptions {
+                    subdivision_depth,
+                    samples,
+                    ..Default::default()
+                };
+                let data = stl::render(&diagram, signature, options).unwrap();
                 generate_download("homotopy_io_export", "stl", data.as_bytes())
                     .map_err(ModelError::Export)?;
             }
 
+            Action::ExportObj(subdivision_depth, samples, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = MeshOptions {
+                    subdivision_depth,
+                    samples,
+                    theme,
+                };
+                let (data, materials) =
+                    obj::render(&diagram, signature, options, "homotopy_io_export.mtl").unwrap();
+                generate_download("homotopy_io_export", "obj", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+                generate_download("homotopy_io_export", "mtl", materials.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportGltf(subdivision_depth, samples, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = MeshOptions {
+                    subdivision_depth,
+                    samples,
+                    theme,
+                };
+                let data = gltf::render(&diagram, signature, options).unwrap();
+                generate_download("homotopy_io_export", "glb", &data)
+                    .map_err(ModelError::Export)?;
+            }
+
+            Action::ExportPly(subdivision_depth, samples, theme) => {
+                let signature = &self.proof().signature;
+                let diagram = self.proof().workspace.as_ref().unwrap().visible_diagram();
+                let options = MeshOptions {
+                    subdivision_depth,
+                    samples,
+                    theme,
+                };
+                let data = ply::render(&diagram, signature, options).unwrap();
+                generate_download("homotopy_io_export", "ply", data.as_bytes())
+                    .map_err(ModelError::Export)?;
+            }
+
             Action::ExportActions => {
                 crate::panic::export_dump(true)?;
             }
@@ -199,13 +447,25 @@ impl State {
             Action::ExportProof => {
                 let data = serialize::serialize(
                     self.proof().signature.clone(),
-                    self.proof().workspace.clone(),
+                    self.proof().tabs(),
+                    self.proof().shelf.clone(),
                     self.proof().metadata.clone(),
                 );
                 generate_download("homotopy_io_export", "hom", data.as_slice())
                     .map_err(ModelError::Export)?;
             }
 
+            Action::ExportLibrary(nodes) => {
+                let data = serialize::serialize(
+                    self.proof().signature.library(&nodes),
+                    Default::default(),
+                    Default::default(),
+                    Default::default(),
+                );
+                generate_download("homotopy_io_library", "hom", data.as_slice())
+                    .map_err(ModelError::Export)?;
+            }
+
             Action::ImportActions(data) => {
                 let (safe, actions): (bool, Vec<proof::Action>) =
                     serde_json::from_slice(&data.0)
@@ -225,6 +485,8 @@ impl State {
                 }
             }
 
+            Action::OpenProof(data, actions) => self.open_proof(data, actions)?,
+
             Action::Select(index) => {
                 let action = match self.attach.as_ref() {
                     // Select a generator.
@@ -244,8 +506,13 @@ impl State {
                 self.update(Action::Proof(action))?;
             }
             Action::SelectPoint(point, weak_units) => self.select_point(&point, weak_units)?,
+            Action::SelectDiagram(candidate) => {
+                let matches = self.proof().attach_options(&candidate);
+                self.offer_attach(matches)?;
+            }
             Action::HighlightAttachment(option) => self.highlight_attachment(option),
             Action::HighlightSlice(slice) => self.highlight_slice(slice),
+            Action::Compare(steps) => self.compare(steps)?,
             Action::ClearAttach => self.clear_attach(),
             Action::Help => help()?,
         }
@@ -253,90 +520,46 @@ impl State {
         Ok(true)
     }
 
-    /// Handler for [Action::SelectPoint].
-    fn select_point(&mut self, point: &[SliceIndex], weak_units: bool) -> Result<(), ModelError> {
-        let Some(workspace) = self.proof().workspace.as_ref() else {
-            return Ok(());
-        };
-
-        let mut matches: Vector<AttachOption> = Default::default();
-
-        let point = {
-            let mut point_with_path: Vec<SliceIndex> = workspace.path.iter().copied().collect();
-            point_with_path.extend(point);
-            point_with_path
-        };
-
-        let (boundary_path, point) = BoundaryPath::split(&point);
-
-        let haystack = match boundary_path {
-            None => workspace.diagram.clone(),
-            Some(boundary_path) => DiagramN::try_from(workspace.diagram.clone())
-                .ok()
-                .and_then(|diagram| diagram.boundary(boundary_path))
-                .ok_or(ModelError::NoAttachment)?,
+    /// Handler for [Action::OpenProof].
+    fn open_proof(
+        &mut self,
+        data: proof::SerializedData,
+        actions: Option<proof::SerializedData>,
+    ) -> Result<(), ModelError> {
+        let replayed = actions
+            .and_then(|actions| {
+                serde_json::from_slice::<(bool, Vec<proof::Action>)>(&actions.0).ok()
+            })
+            .and_then(|(_, actions)| replay(&actions).ok());
+        self.history = match replayed {
+            Some(history) => history,
+            None => replay(&[proof::Action::ImportProof(data)])?,
         };
+        self.compare = None;
+        self.clear_attach();
 
-        let boundary = boundary_path.map_or(Boundary::Target, BoundaryPath::boundary);
-
-        for info in self.proof().signature.iter() {
-            macro_rules! extend {
-                ($diagram:expr, $tag:expr) => {
-                    let needle = $diagram.slice(boundary.flip()).unwrap();
-                    matches.extend(
-                        haystack
-                            .embeddings(&needle)
-                            .filter(|embedding| contains_point(&needle, &point, embedding))
-                            .map(|embedding| AttachOption {
-                                generator: info.generator,
-                                diagram: $diagram,
-                                tag: $tag,
-                                boundary_path,
-                                embedding: embedding.into_iter().collect(),
-                            }),
-                    );
-                };
-            }
-
-            match info.generator.dimension.cmp(&(haystack.dimension() + 1)) {
-                std::cmp::Ordering::Less => {
-                    if weak_units {
-                        let identity = |mut diagram: Diagram| {
-                            while diagram.dimension() < haystack.dimension() + 1 {
-                                diagram = diagram.weak_identity().into();
-                            }
-                            DiagramN::try_from(diagram).unwrap()
-                        };
-
-                        extend!(identity(info.diagram.clone()), Some("identity".to_owned()));
-                    }
+        crate::panic::clear_actions();
+        for action in self.history.get_last_import_segment() {
+            crate::panic::push_action(&action);
+        }
+        Ok(())
+    }
 
-                    if let Diagram::DiagramN(d) = &info.diagram {
-                        if info.invertible {
-                            let bubble = |mut diagram: DiagramN| {
-                                while diagram.dimension() < haystack.dimension() + 1 {
-                                    diagram = diagram.bubble();
-                                }
-                                diagram
-                            };
-
-                            extend!(bubble(d.clone()), Some("bubble".to_owned()));
-                            extend!(bubble(d.inverse()), Some("inverse bubble".to_owned()));
-                        }
-                    }
-                }
-                std::cmp::Ordering::Equal => {
-                    if let Diagram::DiagramN(d) = &info.diagram {
-                        extend!(d.clone(), None);
-                        if info.invertible {
-                            extend!(d.inverse(), Some("inverse".to_owned()));
-                        }
-                    }
-                }
-                std::cmp::Ordering::Greater => (),
-            }
+    /// Handler for [Action::SelectPoint].
+    fn select_point(&mut self, point: &[SliceIndex], weak_units: bool) -> Result<(), ModelError> {
+        if self.proof().workspace.is_none() {
+            return Ok(());
         }
 
+        let matches = self
+            .proof()
+            .attach_options_at(point, weak_units)
+            .ok_or(ModelError::NoAttachment)?;
+        self.offer_attach(matches)
+    }
+
+    /// Attach immediately if there is exactly one option, otherwise let the user choose.
+    fn offer_attach(&mut self, matches: Vector<AttachOption>) -> Result<(), ModelError> {
         match matches.len() {
             0 => {
                 self.clear_attach();
@@ -368,6 +591,17 @@ impl State {
         self.slice_highlight = option;
     }
 
+    /// Handler for [Action::Compare].
+    fn compare(&mut self, steps: Option<usize>) -> Result<(), ModelError> {
+        if let Some(steps) = steps {
+            self.history
+                .earlier(steps)
+                .ok_or(ModelError::IndexOutOfBounds)?;
+        }
+        self.compare = steps;
+        Ok(())
+    }
+
     /// Handler for [Action::ClearAttach].
     fn clear_attach(&mut self) {
         self.attach = None;
@@ -392,6 +626,18 @@ pub enum ModelError {
     IndexOutOfBounds,
 }
 
+/// A fresh history of the given actions performed one after the other on an empty proof.
+fn replay(actions: &[proof::Action]) -> Result<History, ModelError> {
+    let mut history = History::default();
+    let mut proof = history.proof().clone();
+    for action in actions {
+        if proof.update(action)? {
+            history.add(action.clone(), proof.clone());
+        }
+    }
+    Ok(history)
+}
+
 fn help() -> Result<(), ModelError> {
     let window = web_sys::window().ok_or(ModelError::Internal)?;
     let document = window.document().ok_or(ModelError::Internal)?;
@@ -422,27 +668,3 @@ pub fn generate_download(name: &str, ext: &str, data: &[u8]) -> Result<(), wasm_
     a.remove();
     web_sys::Url::revoke_object_url(&url)
 }
-
-fn contains_point(diagram: &Diagram, point: &[Height], embedding: &[RegularHeight]) -> bool {
-    use Diagram::{Diagram0, DiagramN};
-
-    match (point.split_first(), diagram) {
-        (None, _) => true,
-        (Some(_), Diagram0(_)) => false,
-        (Some((height, point)), DiagramN(diagram)) => {
-            let (shift, embedding) = embedding.split_first().unwrap_or((&0, &[]));
-            let shift = Height::Regular(*shift);
-
-            if usize::from(*height) < usize::from(shift) {
-                return false;
-            }
-
-            let height = Height::from(usize::from(*height) - usize::from(shift));
-
-            match diagram.slice(height) {
-                Some(slice) => contains_point(&slice, point, embedding),
-                None => false,
-            }
-        }
-    }
-}
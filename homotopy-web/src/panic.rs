@@ -1,6 +1,8 @@
 use std::{io::Write, sync::Mutex};
 
+use gloo::storage::{LocalStorage, Storage};
 use homotopy_model::proof;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use zip::write::{FileOptions, ZipWriter};
 
@@ -13,6 +15,9 @@ use crate::model::{generate_download, ModelError};
 pub fn panic_handler(info: &std::panic::PanicInfo<'_>) {
     display_panic_message();
 
+    // Keep enough to recover and report the crash when the app is next opened.
+    save_crash_report(&info.to_string());
+
     // This provides better error messages in debug mode.
     // It's disabled in release mode so it doesn't bloat up the file size.
     #[cfg(feature = "console_error_panic_hook")]
@@ -45,6 +50,11 @@ impl CrashDump {
         }
     }
 
+    fn clear(&mut self) {
+        self.actions.clear();
+        self.import = None;
+    }
+
     fn pop_action(&mut self) -> bool {
         match (self.actions.pop().is_some(), self.import.is_some()) {
             (true, _) => true,
@@ -61,49 +71,50 @@ impl CrashDump {
     }
 
     fn get_dump(&self, safe: bool) -> Option<Vec<u8>> {
-        let mut actions: Vec<u8> = Vec::new();
-        if safe {
-            actions.extend(b"[true,[");
-        } else {
-            actions.extend(b"[false,[");
-        }
-        actions.extend(self.actions.join(",").as_bytes());
-        actions.extend(b"]]");
-
+        let actions = action_dump(&self.actions, safe);
         if let Some(ibuf) = &self.import {
-            let mut buf: Vec<u8> = vec![0; ibuf.len() + actions.len()];
-            let size = {
-                let mut zip = ZipWriter::new(std::io::Cursor::new(&mut buf[..]));
-                let options =
-                    FileOptions::default().compression_method(zip::CompressionMethod::DEFLATE);
-
-                zip.start_file("crash_last_import.hom", options).ok()?;
-                let mut j = 0;
-                while j < ibuf.len() {
-                    j += zip.write(&ibuf[j..]).ok()?;
-                }
-
-                zip.start_file("crash_action_dump.txt", options).ok()?;
-
-                let mut j = 0;
-                while j < actions.len() {
-                    j += zip.write(&actions[j..]).ok()?;
-                }
-                zip.flush().ok()?;
-                zip.finish().ok()?.position()
-            };
-            buf.resize(size as usize, 0);
-            Some(buf)
+            zip(&[
+                ("crash_last_import.hom", ibuf.as_slice()),
+                ("crash_action_dump.txt", actions.as_slice()),
+            ])
         } else {
             Some(actions)
         }
     }
 }
 
+/// Actions in the format read by the `--input-actions` option of homotopy-cli.
+fn action_dump(actions: &[String], safe: bool) -> Vec<u8> {
+    let mut dump: Vec<u8> = Vec::new();
+    if safe {
+        dump.extend(b"[true,[");
+    } else {
+        dump.extend(b"[false,[");
+    }
+    dump.extend(actions.join(",").as_bytes());
+    dump.extend(b"]]");
+    dump
+}
+
+fn zip(files: &[(&str, &[u8])]) -> Option<Vec<u8>> {
+    let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::DEFLATE);
+    for (name, data) in files {
+        zip.start_file(*name, options).ok()?;
+        zip.write_all(data).ok()?;
+    }
+    Some(zip.finish().ok()?.into_inner())
+}
+
 pub fn push_action(action: &proof::Action) {
     CRASH_INFO.lock().unwrap().push_action(action);
 }
 
+/// Forget the actions so far, as when the history is replaced.
+pub fn clear_actions() {
+    CRASH_INFO.lock().unwrap().clear();
+}
+
 pub fn pop_action() -> bool {
     CRASH_INFO.lock().unwrap().pop_action()
 }
@@ -125,6 +136,110 @@ pub fn export_dump(safe: bool) -> Result<(), ModelError> {
     }
 }
 
+/// The key under which the last crash is saved in local storage.
+const CRASH_KEY: &str = "homotopy-io:crash";
+
+/// The state of the app when it last crashed, saved in local storage so that the next session can
+/// recover the proof and help to report the bug.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub message: String,
+    pub version: String,
+    /// When the crash happened, in milliseconds since the epoch.
+    pub time: f64,
+    /// The proof before the action that crashed, base64-encoded.
+    proof: Option<String>,
+    /// The last imported proof, base64-encoded, which the actions are replayed on.
+    import: Option<String>,
+    /// The actions since the last import, the last one of which crashed.
+    actions: Vec<String>,
+}
+
+impl CrashReport {
+    pub fn can_recover(&self) -> bool {
+        self.proof.is_some()
+    }
+
+    pub fn proof(&self) -> Option<Vec<u8>> {
+        decode(self.proof.as_ref()?)
+    }
+
+    /// A zip of everything needed to reproduce the crash with homotopy-cli.
+    fn bundle(&self) -> Option<Vec<u8>> {
+        let summary = format!(
+            "{}\n\nVersion: {}\nDate: {}\nBrowser: {}\n",
+            self.message,
+            self.version,
+            String::from(js_sys::Date::new(&self.time.into()).to_iso_string()),
+            web_sys::window()
+                .and_then(|window| window.navigator().user_agent().ok())
+                .unwrap_or_default(),
+        );
+        let actions = action_dump(&self.actions, false);
+        let import = self.import.as_deref().and_then(decode);
+        let proof = self.proof();
+
+        let mut files: Vec<(&str, &[u8])> = vec![
+            ("crash_report.txt", summary.as_bytes()),
+            ("crash_action_dump.txt", actions.as_slice()),
+        ];
+        if let Some(import) = &import {
+            files.push(("crash_last_import.hom", import.as_slice()));
+        }
+        if let Some(proof) = &proof {
+            files.push(("crash_proof.hom", proof.as_slice()));
+        }
+        zip(&files)
+    }
+}
+
+fn save_crash_report(message: &str) {
+    // The panic may have happened while the crash info was locked.
+    let Ok(dump) = CRASH_INFO.try_lock() else { return };
+    let report = CrashReport {
+        message: message.to_owned(),
+        version: option_env!("GIT_DESCRIBE")
+            .unwrap_or(env!("CARGO_PKG_VERSION"))
+            .to_owned(),
+        time: js_sys::Date::now(),
+        proof: crate::app::projects::latest().as_deref().and_then(encode),
+        import: dump.import.as_deref().and_then(encode),
+        actions: dump.actions.clone(),
+    };
+    if let Err(error) = LocalStorage::set(CRASH_KEY, &report) {
+        tracing::error!("Failed to save crash report: {error}");
+    }
+}
+
+/// The report of a crash in an earlier session, if it has not been dismissed yet.
+pub fn crash_report() -> Option<CrashReport> {
+    LocalStorage::get(CRASH_KEY).ok()
+}
+
+pub fn dismiss_crash_report() {
+    LocalStorage::delete(CRASH_KEY);
+}
+
+pub fn export_crash_report(report: &CrashReport) -> Result<(), ModelError> {
+    let data = report.bundle().ok_or(ModelError::Internal)?;
+    generate_download("homotopy_io_bug_report", "zip", &data).map_err(ModelError::Export)
+}
+
+// Local storage only holds strings, so binary data is kept in base64.
+fn encode(data: &[u8]) -> Option<String> {
+    let binary: String = data.iter().copied().map(char::from).collect();
+    web_sys::window()?.btoa(&binary).ok()
+}
+
+fn decode(data: &str) -> Option<Vec<u8>> {
+    web_sys::window()?
+        .atob(data)
+        .ok()?
+        .chars()
+        .map(|c| u8::try_from(c).ok())
+        .collect()
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen]
@@ -1,5 +1,14 @@
-use homotopy_model::proof::{Action::ImportProof, SerializedData};
-use homotopy_web::model::{Action::Proof, State};
+use homotopy_model::{
+    proof::{
+        Action::{CreateGeneratorZero, ImportProof},
+        SerializedData,
+    },
+    serialize,
+};
+use homotopy_web::model::{
+    Action::{OpenProof, Proof},
+    State,
+};
 
 #[test]
 #[ignore]
@@ -20,3 +29,39 @@ fn action() {
         .update(unimplemented!("trigger action goes here"))
         .expect("failed to trigger action");
 }
+
+#[test]
+fn open_proof() {
+    let mut saved = State::default();
+    saved.update(Proof(CreateGeneratorZero)).unwrap();
+    saved.update(Proof(CreateGeneratorZero)).unwrap();
+    let proof = saved.proof();
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.tabs(),
+        proof.shelf.clone(),
+        proof.metadata.clone(),
+    );
+    let actions = serde_json::to_vec(&(true, saved.history.get_last_import_segment())).unwrap();
+
+    // The actions replace the history of whatever was open before.
+    let mut state = State::default();
+    state.update(Proof(CreateGeneratorZero)).unwrap();
+    state
+        .update(OpenProof(data.clone().into(), Some(actions.into())))
+        .unwrap();
+    assert_eq!(state.history.get_actions(), saved.history.get_actions());
+    assert_eq!(state.history.depth(), 2);
+    assert_eq!(state.proof().signature.iter().count(), 2);
+
+    // Without actions, the proof is imported into a history of its own.
+    let mut state = State::default();
+    state.update(Proof(CreateGeneratorZero)).unwrap();
+    state.update(OpenProof(data.into(), None)).unwrap();
+    assert!(matches!(
+        state.history.get_actions().as_slice(),
+        [ImportProof(_)]
+    ));
+    assert_eq!(state.history.depth(), 1);
+    assert_eq!(state.proof().signature.iter().count(), 2);
+}
@@ -0,0 +1,45 @@
+use euclid::default::Vector2D;
+use homotopy_web::gesture::{self, Gesture, LONG_PRESS, SELECT_PRESSURE};
+
+#[test]
+fn press() {
+    let still = Vector2D::new(1.0, 1.0);
+    let moved = Vector2D::new(0.0, 10.0);
+
+    assert_eq!(gesture::press(still, 0, 5.0), Gesture::Undecided);
+    assert_eq!(
+        gesture::press(still, LONG_PRESS - 1, 5.0),
+        Gesture::Undecided
+    );
+    assert_eq!(gesture::press(still, LONG_PRESS, 5.0), Gesture::Select);
+    // Moving far enough drags however long the finger rested.
+    assert_eq!(gesture::press(moved, 0, 5.0), Gesture::Drag(moved));
+    assert_eq!(gesture::press(moved, LONG_PRESS, 5.0), Gesture::Drag(moved));
+}
+
+#[test]
+fn tap() {
+    assert_eq!(gesture::tap(None), Gesture::Select);
+    assert_eq!(gesture::tap(Some(0.5)), Gesture::Select);
+    assert_eq!(gesture::tap(Some(SELECT_PRESSURE)), Gesture::Select);
+    assert_eq!(gesture::tap(Some(0.1)), Gesture::Undecided);
+}
+
+#[test]
+fn two_finger() {
+    let up = Vector2D::new(0.0, -10.0);
+    let slightly_left = Vector2D::new(-2.0, -10.0);
+    let down = Vector2D::new(0.0, 10.0);
+    let right = Vector2D::new(10.0, 0.0);
+    let short = Vector2D::new(0.0, -1.0);
+
+    assert_eq!(
+        gesture::two_finger([up, slightly_left], 5.0),
+        Gesture::Drag(up + slightly_left)
+    );
+    // Fingers moving apart or at right angles zoom.
+    assert_eq!(gesture::two_finger([up, down], 5.0), Gesture::Pinch);
+    assert_eq!(gesture::two_finger([up, right], 5.0), Gesture::Pinch);
+    // Both fingers have to move far enough.
+    assert_eq!(gesture::two_finger([up, short], 5.0), Gesture::Undecided);
+}